@@ -48,8 +48,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     // Configure room options with encryption
     let mut room_options = RoomOptions::default();
-    room_options.encryption =
-        Some(E2eeOptions { key_provider, encryption_type: livekit::e2ee::EncryptionType::Gcm });
+    room_options.encryption = Some(E2eeOptions {
+        key_provider,
+        encryption_type: livekit::e2ee::EncryptionType::Gcm,
+        frame_transformer: None,
+    });
 
     // Connect to room
     let (room, rx) = Room::connect(&url, &token, room_options).await?;
@@ -93,8 +93,11 @@ async fn service_task(inner: Arc<ServiceInner>, mut cmd_rx: mpsc::UnboundedRecei
 
                 let key_provider =
                     KeyProvider::with_shared_key(KeyProviderOptions::default(), key.into_bytes());
-                let e2ee = enable_e2ee
-                    .then_some(E2eeOptions { encryption_type: EncryptionType::Gcm, key_provider });
+                let e2ee = enable_e2ee.then_some(E2eeOptions {
+                    encryption_type: EncryptionType::Gcm,
+                    key_provider,
+                    frame_transformer: None,
+                });
 
                 let mut options = RoomOptions::default();
                 options.auto_subscribe = auto_subscribe;
@@ -0,0 +1,101 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares allocating a buffer per frame with recycling them from a pool.
+//!
+//! cargo bench -p libwebrtc --bench buffer_pool
+
+use std::{
+    collections::VecDeque,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use libwebrtc::video_frame::{I420Buffer, I420BufferPool, NV12Buffer, NV12BufferPool};
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+const FRAMES: usize = 2000;
+// Frames kept alive at the same time, like the ones queued inside the WebRTC pipeline
+const IN_FLIGHT: usize = 4;
+
+struct Stats {
+    total: Duration,
+    worst: Duration,
+}
+
+fn run<T>(mut next: impl FnMut() -> T) -> Stats {
+    let mut in_flight = VecDeque::with_capacity(IN_FLIGHT);
+    let mut worst = Duration::ZERO;
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        let frame_start = Instant::now();
+        in_flight.push_back(black_box(next()));
+        if in_flight.len() > IN_FLIGHT {
+            in_flight.pop_front();
+        }
+        worst = worst.max(frame_start.elapsed());
+    }
+    Stats { total: start.elapsed(), worst }
+}
+
+fn report(name: &str, stats: Stats) {
+    println!(
+        "{name:<12} {:>8.2} us/frame, worst {:>8.2} us",
+        stats.total.as_secs_f64() * 1e6 / FRAMES as f64,
+        stats.worst.as_secs_f64() * 1e6
+    );
+}
+
+fn main() {
+    println!("{FRAMES} frames of {WIDTH}x{HEIGHT}, {IN_FLIGHT} in flight");
+
+    report(
+        "i420 alloc",
+        run(|| {
+            let mut buffer = I420Buffer::new(WIDTH, HEIGHT);
+            buffer.data_mut().0[0] = 1;
+            buffer
+        }),
+    );
+
+    let pool = I420BufferPool::new(WIDTH, HEIGHT, IN_FLIGHT + 1);
+    report(
+        "i420 pool",
+        run(|| {
+            let mut buffer = pool.acquire().unwrap();
+            buffer.data_mut().0[0] = 1;
+            buffer
+        }),
+    );
+
+    report(
+        "nv12 alloc",
+        run(|| {
+            let mut buffer = NV12Buffer::new(WIDTH, HEIGHT);
+            buffer.data_mut().0[0] = 1;
+            buffer
+        }),
+    );
+
+    let pool = NV12BufferPool::new(WIDTH, HEIGHT, IN_FLIGHT + 1);
+    report(
+        "nv12 pool",
+        run(|| {
+            let mut buffer = pool.acquire().unwrap();
+            buffer.data_mut().0[0] = 1;
+            buffer
+        }),
+    );
+}
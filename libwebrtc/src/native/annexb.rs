@@ -0,0 +1,184 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splits H.264/H.265 Annex-B byte streams (e.g. raw `.h264` files) into access units,
+//! ready to be pushed into an [`EncodedVideoSource`](crate::video_source::native::EncodedVideoSource).
+
+use crate::video_source::native::EncodedVideoCodec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessUnit<'a> {
+    /// NAL units of the frame, including their start codes
+    pub data: &'a [u8],
+    pub is_keyframe: bool,
+}
+
+/// Returns the NAL units of `data` as (offset of the start code, offset of the payload).
+fn nal_units(data: &[u8]) -> Vec<(usize, usize)> {
+    let mut units = Vec::new();
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            let start = if i > 0 && data[i - 1] == 0 { i - 1 } else { i };
+            units.push((start, i + 3));
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    units
+}
+
+enum NalKind {
+    /// Parameter sets, SEI, AUD: they always start a new access unit
+    Prefix,
+    /// A slice, `first` if it is the first slice of a picture
+    Slice {
+        first: bool,
+        keyframe: bool,
+    },
+    Other,
+}
+
+fn nal_kind(codec: EncodedVideoCodec, nal: &[u8]) -> NalKind {
+    match codec {
+        EncodedVideoCodec::H264 => {
+            let Some(&header) = nal.first() else {
+                return NalKind::Other;
+            };
+            match header & 0x1f {
+                // first_mb_in_slice == 0 is coded as a single 1 bit
+                t @ (1 | 5) => NalKind::Slice {
+                    first: nal.get(1).is_some_and(|b| b & 0x80 != 0),
+                    keyframe: t == 5,
+                },
+                6..=9 => NalKind::Prefix,
+                _ => NalKind::Other,
+            }
+        }
+        EncodedVideoCodec::H265 => {
+            let Some(&header) = nal.first() else {
+                return NalKind::Other;
+            };
+            match (header >> 1) & 0x3f {
+                // first_slice_segment_in_pic_flag follows the two bytes header
+                t @ 0..=31 => NalKind::Slice {
+                    first: nal.get(2).is_some_and(|b| b & 0x80 != 0),
+                    keyframe: (16..=23).contains(&t),
+                },
+                32..=35 | 39 => NalKind::Prefix,
+                _ => NalKind::Other,
+            }
+        }
+    }
+}
+
+/// Groups the NAL units of an Annex-B byte stream into access units.
+///
+/// Bytes before the first start code are ignored.
+///
+pub fn split_access_units(codec: EncodedVideoCodec, data: &[u8]) -> Vec<AccessUnit<'_>> {
+    let units = nal_units(data);
+    let mut access_units = Vec::new();
+
+    let mut au_start = None;
+    let mut has_slice = false;
+    let mut is_keyframe = false;
+    for (index, &(start, payload)) in units.iter().enumerate() {
+        let end = units.get(index + 1).map_or(data.len(), |&(next, _)| next);
+        let kind = nal_kind(codec, &data[payload..end]);
+
+        let new_au = match kind {
+            NalKind::Prefix => has_slice,
+            NalKind::Slice { first, .. } => has_slice && first,
+            NalKind::Other => false,
+        };
+        if new_au {
+            if let Some(au_start) = au_start {
+                access_units.push(AccessUnit { data: &data[au_start..start], is_keyframe });
+            }
+            au_start = None;
+            has_slice = false;
+            is_keyframe = false;
+        }
+
+        au_start.get_or_insert(start);
+        if let NalKind::Slice { keyframe, .. } = kind {
+            has_slice = true;
+            is_keyframe |= keyframe;
+        }
+    }
+
+    if let Some(au_start) = au_start {
+        access_units.push(AccessUnit { data: &data[au_start..], is_keyframe });
+    }
+    access_units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nal(header: &[u8], payload: &[u8]) -> Vec<u8> {
+        [&[0, 0, 0, 1], header, payload].concat()
+    }
+
+    #[test]
+    fn split_h264() {
+        let sps = nal(&[0x67], &[0x42, 0xc0, 0x1e]);
+        let pps = nal(&[0x68], &[0xce]);
+        let idr = [nal(&[0x65], &[0x88, 0x84]), nal(&[0x65], &[0x40, 0x01])].concat();
+        let p1 = nal(&[0x41], &[0x9a, 0x00]);
+        let p2 = [nal(&[0x41], &[0x9a, 0x01]), nal(&[0x41], &[0x20])].concat();
+        let stream = [sps.as_slice(), &pps, &idr, &p1, &p2].concat();
+
+        let access_units = split_access_units(EncodedVideoCodec::H264, &stream);
+        assert_eq!(
+            access_units,
+            vec![
+                AccessUnit { data: &[sps, pps, idr].concat(), is_keyframe: true },
+                AccessUnit { data: &p1, is_keyframe: false },
+                AccessUnit { data: &p2, is_keyframe: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_h265() {
+        let vps = nal(&[0x40, 0x01], &[0x0c]);
+        let sps = nal(&[0x42, 0x01], &[0x01]);
+        let pps = nal(&[0x44, 0x01], &[0xc1]);
+        let idr = nal(&[0x26, 0x01], &[0xaf, 0x00]);
+        let trail = [nal(&[0x02, 0x01], &[0xd0]), nal(&[0x02, 0x01], &[0x10])].concat();
+        let stream = [vps.as_slice(), &sps, &pps, &idr, &trail].concat();
+
+        let access_units = split_access_units(EncodedVideoCodec::H265, &stream);
+        assert_eq!(
+            access_units,
+            vec![
+                AccessUnit { data: &[vps, sps, pps, idr].concat(), is_keyframe: true },
+                AccessUnit { data: &trail, is_keyframe: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn three_bytes_start_code() {
+        let stream = [0xff, 0, 0, 1, 0x65, 0x80, 0, 0, 1, 0x41, 0x80];
+        let access_units = split_access_units(EncodedVideoCodec::H264, &stream);
+        assert_eq!(access_units.len(), 2);
+        assert_eq!(access_units[0].data, &[0, 0, 1, 0x65, 0x80]);
+        assert!(access_units[0].is_keyframe);
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime selection of the video codec backends, e.g. to fall back to software codecs when a
+//! hardware codec misbehaves.
+//!
+//! The options can be set with [`set_video_codec_backend_options`] or with the
+//! `LK_VIDEO_CODEC_BACKENDS` environment variable, which takes precedence. They apply to the
+//! encoders and decoders created afterwards, so they are usually set before connecting.
+//!
+//! The environment variable contains `codec=backends` entries separated by `;`. The codec is
+//! `vp8`, `vp9`, `av1`, `h264`, `h265` or `*` for every codec. Backends are listed by order of
+//! preference, a backend prefixed by `-` is disabled. E.g. `h264=-rockchip-mpp;*=nvidia`.
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+use webrtc_sys::{
+    encoded_video_sink::ffi::VideoCodecType as SysVideoCodecType, video_codec_backend as sys_vcb,
+};
+
+use super::codec_support::{codec_type, CodecDirection, SdpVideoFormat, VideoCodecType};
+
+pub const VIDEO_CODEC_BACKENDS_ENV: &str = "LK_VIDEO_CODEC_BACKENDS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoCodecBackend {
+    /// Factories registered with `register_video_encoder_factory` or
+    /// `register_video_decoder_factory`
+    Rust,
+    /// VideoToolbox on Apple platforms, MediaCodec on Android
+    Platform,
+    Nvidia,
+    Vaapi,
+    RockchipMpp,
+    /// libvpx, OpenH264, libaom and dav1d
+    Software,
+}
+
+impl VideoCodecBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Platform => "platform",
+            Self::Nvidia => "nvidia",
+            Self::Vaapi => "vaapi",
+            Self::RockchipMpp => "rockchip-mpp",
+            Self::Software => "software",
+        }
+    }
+}
+
+impl fmt::Display for VideoCodecBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VideoCodecBackend {
+    type Err = CodecBackendParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "rust" => Self::Rust,
+            "platform" => Self::Platform,
+            "nvidia" => Self::Nvidia,
+            "vaapi" => Self::Vaapi,
+            "rockchip-mpp" => Self::RockchipMpp,
+            "software" => Self::Software,
+            _ => Err(CodecBackendParseError::UnknownBackend(s.to_owned()))?,
+        })
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CodecBackendParseError {
+    #[error("unknown video codec backend: {0}")]
+    UnknownBackend(String),
+    #[error("unknown video codec: {0}")]
+    UnknownCodec(String),
+    #[error("invalid entry: {0}, expected codec=backends")]
+    InvalidEntry(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodecBackendPreference {
+    /// None applies to every codec, after the preferences of the codec itself
+    pub codec: Option<VideoCodecType>,
+    /// Tried first, in this order. The other backends keep their default order.
+    pub preferred: Vec<VideoCodecBackend>,
+    pub disabled: Vec<VideoCodecBackend>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VideoCodecBackendOptions {
+    pub preferences: Vec<CodecBackendPreference>,
+}
+
+impl VideoCodecBackendOptions {
+    /// Parses the format of the `LK_VIDEO_CODEC_BACKENDS` environment variable
+    pub fn parse(value: &str) -> Result<Self, CodecBackendParseError> {
+        let mut preferences = Vec::new();
+        for entry in value.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (codec, backends) = entry
+                .split_once('=')
+                .ok_or_else(|| CodecBackendParseError::InvalidEntry(entry.to_owned()))?;
+
+            let codec = match codec.trim() {
+                "*" => None,
+                name => match codec_type(name) {
+                    VideoCodecType::Generic => {
+                        Err(CodecBackendParseError::UnknownCodec(name.to_owned()))?
+                    }
+                    codec => Some(codec),
+                },
+            };
+
+            let mut preference = CodecBackendPreference { codec, ..Default::default() };
+            for backend in backends.split(',').map(str::trim).filter(|b| !b.is_empty()) {
+                match backend.strip_prefix('-') {
+                    Some(backend) => preference.disabled.push(backend.parse()?),
+                    None => preference.preferred.push(backend.parse()?),
+                }
+            }
+            preferences.push(preference);
+        }
+        Ok(Self { preferences })
+    }
+
+    fn rules(&self) -> Vec<sys_vcb::ffi::VideoCodecBackendRule> {
+        let mut rules = Vec::new();
+        for preference in &self.preferences {
+            let codec = preference.codec.map_or(SysVideoCodecType::Generic, Into::into);
+            let preferred = preference.preferred.iter().map(|backend| (backend, false));
+            let disabled = preference.disabled.iter().map(|backend| (backend, true));
+            rules.extend(preferred.chain(disabled).map(|(backend, disabled)| {
+                sys_vcb::ffi::VideoCodecBackendRule { codec, backend: (*backend).into(), disabled }
+            }));
+        }
+        rules
+    }
+}
+
+/// Sets the preferred and disabled backends, replacing the previous options.
+///
+/// It is ignored when the `LK_VIDEO_CODEC_BACKENDS` environment variable is set.
+///
+pub fn set_video_codec_backend_options(options: &VideoCodecBackendOptions) {
+    if std::env::var_os(VIDEO_CODEC_BACKENDS_ENV).is_some() {
+        log::warn!("{} is set, ignoring the video codec backend options", VIDEO_CODEC_BACKENDS_ENV);
+        return;
+    }
+    sys_vcb::ffi::set_video_codec_backend_rules(options.rules());
+}
+
+/// Applies the options of the environment variable, if set
+pub(crate) fn apply_env_options() {
+    let Ok(value) = std::env::var(VIDEO_CODEC_BACKENDS_ENV) else {
+        return;
+    };
+    match VideoCodecBackendOptions::parse(&value) {
+        Ok(options) => {
+            log::info!("using video codec backends from {}: {}", VIDEO_CODEC_BACKENDS_ENV, value);
+            sys_vcb::ffi::set_video_codec_backend_rules(options.rules());
+        }
+        Err(err) => log::error!("invalid {}: {}", VIDEO_CODEC_BACKENDS_ENV, err),
+    }
+}
+
+/// The backends tried, in order, to encode or decode a format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoCodecBackends {
+    pub codec: VideoCodecType,
+    pub direction: CodecDirection,
+    pub format: SdpVideoFormat,
+    pub backends: Vec<VideoCodecBackend>,
+}
+
+pub(crate) fn group_backend_entries(
+    entries: Vec<sys_vcb::ffi::VideoCodecBackendEntry>,
+) -> Vec<VideoCodecBackends> {
+    let mut groups: Vec<VideoCodecBackends> = Vec::new();
+    for entry in entries {
+        let direction = if entry.encoder { CodecDirection::Encode } else { CodecDirection::Decode };
+        let format = SdpVideoFormat::from(entry.format);
+        match groups.last_mut() {
+            Some(group) if group.direction == direction && group.format == format => {
+                group.backends.push(entry.backend.into());
+            }
+            _ => groups.push(VideoCodecBackends {
+                codec: codec_type(&format.name),
+                direction,
+                format,
+                backends: vec![entry.backend.into()],
+            }),
+        }
+    }
+    groups
+}
+
+impl From<VideoCodecBackend> for sys_vcb::ffi::VideoCodecBackend {
+    fn from(backend: VideoCodecBackend) -> Self {
+        match backend {
+            VideoCodecBackend::Rust => Self::Rust,
+            VideoCodecBackend::Platform => Self::Platform,
+            VideoCodecBackend::Nvidia => Self::Nvidia,
+            VideoCodecBackend::Vaapi => Self::Vaapi,
+            VideoCodecBackend::RockchipMpp => Self::RockchipMpp,
+            VideoCodecBackend::Software => Self::Software,
+        }
+    }
+}
+
+impl From<sys_vcb::ffi::VideoCodecBackend> for VideoCodecBackend {
+    fn from(backend: sys_vcb::ffi::VideoCodecBackend) -> Self {
+        match backend {
+            sys_vcb::ffi::VideoCodecBackend::Rust => Self::Rust,
+            sys_vcb::ffi::VideoCodecBackend::Platform => Self::Platform,
+            sys_vcb::ffi::VideoCodecBackend::Nvidia => Self::Nvidia,
+            sys_vcb::ffi::VideoCodecBackend::Vaapi => Self::Vaapi,
+            sys_vcb::ffi::VideoCodecBackend::RockchipMpp => Self::RockchipMpp,
+            _ => Self::Software,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_options() {
+        let options =
+            VideoCodecBackendOptions::parse("h264=-rockchip-mpp, software; *=nvidia").unwrap();
+        assert_eq!(
+            options.preferences,
+            vec![
+                CodecBackendPreference {
+                    codec: Some(VideoCodecType::H264),
+                    preferred: vec![VideoCodecBackend::Software],
+                    disabled: vec![VideoCodecBackend::RockchipMpp],
+                },
+                CodecBackendPreference {
+                    codec: None,
+                    preferred: vec![VideoCodecBackend::Nvidia],
+                    disabled: vec![],
+                },
+            ]
+        );
+
+        let rules = options.rules();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].backend, sys_vcb::ffi::VideoCodecBackend::Software);
+        assert!(rules[1].disabled);
+        assert_eq!(rules[2].codec, SysVideoCodecType::Generic);
+
+        assert_eq!(VideoCodecBackendOptions::parse("").unwrap(), Default::default());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            VideoCodecBackendOptions::parse("h264=cuda"),
+            Err(CodecBackendParseError::UnknownBackend("cuda".into()))
+        );
+        assert_eq!(
+            VideoCodecBackendOptions::parse("mjpeg=software"),
+            Err(CodecBackendParseError::UnknownCodec("mjpeg".into()))
+        );
+        assert!(matches!(
+            VideoCodecBackendOptions::parse("software"),
+            Err(CodecBackendParseError::InvalidEntry(_))
+        ));
+    }
+}
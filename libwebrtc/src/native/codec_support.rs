@@ -0,0 +1,196 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Video codec implementations available to a `PeerConnectionFactory`.
+
+use webrtc_sys::peer_connection_factory as sys_pcf;
+
+pub use super::video_encoder::{SdpVideoFormat, VideoCodecType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodecDirection {
+    Encode,
+    Decode,
+}
+
+/// The implementation used to encode or decode a format, e.g. OpenH264 or a hardware codec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoCodecSupport {
+    pub codec: VideoCodecType,
+    pub direction: CodecDirection,
+    /// Format as negotiated in the SDP
+    pub format: SdpVideoFormat,
+    pub implementation_name: String,
+    pub is_hardware_accelerated: bool,
+    pub is_power_efficient: bool,
+    /// e.g. "constrained-baseline" for H.264, "0" or "2" for VP9, "main" for AV1
+    pub profile: Option<String>,
+    /// Maximum level, e.g. "3.1"
+    pub level: Option<String>,
+}
+
+impl VideoCodecSupport {
+    pub(crate) fn new(
+        direction: CodecDirection,
+        implementation: sys_pcf::ffi::VideoCodecImplementation,
+    ) -> Self {
+        let format = SdpVideoFormat::from(implementation.format);
+        let codec = codec_type(&format.name);
+        let (profile, level) = profile_level(codec, &format);
+        Self {
+            codec,
+            direction,
+            format,
+            implementation_name: implementation.implementation_name,
+            is_hardware_accelerated: implementation.is_hardware_accelerated,
+            is_power_efficient: implementation.is_power_efficient,
+            profile,
+            level,
+        }
+    }
+}
+
+pub(crate) fn codec_type(name: &str) -> VideoCodecType {
+    match name.to_ascii_uppercase().as_str() {
+        "VP8" => VideoCodecType::VP8,
+        "VP9" => VideoCodecType::VP9,
+        "AV1" => VideoCodecType::AV1,
+        "H264" => VideoCodecType::H264,
+        "H265" => VideoCodecType::H265,
+        _ => VideoCodecType::Generic,
+    }
+}
+
+/// Profile and level of a format, using the defaults of the RTP payload formats when the
+/// fmtp parameters are missing.
+fn profile_level(
+    codec: VideoCodecType,
+    format: &SdpVideoFormat,
+) -> (Option<String>, Option<String>) {
+    let parameter = |key: &str| format.parameters.get(key).map(String::as_str);
+    match codec {
+        VideoCodecType::H264 => parameter("profile-level-id")
+            .and_then(h264_profile_level)
+            .map_or((None, None), |(profile, level)| (Some(profile.to_owned()), Some(level))),
+        VideoCodecType::H265 => {
+            let profile = match parameter("profile-id").unwrap_or("1") {
+                "1" => "main".to_owned(),
+                "2" => "main-10".to_owned(),
+                profile => profile.to_owned(),
+            };
+            let level = parameter("level-id")
+                .unwrap_or("93")
+                .parse::<u32>()
+                .ok()
+                .map(|level| format!("{}.{}", level / 30, level % 30 / 3));
+            (Some(profile), level)
+        }
+        VideoCodecType::VP9 => (Some(parameter("profile-id").unwrap_or("0").to_owned()), None),
+        VideoCodecType::AV1 => {
+            let profile = match parameter("profile").unwrap_or("0") {
+                "0" => "main".to_owned(),
+                "1" => "high".to_owned(),
+                "2" => "professional".to_owned(),
+                profile => profile.to_owned(),
+            };
+            let level = parameter("level-idx")
+                .unwrap_or("5")
+                .parse::<u32>()
+                .ok()
+                .map(|idx| format!("{}.{}", 2 + (idx >> 2), idx & 3));
+            (Some(profile), level)
+        }
+        VideoCodecType::VP8 | VideoCodecType::Generic => (None, None),
+    }
+}
+
+/// Parses a H.264 profile-level-id (RFC 6184), e.g. "42e01f" is constrained baseline 3.1
+fn h264_profile_level(profile_level_id: &str) -> Option<(&'static str, String)> {
+    let value =
+        u32::from_str_radix(profile_level_id, 16).ok().filter(|_| profile_level_id.len() == 6)?;
+    let profile_idc = (value >> 16) as u8;
+    let profile_iop = (value >> 8) as u8;
+    let level_idc = value as u8;
+
+    // Same patterns as WebRTC, "x" bits of profile-iop are ignored
+    const PATTERNS: [(u8, &str, &str); 9] = [
+        (0x42, "x1xx0000", "constrained-baseline"),
+        (0x4d, "1xxx0000", "constrained-baseline"),
+        (0x58, "11xx0000", "constrained-baseline"),
+        (0x42, "x0xx0000", "baseline"),
+        (0x58, "10xx0000", "baseline"),
+        (0x4d, "0x0x0000", "main"),
+        (0x64, "00000000", "high"),
+        (0x64, "00001100", "constrained-high"),
+        (0xf4, "00000000", "predictive-high-444"),
+    ];
+    let profile = PATTERNS.iter().find_map(|(idc, pattern, profile)| {
+        let matches = pattern.bytes().enumerate().all(|(i, bit)| {
+            let value = (profile_iop >> (7 - i)) & 1;
+            bit == b'x' || u8::from(bit == b'1') == value
+        });
+        (*idc == profile_idc && matches).then_some(*profile)
+    })?;
+
+    // Level 1b is signaled with constraint_set3_flag on baseline and main profiles
+    let level = if level_idc == 11 && profile_iop & 0x10 != 0 && profile_idc != 0x64 {
+        "1b".to_owned()
+    } else {
+        format!("{}.{}", level_idc / 10, level_idc % 10)
+    };
+    Some((profile, level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(name: &str, parameters: &[(&str, &str)]) -> SdpVideoFormat {
+        SdpVideoFormat {
+            name: name.to_owned(),
+            parameters: parameters.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn h264_profiles() {
+        assert_eq!(h264_profile_level("42e01f"), Some(("constrained-baseline", "3.1".into())));
+        assert_eq!(h264_profile_level("42001f"), Some(("baseline", "3.1".into())));
+        assert_eq!(h264_profile_level("4d0032"), Some(("main", "5.0".into())));
+        assert_eq!(h264_profile_level("640c34"), Some(("constrained-high", "5.2".into())));
+        assert_eq!(h264_profile_level("64001f"), Some(("high", "3.1".into())));
+        assert_eq!(h264_profile_level("42f00b"), Some(("constrained-baseline", "1b".into())));
+        assert_eq!(h264_profile_level("ff001f"), None);
+        assert_eq!(h264_profile_level("42e0"), None);
+    }
+
+    #[test]
+    fn profile_levels() {
+        let (profile, level) =
+            profile_level(VideoCodecType::VP9, &format("VP9", &[("profile-id", "2")]));
+        assert_eq!((profile.as_deref(), level), (Some("2"), None));
+
+        let (profile, level) = profile_level(VideoCodecType::AV1, &format("AV1", &[]));
+        assert_eq!((profile.as_deref(), level.as_deref()), (Some("main"), Some("3.1")));
+
+        let (profile, level) = profile_level(
+            VideoCodecType::H265,
+            &format("H265", &[("profile-id", "1"), ("level-id", "120")]),
+        );
+        assert_eq!((profile.as_deref(), level.as_deref()), (Some("main"), Some("4.0")));
+
+        assert_eq!(codec_type("h264"), VideoCodecType::H264);
+        assert_eq!(profile_level(VideoCodecType::VP8, &format("VP8", &[])), (None, None));
+    }
+}
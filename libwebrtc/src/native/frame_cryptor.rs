@@ -20,11 +20,15 @@ use webrtc_sys::frame_cryptor::{self as sys_fc};
 
 use crate::{
     peer_connection_factory::PeerConnectionFactory, rtp_receiver::RtpReceiver,
-    rtp_sender::RtpSender,
+    rtp_sender::RtpSender, RtcError, RtcErrorType,
 };
 
 pub type OnStateChange = Box<dyn FnMut(String, EncryptionState) + Send + Sync>;
 
+/// Like [`OnStateChange`], with the key index of the frame that caused the change: the index
+/// carried by the frame for a receiver, or the index used to encrypt it for a sender.
+pub type OnStateChangeWithKeyIndex = Box<dyn FnMut(String, EncryptionState, i32) + Send + Sync>;
+
 #[derive(Debug, Clone)]
 pub struct KeyProviderOptions {
     pub shared_key: bool,
@@ -91,6 +95,12 @@ impl KeyProvider {
         self.sys_handle.get_key(participant_id, key_index).ok()
     }
 
+    /// Clears the decryption failures counted for a participant, so that its frames are
+    /// decrypted again after exceeding the failure tolerance.
+    pub fn reset_key_status(&self, participant_id: String) {
+        self.sys_handle.reset_key_status(participant_id);
+    }
+
     pub fn set_sif_trailer(&self, trailer: Vec<u8>) {
         self.sys_handle.set_sif_trailer(trailer);
     }
@@ -130,7 +140,7 @@ impl FrameCryptor {
         algorithm: EncryptionAlgorithm,
         key_provider: KeyProvider,
         receiver: RtpReceiver,
-    ) -> Self {
+    ) -> Result<Self, RtcError> {
         let observer = Arc::new(RtcFrameCryptorObserver::default());
         let sys_handle = sys_fc::ffi::new_frame_cryptor_for_rtp_receiver(
             peer_factory.handle.sys_handle.clone(),
@@ -138,11 +148,15 @@ impl FrameCryptor {
             algorithm.into(),
             key_provider.sys_handle,
             receiver.handle.sys_handle,
-        );
+        )
+        .map_err(|e| RtcError {
+            error_type: RtcErrorType::InvalidState,
+            message: e.what().to_owned(),
+        })?;
         let fc = Self { observer: observer.clone(), sys_handle: sys_handle.clone() };
         fc.sys_handle
             .register_observer(Box::new(sys_fc::RtcFrameCryptorObserverWrapper::new(observer)));
-        fc
+        Ok(fc)
     }
 
     pub fn set_enabled(self: &FrameCryptor, enabled: bool) {
@@ -166,6 +180,14 @@ impl FrameCryptor {
     }
 
     pub fn on_state_change(&self, handler: Option<OnStateChange>) {
+        let handler = handler.map(|mut handler| {
+            Box::new(move |participant_id, state, _| handler(participant_id, state))
+                as OnStateChangeWithKeyIndex
+        });
+        self.on_state_change_with_key_index(handler);
+    }
+
+    pub fn on_state_change_with_key_index(&self, handler: Option<OnStateChangeWithKeyIndex>) {
         *self.observer.state_change_handler.lock() = handler;
     }
 }
@@ -215,7 +237,7 @@ impl DataPacketCryptor {
 
 #[derive(Default)]
 struct RtcFrameCryptorObserver {
-    state_change_handler: Mutex<Option<OnStateChange>>,
+    state_change_handler: Mutex<Option<OnStateChangeWithKeyIndex>>,
 }
 
 impl sys_fc::RtcFrameCryptorObserver for RtcFrameCryptorObserver {
@@ -223,10 +245,11 @@ impl sys_fc::RtcFrameCryptorObserver for RtcFrameCryptorObserver {
         &self,
         participant_id: String,
         state: sys_fc::ffi::FrameCryptionState,
+        key_index: i32,
     ) {
         let mut handler = self.state_change_handler.lock();
         if let Some(f) = handler.as_mut() {
-            f(participant_id, state.into());
+            f(participant_id, state.into(), key_index);
         }
     }
 }
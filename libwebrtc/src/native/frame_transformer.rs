@@ -0,0 +1,196 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use cxx::SharedPtr;
+use webrtc_sys::frame_transformer::{self as sys_ft};
+
+use crate::{rtp_receiver::RtpReceiver, rtp_sender::RtpSender, MediaType, RtcError, RtcErrorType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDirection {
+    Unknown,
+    Sender,
+    Receiver,
+}
+
+/// Describes an encoded frame passed to a [`FrameTransformer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameMetadata {
+    pub direction: FrameDirection,
+    pub media_type: MediaType,
+    pub ssrc: u32,
+    pub payload_type: u8,
+    pub rtp_timestamp: u32,
+    /// Always false for audio frames
+    pub is_key_frame: bool,
+}
+
+/// Transforms the encoded frames of a sender before packetization, or of a receiver before
+/// decoding, e.g. to encrypt them with a custom cipher.
+///
+/// It is called synchronously on the WebRTC encoding/decoding threads, so it shouldn't block.
+pub trait FrameTransformer: Send + Sync {
+    /// Returns the transformed payload, or None to drop the frame. A panic also drops the frame.
+    fn transform(
+        &self,
+        participant_id: &str,
+        metadata: &FrameMetadata,
+        payload: &[u8],
+    ) -> Option<Vec<u8>>;
+}
+
+/// Attaches a [`FrameTransformer`] to a [`RtpSender`] or a [`RtpReceiver`].
+///
+/// Dropping this handle doesn't detach the transformer, it stays attached for the lifetime of
+/// the sender or receiver. Use [`RtpFrameTransformer::set_enabled`] to forward the frames
+/// untouched instead.
+#[derive(Clone)]
+pub struct RtpFrameTransformer {
+    sys_handle: SharedPtr<sys_ft::ffi::FrameTransformer>,
+}
+
+impl RtpFrameTransformer {
+    pub fn new_for_rtp_sender(
+        participant_id: String,
+        transformer: Arc<dyn FrameTransformer>,
+        sender: RtpSender,
+    ) -> Self {
+        let wrapper = Arc::new(FrameTransformerWrapper { participant_id, transformer });
+        let sys_handle = sys_ft::ffi::new_frame_transformer_for_rtp_sender(
+            Box::new(sys_ft::RtcFrameTransformerWrapper::new(wrapper)),
+            sender.handle.sys_handle,
+        );
+        Self { sys_handle }
+    }
+
+    pub fn new_for_rtp_receiver(
+        participant_id: String,
+        transformer: Arc<dyn FrameTransformer>,
+        receiver: RtpReceiver,
+    ) -> Result<Self, RtcError> {
+        let wrapper = Arc::new(FrameTransformerWrapper { participant_id, transformer });
+        let sys_handle = sys_ft::ffi::new_frame_transformer_for_rtp_receiver(
+            Box::new(sys_ft::RtcFrameTransformerWrapper::new(wrapper)),
+            receiver.handle.sys_handle,
+        )
+        .map_err(|e| RtcError {
+            error_type: RtcErrorType::InvalidState,
+            message: e.what().to_owned(),
+        })?;
+        Ok(Self { sys_handle })
+    }
+
+    /// When disabled, frames are forwarded untouched
+    pub fn set_enabled(&self, enabled: bool) {
+        self.sys_handle.set_enabled(enabled);
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.sys_handle.enabled()
+    }
+}
+
+struct FrameTransformerWrapper {
+    participant_id: String,
+    transformer: Arc<dyn FrameTransformer>,
+}
+
+impl sys_ft::RtcFrameTransformer for FrameTransformerWrapper {
+    fn transform(
+        &self,
+        metadata: &sys_ft::ffi::TransformableFrameMetadata,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> bool {
+        let metadata = FrameMetadata {
+            direction: metadata.direction.into(),
+            media_type: if metadata.is_audio { MediaType::Audio } else { MediaType::Video },
+            ssrc: metadata.ssrc,
+            payload_type: metadata.payload_type,
+            rtp_timestamp: metadata.rtp_timestamp,
+            is_key_frame: metadata.is_key_frame,
+        };
+
+        // A panic can't unwind into the WebRTC thread calling this, the frame is dropped instead
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.transformer.transform(&self.participant_id, &metadata, payload)
+        }));
+        match result {
+            Ok(Some(transformed)) => {
+                *output = transformed;
+                true
+            }
+            Ok(None) => false,
+            Err(_) => {
+                log::error!(
+                    "frame transformer of {} panicked, dropping the frame",
+                    self.participant_id
+                );
+                false
+            }
+        }
+    }
+}
+
+impl From<sys_ft::ffi::FrameDirection> for FrameDirection {
+    fn from(value: sys_ft::ffi::FrameDirection) -> Self {
+        match value {
+            sys_ft::ffi::FrameDirection::Sender => Self::Sender,
+            sys_ft::ffi::FrameDirection::Receiver => Self::Receiver,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use webrtc_sys::frame_transformer::{self as sys_ft, RtcFrameTransformer};
+
+    use super::{FrameMetadata, FrameTransformer, FrameTransformerWrapper};
+
+    struct Panics;
+
+    impl FrameTransformer for Panics {
+        fn transform(&self, _: &str, _: &FrameMetadata, _: &[u8]) -> Option<Vec<u8>> {
+            panic!("transform failed");
+        }
+    }
+
+    #[test]
+    fn panic_drops_the_frame() {
+        let wrapper = FrameTransformerWrapper {
+            participant_id: "alice".to_owned(),
+            transformer: Arc::new(Panics),
+        };
+        let metadata = sys_ft::ffi::TransformableFrameMetadata {
+            direction: sys_ft::ffi::FrameDirection::Receiver,
+            is_audio: false,
+            ssrc: 1,
+            payload_type: 96,
+            rtp_timestamp: 0,
+            is_key_frame: true,
+        };
+
+        let mut output = Vec::new();
+        assert!(!wrapper.transform(&metadata, &[1, 2, 3], &mut output));
+        assert!(output.is_empty());
+    }
+}
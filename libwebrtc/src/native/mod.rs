@@ -14,26 +14,35 @@
 
 #[cfg(target_os = "android")]
 pub mod android;
+pub mod annexb;
 pub mod apm;
 pub mod audio_mixer;
 pub mod audio_resampler;
 pub mod audio_source;
 pub mod audio_stream;
 pub mod audio_track;
+pub mod codec_backend;
+pub mod codec_support;
 pub mod data_channel;
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 pub mod desktop_capturer;
 pub mod frame_cryptor;
+pub mod frame_transformer;
 pub mod ice_candidate;
 pub mod media_stream;
 pub mod media_stream_track;
 pub mod peer_connection;
 pub mod peer_connection_factory;
+#[cfg(test)]
+mod raw_video_codec;
 pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
 pub mod session_description;
+pub mod video_convert;
+pub mod video_decoder;
+pub mod video_encoder;
 pub mod video_frame;
 pub mod video_source;
 pub mod video_stream;
@@ -22,11 +22,16 @@ use webrtc_sys::{peer_connection_factory as sys_pcf, rtc_error as sys_err, webrt
 use crate::{
     audio_source::native::NativeAudioSource,
     audio_track::RtcAudioTrack,
-    imp::{audio_track as imp_at, peer_connection as imp_pc, video_track as imp_vt},
+    imp::{
+        audio_track as imp_at,
+        codec_backend::{self, VideoCodecBackends},
+        codec_support::{CodecDirection, VideoCodecSupport, VideoCodecType},
+        peer_connection as imp_pc, video_track as imp_vt,
+    },
     peer_connection::PeerConnection,
     peer_connection_factory::RtcConfiguration,
     rtp_parameters::RtpCapabilities,
-    video_source::native::NativeVideoSource,
+    video_source::native::{EncodedVideoSource, NativeVideoSource},
     video_track::RtcVideoTrack,
     MediaType, RtcError,
 };
@@ -51,6 +56,8 @@ impl Default for PeerConnectionFactory {
             }));
         }
 
+        codec_backend::apply_env_options();
+
         Self { sys_handle: sys_pcf::ffi::create_peer_connection_factory() }
     }
 }
@@ -84,6 +91,20 @@ impl PeerConnectionFactory {
         }
     }
 
+    pub fn create_encoded_video_track(
+        &self,
+        label: &str,
+        source: EncodedVideoSource,
+    ) -> RtcVideoTrack {
+        RtcVideoTrack {
+            handle: imp_vt::RtcVideoTrack {
+                sys_handle: self
+                    .sys_handle
+                    .create_video_track(label.to_string(), source.handle.sys_handle()),
+            },
+        }
+    }
+
     pub fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack {
         RtcAudioTrack {
             handle: imp_at::RtcAudioTrack {
@@ -101,11 +122,40 @@ impl PeerConnectionFactory {
     pub fn get_rtp_receiver_capabilities(&self, media_type: MediaType) -> RtpCapabilities {
         self.sys_handle.rtp_receiver_capabilities(media_type.into()).into()
     }
+
+    pub fn codec_support(&self) -> Vec<VideoCodecSupport> {
+        let encoders =
+            self.sys_handle.video_encoder_implementations().into_iter().map(|implementation| {
+                VideoCodecSupport::new(CodecDirection::Encode, implementation)
+            });
+        let decoders =
+            self.sys_handle.video_decoder_implementations().into_iter().map(|implementation| {
+                VideoCodecSupport::new(CodecDirection::Decode, implementation)
+            });
+        encoders.chain(decoders).collect()
+    }
+
+    pub fn has_video_encoder(&self, codec: VideoCodecType) -> bool {
+        let codec_name = match codec {
+            VideoCodecType::VP8 => "VP8",
+            VideoCodecType::VP9 => "VP9",
+            VideoCodecType::AV1 => "AV1",
+            VideoCodecType::H264 => "H264",
+            VideoCodecType::H265 => "H265",
+            VideoCodecType::Generic => return false,
+        };
+        self.sys_handle.has_video_encoder(codec_name)
+    }
+
+    pub fn codec_backends(&self) -> Vec<VideoCodecBackends> {
+        codec_backend::group_backend_entries(self.sys_handle.video_codec_backends())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::imp::{codec_backend::VideoCodecBackend, codec_support::VideoCodecType};
 
     #[tokio::test]
     async fn test_peer_connection_factory() {
@@ -116,4 +166,44 @@ mod tests {
         let _track = factory.create_video_track("test", source);
         drop(factory);
     }
+
+    #[tokio::test]
+    async fn codec_support() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let factory = PeerConnectionFactory::default();
+        let support = factory.codec_support();
+        for direction in [CodecDirection::Encode, CodecDirection::Decode] {
+            let vp9 = support
+                .iter()
+                .find(|s| s.codec == VideoCodecType::VP9 && s.direction == direction)
+                .expect("libvpx is always available");
+            assert!(!vp9.implementation_name.is_empty());
+            assert!(vp9.profile.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn has_video_encoder() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let factory = PeerConnectionFactory::default();
+        assert!(factory.has_video_encoder(VideoCodecType::VP9), "libvpx is always available");
+        assert!(!factory.has_video_encoder(VideoCodecType::Generic));
+    }
+
+    #[tokio::test]
+    async fn codec_backends() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let factory = PeerConnectionFactory::default();
+        let backends = factory.codec_backends();
+        for direction in [CodecDirection::Encode, CodecDirection::Decode] {
+            let vp9 = backends
+                .iter()
+                .find(|b| b.codec == VideoCodecType::VP9 && b.direction == direction)
+                .expect("libvpx is always available");
+            assert_eq!(vp9.backends.last(), Some(&VideoCodecBackend::Software));
+        }
+    }
 }
@@ -0,0 +1,202 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A fake codec used to test the encoders and decoders implemented in Rust.
+//!
+//! It is negotiated as VP8 and only carries the size and the luma of uniform frames. The
+//! factories are global, so they are registered once for every test of the crate.
+
+use std::sync::{Arc, Once};
+
+use lazy_static::lazy_static;
+use tokio::sync::{broadcast, mpsc};
+
+use super::{video_decoder::*, video_encoder::*};
+use crate::{peer_connection::TrackEvent, prelude::*};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RawCodecEvent {
+    Encoded { width: u32, height: u32, delivered: bool },
+    Decoded { width: u32, height: u32, luma: u8 },
+}
+
+lazy_static! {
+    static ref EVENTS: broadcast::Sender<RawCodecEvent> = broadcast::channel(256).0;
+}
+
+static REGISTER: Once = Once::new();
+
+/// Registers the raw codec and subscribes to its events
+pub(crate) fn subscribe() -> broadcast::Receiver<RawCodecEvent> {
+    REGISTER.call_once(|| {
+        register_video_encoder_factory(Arc::new(RawCodecFactory));
+        register_video_decoder_factory(Arc::new(RawCodecFactory));
+    });
+    EVENTS.subscribe()
+}
+
+/// Keyframe payload accepted by the VP8 depacketizer: frame tag, start code, size and luma
+fn encode_payload(width: u32, height: u32, luma: u8) -> Vec<u8> {
+    let mut payload = vec![0x00, 0x00, 0x00, 0x9d, 0x01, 0x2a];
+    payload.extend_from_slice(&(width as u16).to_le_bytes());
+    payload.extend_from_slice(&(height as u16).to_le_bytes());
+    payload.push(luma);
+    payload
+}
+
+fn decode_payload(payload: &[u8]) -> Option<(u32, u32, u8)> {
+    if payload.len() != 11 || payload[3..6] != [0x9d, 0x01, 0x2a] {
+        return None;
+    }
+    let width = u16::from_le_bytes([payload[6], payload[7]]);
+    let height = u16::from_le_bytes([payload[8], payload[9]]);
+    Some((width.into(), height.into(), payload[10]))
+}
+
+struct RawCodecFactory;
+
+impl VideoEncoderFactory for RawCodecFactory {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+        vec![SdpVideoFormat::new("VP8")]
+    }
+
+    fn create_encoder(&self, format: &SdpVideoFormat) -> Option<Box<dyn VideoEncoder>> {
+        (format.name == "VP8").then(|| Box::new(RawEncoder { callback: None }) as _)
+    }
+}
+
+impl VideoDecoderFactory for RawCodecFactory {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+        vec![SdpVideoFormat::new("VP8")]
+    }
+
+    fn create_decoder(&self, format: &SdpVideoFormat) -> Option<Box<dyn VideoDecoder>> {
+        (format.name == "VP8").then(|| Box::new(RawDecoder { callback: None }) as _)
+    }
+}
+
+struct RawEncoder {
+    callback: Option<EncodedImageCallback>,
+}
+
+impl VideoEncoder for RawEncoder {
+    fn init_encode(
+        &mut self,
+        settings: &VideoEncoderSettings,
+        callback: EncodedImageCallback,
+    ) -> Result<(), VideoEncoderError> {
+        assert_eq!(settings.codec, VideoCodecType::VP8);
+        self.callback = Some(callback);
+        Ok(())
+    }
+
+    fn encode(&mut self, input: VideoEncoderInput) -> Result<(), VideoEncoderError> {
+        let callback = self.callback.as_ref().ok_or(VideoEncoderError::Uninitialized)?;
+        let i420 = input.frame.buffer.to_i420();
+        let (width, height) = (i420.width(), i420.height());
+        let payload = encode_payload(width, height, i420.data().0[0]);
+        // Every frame is independent
+        let delivered = callback.on_encoded_image(&EncodedImage {
+            data: &payload,
+            codec: VideoCodecType::VP8,
+            is_keyframe: true,
+            rtp_timestamp: input.rtp_timestamp,
+            timestamp_us: input.frame.timestamp_us,
+            width,
+            height,
+            qp: None,
+            temporal_index: None,
+            layer_sync: false,
+        });
+        let _ = EVENTS.send(RawCodecEvent::Encoded { width, height, delivered });
+        Ok(())
+    }
+
+    fn set_rates(&mut self, _bitrate_bps: u32, _framerate_fps: f64) {}
+
+    fn encoder_info(&self) -> VideoEncoderInfo {
+        VideoEncoderInfo { implementation_name: "raw".into(), is_hardware_accelerated: false }
+    }
+}
+
+struct RawDecoder {
+    callback: Option<DecodedImageCallback>,
+}
+
+impl VideoDecoder for RawDecoder {
+    fn configure(
+        &mut self,
+        settings: &VideoDecoderSettings,
+        callback: DecodedImageCallback,
+    ) -> Result<(), VideoDecoderError> {
+        assert_eq!(settings.codec, VideoCodecType::VP8);
+        self.callback = Some(callback);
+        Ok(())
+    }
+
+    fn decode(&mut self, input: &VideoDecoderInput) -> Result<(), VideoDecoderError> {
+        let callback = self.callback.as_ref().ok_or(VideoDecoderError::Uninitialized)?;
+        let (width, height, luma) =
+            decode_payload(input.data).ok_or(VideoDecoderError::FallbackSoftware)?;
+
+        let mut buffer = NV12Buffer::new(width, height);
+        let (y, uv) = buffer.data_mut();
+        y.fill(luma);
+        uv.fill(128);
+        callback.on_decoded_image(&DecodedImage {
+            buffer: &buffer,
+            rtp_timestamp: input.rtp_timestamp,
+            qp: None,
+        });
+        let _ = EVENTS.send(RawCodecEvent::Decoded { width, height, luma });
+        Ok(())
+    }
+
+    fn decoder_info(&self) -> VideoDecoderInfo {
+        VideoDecoderInfo { implementation_name: "raw".into(), is_hardware_accelerated: false }
+    }
+}
+
+/// Connects two peer connections of the same factory, `bob` sending `track` to `alice`
+pub(crate) async fn connect(
+    bob: &PeerConnection,
+    alice: &PeerConnection,
+    track: MediaStreamTrack,
+) -> mpsc::UnboundedReceiver<TrackEvent> {
+    let (bob_ice_tx, mut bob_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+    let (alice_ice_tx, mut alice_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+    let (track_tx, track_rx) = mpsc::unbounded_channel();
+    bob.on_ice_candidate(Some(Box::new(move |candidate| {
+        let _ = bob_ice_tx.send(candidate);
+    })));
+    alice.on_ice_candidate(Some(Box::new(move |candidate| {
+        let _ = alice_ice_tx.send(candidate);
+    })));
+    alice.on_track(Some(Box::new(move |event| {
+        let _ = track_tx.send(event);
+    })));
+
+    bob.add_track(track, &["stream"]).unwrap();
+
+    let offer = bob.create_offer(OfferOptions::default()).await.unwrap();
+    bob.set_local_description(offer.clone()).await.unwrap();
+    alice.set_remote_description(offer).await.unwrap();
+    let answer = alice.create_answer(AnswerOptions::default()).await.unwrap();
+    alice.set_local_description(answer.clone()).await.unwrap();
+    bob.set_remote_description(answer).await.unwrap();
+
+    bob.add_ice_candidate(alice_ice_rx.recv().await.unwrap()).await.unwrap();
+    alice.add_ice_candidate(bob_ice_rx.recv().await.unwrap()).await.unwrap();
+    track_rx
+}
@@ -0,0 +1,762 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between all the buffer types and RGB formats.
+//!
+//! A pair uses a direct libyuv conversion when there is one. Otherwise YUV buffers go through
+//! I420 and RGB formats through ARGB.
+
+use super::yuv_helper;
+use crate::video_frame::{
+    self as vf, internal::BufferSealed, BoxVideoBuffer, ConvertError, ConvertTarget, VideoBuffer,
+    VideoBufferType, VideoFormatType,
+};
+
+pub fn convert_to(
+    src: &dyn VideoBuffer,
+    target: ConvertTarget,
+) -> Result<BoxVideoBuffer, ConvertError> {
+    let (width, height) = (src.width(), src.height());
+    let mut dst: BoxVideoBuffer = match target {
+        ConvertTarget::Buffer(VideoBufferType::I420) => {
+            Box::new(vf::I420Buffer::new(width, height))
+        }
+        ConvertTarget::Buffer(VideoBufferType::I420A) => {
+            Box::new(vf::I420ABuffer::new(width, height))
+        }
+        ConvertTarget::Buffer(VideoBufferType::I422) => {
+            Box::new(vf::I422Buffer::new(width, height))
+        }
+        ConvertTarget::Buffer(VideoBufferType::I444) => {
+            Box::new(vf::I444Buffer::new(width, height))
+        }
+        ConvertTarget::Buffer(VideoBufferType::I010) => {
+            Box::new(vf::I010Buffer::new(width, height))
+        }
+        ConvertTarget::Buffer(VideoBufferType::NV12) => {
+            Box::new(vf::NV12Buffer::new(width, height))
+        }
+        ConvertTarget::Format(format) => Box::new(vf::RgbaBuffer::new(format, width, height)),
+        ConvertTarget::Buffer(buffer_type) => {
+            return Err(ConvertError::UnsupportedTarget(buffer_type))
+        }
+    };
+    convert_into(src, dst.as_mut())?;
+    Ok(dst)
+}
+
+pub fn convert_into(src: &dyn VideoBuffer, dst: &mut dyn VideoBuffer) -> Result<(), ConvertError> {
+    let (src_size, dst_size) = ((src.width(), src.height()), (dst.width(), dst.height()));
+    if src_size != dst_size {
+        return Err(ConvertError::SizeMismatch { src: src_size, dst: dst_size });
+    }
+
+    let buffer_type = dst.buffer_type();
+    let dst = dst.as_any_mut();
+    if let Some(dst) = dst.downcast_mut::<vf::I420Buffer>() {
+        let (stride_y, stride_u, stride_v) = dst.strides();
+        let (data_y, data_u, data_v) = dst.handle.planes_mut();
+        write_i420(src, data_y, stride_y, data_u, stride_u, data_v, stride_v);
+    } else if let Some(dst) = dst.downcast_mut::<vf::I420ABuffer>() {
+        to_i420a(src, dst);
+    } else if let Some(dst) = dst.downcast_mut::<vf::I422Buffer>() {
+        to_i422(src, dst);
+    } else if let Some(dst) = dst.downcast_mut::<vf::I444Buffer>() {
+        to_i444(src, dst);
+    } else if let Some(dst) = dst.downcast_mut::<vf::I010Buffer>() {
+        to_i010(src, dst);
+    } else if let Some(dst) = dst.downcast_mut::<vf::NV12Buffer>() {
+        to_nv12(src, dst);
+    } else if let Some(dst) = dst.downcast_mut::<vf::RgbaBuffer>() {
+        to_rgba(src, dst);
+    } else {
+        return Err(ConvertError::UnsupportedTarget(buffer_type));
+    }
+    Ok(())
+}
+
+/// Converts between packed RGB formats, going through ARGB when there is no direct conversion
+#[allow(clippy::too_many_arguments)]
+pub fn rgba_to_rgba(
+    src: &[u8],
+    src_stride: u32,
+    src_format: VideoFormatType,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_format: VideoFormatType,
+    width: i32,
+    height: i32,
+) {
+    use VideoFormatType::*;
+
+    match (src_format, dst_format) {
+        _ if src_format == dst_format => copy_plane(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            width.unsigned_abs() * 4,
+            height.unsigned_abs(),
+        ),
+        (ARGB, ABGR) => yuv_helper::argb_to_abgr(src, src_stride, dst, dst_stride, width, height),
+        (ARGB, BGRA) => yuv_helper::argb_to_bgra(src, src_stride, dst, dst_stride, width, height),
+        (ARGB, RGBA) => yuv_helper::argb_to_rgba(src, src_stride, dst, dst_stride, width, height),
+        (ABGR, ARGB) => yuv_helper::abgr_to_argb(src, src_stride, dst, dst_stride, width, height),
+        (BGRA, ARGB) => yuv_helper::bgra_to_argb(src, src_stride, dst, dst_stride, width, height),
+        (RGBA, ARGB) => yuv_helper::rgba_to_argb(src, src_stride, dst, dst_stride, width, height),
+        _ => {
+            let argb_stride = width.unsigned_abs() * 4;
+            let mut argb = vec![0; (argb_stride * height.unsigned_abs()) as usize];
+            rgba_to_rgba(src, src_stride, src_format, &mut argb, argb_stride, ARGB, width, height);
+            rgba_to_rgba(&argb, argb_stride, ARGB, dst, dst_stride, dst_format, width, height);
+        }
+    }
+}
+
+fn copy_plane<T: Copy>(
+    src: &[T],
+    src_stride: u32,
+    dst: &mut [T],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    let width = width as usize;
+    for row in 0..height as usize {
+        let src = &src[row * src_stride as usize..][..width];
+        dst[row * dst_stride as usize..][..width].copy_from_slice(src);
+    }
+}
+
+fn chroma_size(size: u32) -> u32 {
+    (size + 1) / 2
+}
+
+/// Runs `f` with `src` as I420, converting it first if needed
+fn with_i420<R>(src: &dyn VideoBuffer, f: impl FnOnce(&vf::I420Buffer) -> R) -> R {
+    match src.as_i420() {
+        Some(i420) => f(i420),
+        None => {
+            let mut i420 = vf::I420Buffer::new(src.width(), src.height());
+            let (stride_y, stride_u, stride_v) = i420.strides();
+            let (data_y, data_u, data_v) = i420.handle.planes_mut();
+            write_i420(src, data_y, stride_y, data_u, stride_u, data_v, stride_v);
+            f(&i420)
+        }
+    }
+}
+
+/// Runs `f` with the pixels of `src` as ARGB or ABGR, the formats libyuv supports the most
+fn with_argb<R>(src: &vf::RgbaBuffer, f: impl FnOnce(&[u8], u32, VideoFormatType) -> R) -> R {
+    match src.format() {
+        VideoFormatType::ARGB | VideoFormatType::ABGR => f(src.data(), src.stride(), src.format()),
+        format => {
+            let (width, height) = (src.width() as i32, src.height() as i32);
+            let stride = src.width() * 4;
+            let mut argb = vec![0; (stride * src.height()) as usize];
+            rgba_to_rgba(
+                src.data(),
+                src.stride(),
+                format,
+                &mut argb,
+                stride,
+                VideoFormatType::ARGB,
+                width,
+                height,
+            );
+            f(&argb, stride, VideoFormatType::ARGB)
+        }
+    }
+}
+
+fn write_i420(
+    src: &dyn VideoBuffer,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+) {
+    let (width, height) = (src.width() as i32, src.height() as i32);
+    let (chroma_width, chroma_height) = (chroma_size(src.width()), chroma_size(src.height()));
+
+    if let Some(src) = src.as_i420() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.data();
+        copy_plane(src_y, stride_y, dst_y, dst_stride_y, src.width(), src.height());
+        copy_plane(src_u, stride_u, dst_u, dst_stride_u, chroma_width, chroma_height);
+        copy_plane(src_v, stride_v, dst_v, dst_stride_v, chroma_width, chroma_height);
+    } else if let Some(src) = src.as_i420a() {
+        let (stride_y, stride_u, stride_v, _) = src.strides();
+        let (src_y, src_u, src_v, _) = src.data();
+        copy_plane(src_y, stride_y, dst_y, dst_stride_y, src.width(), src.height());
+        copy_plane(src_u, stride_u, dst_u, dst_stride_u, chroma_width, chroma_height);
+        copy_plane(src_v, stride_v, dst_v, dst_stride_v, chroma_width, chroma_height);
+    } else if let Some(src) = src.as_i422() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.data();
+        yuv_helper::i422_to_i420(
+            src_y,
+            stride_y,
+            src_u,
+            stride_u,
+            src_v,
+            stride_v,
+            dst_y,
+            dst_stride_y,
+            dst_u,
+            dst_stride_u,
+            dst_v,
+            dst_stride_v,
+            width,
+            height,
+        );
+    } else if let Some(src) = src.as_i444() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.data();
+        yuv_helper::i444_to_i420(
+            src_y,
+            stride_y,
+            src_u,
+            stride_u,
+            src_v,
+            stride_v,
+            dst_y,
+            dst_stride_y,
+            dst_u,
+            dst_stride_u,
+            dst_v,
+            dst_stride_v,
+            width,
+            height,
+        );
+    } else if let Some(src) = src.as_i010() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.handle.planes();
+        yuv_helper::i010_to_i420(
+            src_y,
+            stride_y,
+            src_u,
+            stride_u,
+            src_v,
+            stride_v,
+            dst_y,
+            dst_stride_y,
+            dst_u,
+            dst_stride_u,
+            dst_v,
+            dst_stride_v,
+            width,
+            height,
+        );
+    } else if let Some(src) = src.as_nv12() {
+        let (stride_y, stride_uv) = src.strides();
+        let (src_y, src_uv) = src.data();
+        yuv_helper::nv12_to_i420(
+            src_y,
+            stride_y,
+            src_uv,
+            stride_uv,
+            dst_y,
+            dst_stride_y,
+            dst_u,
+            dst_stride_u,
+            dst_v,
+            dst_stride_v,
+            width,
+            height,
+        );
+    } else if let Some(src) = src.as_rgba() {
+        let convert = match src.format() {
+            VideoFormatType::ARGB => yuv_helper::argb_to_i420,
+            VideoFormatType::BGRA => yuv_helper::bgra_to_i420,
+            VideoFormatType::ABGR => yuv_helper::abgr_to_i420,
+            VideoFormatType::RGBA => yuv_helper::rgba_to_i420,
+        };
+        convert(
+            src.data(),
+            src.stride(),
+            dst_y,
+            dst_stride_y,
+            dst_u,
+            dst_stride_u,
+            dst_v,
+            dst_stride_v,
+            width,
+            height,
+        );
+    } else {
+        // Native buffers can only be read through ToI420()
+        let i420 = src.to_i420();
+        write_i420(&i420, dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v);
+    }
+}
+
+fn to_i420a(src: &dyn VideoBuffer, dst: &mut vf::I420ABuffer) {
+    let (width, height) = (src.width() as i32, src.height() as i32);
+    let (stride_y, stride_u, stride_v, stride_a) = dst.strides();
+    let (data_y, data_u, data_v, data_a) = dst.data_mut();
+    write_i420(src, data_y, stride_y, data_u, stride_u, data_v, stride_v);
+
+    let Some(data_a) = data_a else {
+        return;
+    };
+
+    if let Some((src_a, src_stride_a)) =
+        src.as_i420a().and_then(|src| src.data().3.map(|src_a| (src_a, src.strides().3)))
+    {
+        copy_plane(src_a, src_stride_a, data_a, stride_a, src.width(), src.height());
+    } else if let Some(src) = src.as_rgba() {
+        with_argb(src, |argb, argb_stride, _| {
+            yuv_helper::argb_extract_alpha(argb, argb_stride, data_a, stride_a, width, height)
+        });
+    } else {
+        data_a.fill(255);
+    }
+}
+
+macro_rules! to_planar {
+    ($fnc:ident, $type:ident, $as:ident, $from_i420:ident) => {
+        fn $fnc(src: &dyn VideoBuffer, dst: &mut vf::$type) {
+            let (width, height) = (src.width() as i32, src.height() as i32);
+            let (dst_stride_y, dst_stride_u, dst_stride_v) = dst.strides();
+            let (dst_y, dst_u, dst_v) = dst.handle.planes_mut();
+
+            if let Some(src) = src.$as() {
+                let (stride_y, stride_u, stride_v) = src.strides();
+                let (src_y, src_u, src_v) = src.handle.planes();
+                let (chroma_width, chroma_height) = (src.chroma_width(), src.chroma_height());
+                copy_plane(src_y, stride_y, dst_y, dst_stride_y, src.width(), src.height());
+                copy_plane(src_u, stride_u, dst_u, dst_stride_u, chroma_width, chroma_height);
+                copy_plane(src_v, stride_v, dst_v, dst_stride_v, chroma_width, chroma_height);
+                return;
+            }
+
+            with_i420(src, |src| {
+                let (stride_y, stride_u, stride_v) = src.strides();
+                let (src_y, src_u, src_v) = src.data();
+                yuv_helper::$from_i420(
+                    src_y,
+                    stride_y,
+                    src_u,
+                    stride_u,
+                    src_v,
+                    stride_v,
+                    dst_y,
+                    dst_stride_y,
+                    dst_u,
+                    dst_stride_u,
+                    dst_v,
+                    dst_stride_v,
+                    width,
+                    height,
+                )
+            });
+        }
+    };
+}
+
+to_planar!(to_i422, I422Buffer, as_i422, i420_to_i422);
+to_planar!(to_i444, I444Buffer, as_i444, i420_to_i444);
+to_planar!(to_i010, I010Buffer, as_i010, i420_to_i010);
+
+fn to_nv12(src: &dyn VideoBuffer, dst: &mut vf::NV12Buffer) {
+    let (width, height) = (src.width() as i32, src.height() as i32);
+    let (dst_stride_y, dst_stride_uv) = dst.strides();
+    let (dst_y, dst_uv) = dst.handle.planes_mut();
+
+    if let Some(src) = src.as_nv12() {
+        let (stride_y, stride_uv) = src.strides();
+        let (src_y, src_uv) = src.data();
+        let chroma_height = src.chroma_height();
+        copy_plane(src_y, stride_y, dst_y, dst_stride_y, src.width(), src.height());
+        copy_plane(src_uv, stride_uv, dst_uv, dst_stride_uv, src.chroma_width() * 2, chroma_height);
+    } else if let Some(src) = src.as_rgba() {
+        with_argb(src, |argb, argb_stride, format| {
+            let convert = match format {
+                VideoFormatType::ABGR => yuv_helper::abgr_to_nv12,
+                _ => yuv_helper::argb_to_nv12,
+            };
+            convert(argb, argb_stride, dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height)
+        });
+    } else {
+        with_i420(src, |src| {
+            let (stride_y, stride_u, stride_v) = src.strides();
+            let (src_y, src_u, src_v) = src.data();
+            yuv_helper::i420_to_nv12(
+                src_y,
+                stride_y,
+                src_u,
+                stride_u,
+                src_v,
+                stride_v,
+                dst_y,
+                dst_stride_y,
+                dst_uv,
+                dst_stride_uv,
+                width,
+                height,
+            )
+        });
+    }
+}
+
+fn to_rgba(src: &dyn VideoBuffer, dst: &mut vf::RgbaBuffer) {
+    let (width, height) = (src.width() as i32, src.height() as i32);
+    let (format, dst_stride) = (dst.format(), dst.stride());
+    let dst_data = dst.handle.data_mut();
+
+    if let Some(src) = src.as_rgba() {
+        rgba_to_rgba(
+            src.data(),
+            src.stride(),
+            src.format(),
+            dst_data,
+            dst_stride,
+            format,
+            width,
+            height,
+        );
+        return;
+    }
+
+    if let Some(src) = src.as_i420() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.data();
+        let from_i420 = match format {
+            VideoFormatType::ARGB => yuv_helper::i420_to_argb,
+            VideoFormatType::BGRA => yuv_helper::i420_to_bgra,
+            VideoFormatType::ABGR => yuv_helper::i420_to_abgr,
+            VideoFormatType::RGBA => yuv_helper::i420_to_rgba,
+        };
+        from_i420(
+            src_y, stride_y, src_u, stride_u, src_v, stride_v, dst_data, dst_stride, width, height,
+        );
+        return;
+    }
+
+    // The other buffers only have direct conversions to ARGB and ABGR
+    if matches!(format, VideoFormatType::BGRA | VideoFormatType::RGBA) {
+        let mut argb = vf::RgbaBuffer::new(VideoFormatType::ARGB, src.width(), src.height());
+        to_rgba(src, &mut argb);
+        rgba_to_rgba(
+            argb.data(),
+            argb.stride(),
+            VideoFormatType::ARGB,
+            dst_data,
+            dst_stride,
+            format,
+            width,
+            height,
+        );
+        return;
+    }
+
+    let abgr = format == VideoFormatType::ABGR;
+    if let Some(src) = src.as_i420a() {
+        let (stride_y, stride_u, stride_v, stride_a) = src.strides();
+        match src.data() {
+            (src_y, src_u, src_v, Some(src_a)) => {
+                let from_i420a =
+                    if abgr { yuv_helper::i420a_to_abgr } else { yuv_helper::i420a_to_argb };
+                from_i420a(
+                    src_y, stride_y, src_u, stride_u, src_v, stride_v, src_a, stride_a, dst_data,
+                    dst_stride, width, height,
+                );
+            }
+            (src_y, src_u, src_v, None) => {
+                let from_i420 =
+                    if abgr { yuv_helper::i420_to_abgr } else { yuv_helper::i420_to_argb };
+                from_i420(
+                    src_y, stride_y, src_u, stride_u, src_v, stride_v, dst_data, dst_stride, width,
+                    height,
+                );
+            }
+        }
+    } else if let Some(src) = src.as_i422() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.data();
+        let from_i422 = if abgr { yuv_helper::i422_to_abgr } else { yuv_helper::i422_to_argb };
+        from_i422(
+            src_y, stride_y, src_u, stride_u, src_v, stride_v, dst_data, dst_stride, width, height,
+        );
+    } else if let Some(src) = src.as_i444() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.data();
+        let from_i444 = if abgr { yuv_helper::i444_to_abgr } else { yuv_helper::i444_to_argb };
+        from_i444(
+            src_y, stride_y, src_u, stride_u, src_v, stride_v, dst_data, dst_stride, width, height,
+        );
+    } else if let Some(src) = src.as_i010() {
+        let (stride_y, stride_u, stride_v) = src.strides();
+        let (src_y, src_u, src_v) = src.handle.planes();
+        let from_i010 = if abgr { yuv_helper::i010_to_abgr } else { yuv_helper::i010_to_argb };
+        from_i010(
+            src_y, stride_y, src_u, stride_u, src_v, stride_v, dst_data, dst_stride, width, height,
+        );
+    } else if let Some(src) = src.as_nv12() {
+        let (stride_y, stride_uv) = src.strides();
+        let (src_y, src_uv) = src.data();
+        let from_nv12 = if abgr { yuv_helper::nv12_to_abgr } else { yuv_helper::nv12_to_argb };
+        from_nv12(src_y, stride_y, src_uv, stride_uv, dst_data, dst_stride, width, height);
+    } else {
+        with_i420(src, |src| to_rgba(src, dst));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::yuv_helper;
+    use crate::video_frame::{
+        ConvertError, ConvertTarget, I420ABuffer, I420Buffer, I444Buffer, RgbaBuffer, VideoBuffer,
+        VideoBufferType, VideoFormatType,
+    };
+
+    const TARGETS: [ConvertTarget; 10] = [
+        ConvertTarget::Buffer(VideoBufferType::I420),
+        ConvertTarget::Buffer(VideoBufferType::I420A),
+        ConvertTarget::Buffer(VideoBufferType::I422),
+        ConvertTarget::Buffer(VideoBufferType::I444),
+        ConvertTarget::Buffer(VideoBufferType::I010),
+        ConvertTarget::Buffer(VideoBufferType::NV12),
+        ConvertTarget::Format(VideoFormatType::ARGB),
+        ConvertTarget::Format(VideoFormatType::BGRA),
+        ConvertTarget::Format(VideoFormatType::ABGR),
+        ConvertTarget::Format(VideoFormatType::RGBA),
+    ];
+
+    /// Random luma with a uniform chroma, so subsampling is lossless and the colors stay inside
+    /// of the RGB gamut
+    fn random_i444(rng: &mut StdRng, width: u32, height: u32) -> I444Buffer {
+        let mut buffer = I444Buffer::new(width, height);
+        let (stride_y, stride_u, stride_v) = buffer.strides();
+        let (u, v) = (rng.random_range(112..=144), rng.random_range(112..=144));
+        let (data_y, data_u, data_v) = buffer.data_mut();
+        for y in 0..height {
+            for x in 0..width {
+                data_y[(y * stride_y + x) as usize] = rng.random_range(48..=200);
+                data_u[(y * stride_u + x) as usize] = u;
+                data_v[(y * stride_v + x) as usize] = v;
+            }
+        }
+        buffer
+    }
+
+    fn plane<T: Copy + Into<i32>>(data: &[T], stride: u32, width: u32, height: u32) -> Vec<i32> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (y * stride + x) as usize))
+            .map(|i| data[i].into())
+            .collect()
+    }
+
+    /// Visible samples of every plane, I010 is scaled down to 8 bits
+    fn samples(buffer: &dyn VideoBuffer) -> Vec<Vec<i32>> {
+        let (width, height) = (buffer.width(), buffer.height());
+        if let Some(b) = buffer.as_i420() {
+            let ((sy, su, sv), (y, u, v)) = (b.strides(), b.data());
+            let (cw, ch) = (b.chroma_width(), b.chroma_height());
+            vec![plane(y, sy, width, height), plane(u, su, cw, ch), plane(v, sv, cw, ch)]
+        } else if let Some(b) = buffer.as_i420a() {
+            let ((sy, su, sv, sa), (y, u, v, a)) = (b.strides(), b.data());
+            let (cw, ch) = (b.chroma_width(), b.chroma_height());
+            vec![
+                plane(y, sy, width, height),
+                plane(u, su, cw, ch),
+                plane(v, sv, cw, ch),
+                plane(a.unwrap(), sa, width, height),
+            ]
+        } else if let Some(b) = buffer.as_i422() {
+            let ((sy, su, sv), (y, u, v)) = (b.strides(), b.data());
+            let (cw, ch) = (b.chroma_width(), b.chroma_height());
+            vec![plane(y, sy, width, height), plane(u, su, cw, ch), plane(v, sv, cw, ch)]
+        } else if let Some(b) = buffer.as_i444() {
+            let ((sy, su, sv), (y, u, v)) = (b.strides(), b.data());
+            vec![
+                plane(y, sy, width, height),
+                plane(u, su, width, height),
+                plane(v, sv, width, height),
+            ]
+        } else if let Some(b) = buffer.as_i010() {
+            let ((sy, su, sv), (y, u, v)) = (b.strides(), b.handle.planes());
+            let (cw, ch) = (b.chroma_width(), b.chroma_height());
+            [plane(y, sy, width, height), plane(u, su, cw, ch), plane(v, sv, cw, ch)]
+                .into_iter()
+                .map(|plane| plane.into_iter().map(|sample| sample / 4).collect())
+                .collect()
+        } else if let Some(b) = buffer.as_nv12() {
+            let ((sy, suv), (y, uv)) = (b.strides(), b.data());
+            let (cw, ch) = (b.chroma_width(), b.chroma_height());
+            vec![plane(y, sy, width, height), plane(uv, suv, cw * 2, ch)]
+        } else if let Some(b) = buffer.as_rgba() {
+            vec![plane(b.data(), b.stride(), width * 4, height)]
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn assert_close(a: &dyn VideoBuffer, b: &dyn VideoBuffer, tolerance: i32, context: &str) {
+        assert_eq!(a.buffer_type(), b.buffer_type(), "{}", context);
+        for (a, b) in samples(a).iter().zip(samples(b).iter()) {
+            assert_eq!(a.len(), b.len(), "{}", context);
+            for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                assert!((a - b).abs() <= tolerance, "{}: sample {} is {} != {}", context, i, b, a);
+            }
+        }
+    }
+
+    fn is_rgb(target: ConvertTarget) -> bool {
+        matches!(target, ConvertTarget::Format(_))
+    }
+
+    #[test]
+    fn round_trip_every_pair() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..8 {
+            let (width, height) = (rng.random_range(1..=40), rng.random_range(1..=40));
+            let reference = random_i444(&mut rng, width, height);
+
+            for from in TARGETS {
+                let src = reference.convert_to(from).unwrap();
+                for to in TARGETS {
+                    let converted = src.convert_to(to).unwrap();
+                    assert_eq!((converted.width(), converted.height()), (width, height));
+
+                    // Rounding errors add up when the colors go through RGB and back
+                    let tolerance = if is_rgb(from) || is_rgb(to) { 8 } else { 1 };
+                    let back = converted.convert_to(from).unwrap();
+                    let context =
+                        format!("{:?} -> {:?} -> {:?} ({}x{})", from, to, from, width, height);
+                    assert_close(src.as_ref(), back.as_ref(), tolerance, &context);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn convert_into_reuses_buffer() {
+        let mut rng = StdRng::seed_from_u64(0xb0f);
+        for to in TARGETS {
+            let mut dst = I444Buffer::new(16, 8).convert_to(to).unwrap();
+            for _ in 0..3 {
+                let src = random_i444(&mut rng, 16, 8);
+                let expected = src.convert_to(to).unwrap();
+                let ptr = data_ptr(dst.as_ref());
+                src.convert_into(dst.as_mut()).unwrap();
+                assert_eq!(data_ptr(dst.as_ref()), ptr);
+                assert_close(expected.as_ref(), dst.as_ref(), 0, &format!("{:?}", to));
+            }
+        }
+    }
+
+    /// Address of the first plane, to check that the memory is reused
+    fn data_ptr(buffer: &dyn VideoBuffer) -> *const u8 {
+        if let Some(b) = buffer.as_i420() {
+            b.data().0.as_ptr()
+        } else if let Some(b) = buffer.as_i420a() {
+            b.data().0.as_ptr()
+        } else if let Some(b) = buffer.as_i422() {
+            b.data().0.as_ptr()
+        } else if let Some(b) = buffer.as_i444() {
+            b.data().0.as_ptr()
+        } else if let Some(b) = buffer.as_i010() {
+            b.data().0.as_ptr() as *const u8
+        } else if let Some(b) = buffer.as_nv12() {
+            b.data().0.as_ptr()
+        } else {
+            buffer.as_rgba().unwrap().data().as_ptr()
+        }
+    }
+
+    #[test]
+    fn alpha_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0xa1fa);
+        let src = I420ABuffer::new(9, 7);
+        let (data_y, data_u, data_v, data_a) = src.data_mut();
+        data_y.iter_mut().for_each(|y| *y = rng.random_range(48..=200));
+        data_u.fill(120);
+        data_v.fill(136);
+        data_a.unwrap().iter_mut().for_each(|a| *a = rng.random());
+
+        for format in [
+            VideoFormatType::ARGB,
+            VideoFormatType::BGRA,
+            VideoFormatType::ABGR,
+            VideoFormatType::RGBA,
+        ] {
+            let rgba = src.convert_to(format.into()).unwrap();
+            let back = rgba.convert_to(VideoBufferType::I420A.into()).unwrap();
+            assert_eq!(samples(back.as_ref())[3], samples(&src)[3], "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn rgba_channel_order() {
+        let mut argb = RgbaBuffer::new(VideoFormatType::ARGB, 1, 1);
+        // libyuv names the formats after the order of a little endian u32
+        argb.data_mut().copy_from_slice(&[10, 20, 30, 40]);
+
+        let expected = [
+            (VideoFormatType::ARGB, [10, 20, 30, 40]),
+            (VideoFormatType::BGRA, [40, 30, 20, 10]),
+            (VideoFormatType::ABGR, [30, 20, 10, 40]),
+            (VideoFormatType::RGBA, [40, 10, 20, 30]),
+        ];
+        for (format, bytes) in expected {
+            let converted = argb.convert_to(format.into()).unwrap();
+            assert_eq!(converted.as_rgba().unwrap().data(), &bytes, "{:?}", format);
+
+            for (other, other_bytes) in expected {
+                let back = converted.convert_to(other.into()).unwrap();
+                assert_eq!(back.as_rgba().unwrap().data(), &other_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn rgb24_through_argb() {
+        let mut src = RgbaBuffer::new(VideoFormatType::RGBA, 2, 1);
+        src.data_mut().copy_from_slice(&[255, 1, 2, 3, 255, 4, 5, 6]);
+
+        for (format, bytes) in [
+            (VideoFormatType::ARGB, [1, 2, 3, 4, 5, 6]),
+            (VideoFormatType::ABGR, [3, 2, 1, 6, 5, 4]),
+        ] {
+            let argb = src.convert_to(format.into()).unwrap();
+            let argb = argb.as_rgba().unwrap();
+            let mut packed = [0; 6];
+            yuv_helper::argb_to_rgb24(argb.data(), argb.stride(), &mut packed, 6, 2, 1);
+            assert_eq!(packed, bytes, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn unsupported_conversions() {
+        let src = I420Buffer::new(4, 4);
+        for buffer_type in [VideoBufferType::Native, VideoBufferType::Rgba] {
+            assert!(matches!(
+                src.convert_to(buffer_type.into()),
+                Err(ConvertError::UnsupportedTarget(t)) if t == buffer_type
+            ));
+        }
+
+        let mut dst = I420Buffer::new(4, 2);
+        assert!(matches!(
+            src.convert_into(&mut dst),
+            Err(ConvertError::SizeMismatch { src: (4, 4), dst: (4, 2) })
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Video decoders implemented in Rust, e.g. wrapping a hardware decoder.
+//!
+//! Factories registered with [`register_video_decoder_factory`] are used by every
+//! `PeerConnectionFactory`, ahead of the built-in decoders. When a built-in decoder exists for
+//! the same codec, it takes over if the Rust decoder fails. The registry is global to the
+//! process, a factory stays registered until [`unregister_video_decoder_factory`] is called.
+
+use std::sync::Arc;
+
+use cxx::SharedPtr;
+use thiserror::Error;
+use webrtc_sys::{video_decoder as sys_vd, video_encoder as sys_ve};
+
+pub use super::video_encoder::{SdpVideoFormat, VideoCodecType};
+use crate::video_frame::VideoBuffer;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoDecoderSettings {
+    pub codec: VideoCodecType,
+    /// Largest resolution the decoder is expected to output, 0 if unknown
+    pub max_render_width: u32,
+    pub max_render_height: u32,
+    pub number_of_cores: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VideoDecoderInfo {
+    pub implementation_name: String,
+    pub is_hardware_accelerated: bool,
+}
+
+/// An encoded frame to decode. H.264 frames are Annex-B NAL units.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoDecoderInput<'a> {
+    pub data: &'a [u8],
+    /// Must be copied to the decoded frame
+    pub rtp_timestamp: u32,
+    pub render_time_ms: i64,
+    pub is_keyframe: bool,
+    /// Encoded resolution, usually only known on keyframes
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// A decoded frame.
+///
+/// Any buffer type can be used, e.g. an [`NV12Buffer`](crate::video_frame::NV12Buffer) to avoid
+/// a conversion from the output of most hardware decoders.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodedImage<'a> {
+    pub buffer: &'a dyn VideoBuffer,
+    /// RTP timestamp of the [`VideoDecoderInput`]
+    pub rtp_timestamp: u32,
+    pub qp: Option<u8>,
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum VideoDecoderError {
+    #[error("decoder not initialized")]
+    Uninitialized,
+    #[error("decoder error")]
+    Error,
+    /// Switches to the built-in decoder of the codec, if any, e.g. on an unsupported profile
+    #[error("fallback to the built-in decoder")]
+    FallbackSoftware,
+}
+
+/// Delivers the output of a [`VideoDecoder`], it may be used from any thread.
+#[derive(Clone)]
+pub struct DecodedImageCallback {
+    sys_handle: SharedPtr<sys_vd::ffi::DecodedImageSink>,
+}
+
+impl DecodedImageCallback {
+    /// Returns false if the frame was rejected, e.g. when the decoder was released
+    pub fn on_decoded_image(&self, image: &DecodedImage) -> bool {
+        self.sys_handle.on_decoded_frame(
+            image.buffer.sys_handle(),
+            image.rtp_timestamp,
+            image.qp.map_or(-1, i32::from),
+        )
+    }
+}
+
+pub trait VideoDecoder: Send {
+    fn configure(
+        &mut self,
+        settings: &VideoDecoderSettings,
+        callback: DecodedImageCallback,
+    ) -> Result<(), VideoDecoderError>;
+
+    /// Decodes a frame, the output is delivered through the [`DecodedImageCallback`],
+    /// synchronously or not.
+    fn decode(&mut self, input: &VideoDecoderInput) -> Result<(), VideoDecoderError>;
+
+    fn release(&mut self) -> Result<(), VideoDecoderError> {
+        Ok(())
+    }
+
+    fn decoder_info(&self) -> VideoDecoderInfo;
+}
+
+pub trait VideoDecoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat>;
+
+    /// Returns None if the format isn't supported
+    fn create_decoder(&self, format: &SdpVideoFormat) -> Option<Box<dyn VideoDecoder>>;
+}
+
+/// Identifies a registered [`VideoDecoderFactory`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoDecoderFactoryId(u64);
+
+/// Registers a factory used by every `PeerConnectionFactory` ahead of the built-in decoders.
+///
+/// Factories registered last take precedence. It only affects the decoders created afterwards.
+///
+pub fn register_video_decoder_factory(
+    factory: Arc<dyn VideoDecoderFactory>,
+) -> VideoDecoderFactoryId {
+    VideoDecoderFactoryId(sys_vd::ffi::register_video_decoder_factory(Box::new(
+        sys_vd::VideoDecoderFactoryWrapper::new(Arc::new(FactoryAdapter { factory })),
+    )))
+}
+
+/// Stops using a factory for the decoders created afterwards, the existing ones are kept.
+///
+/// Returns false if the factory was already unregistered.
+pub fn unregister_video_decoder_factory(id: VideoDecoderFactoryId) -> bool {
+    sys_vd::ffi::unregister_video_decoder_factory(id.0)
+}
+
+impl From<&sys_vd::ffi::VideoDecoderSettings> for VideoDecoderSettings {
+    fn from(settings: &sys_vd::ffi::VideoDecoderSettings) -> Self {
+        Self {
+            codec: settings.codec.into(),
+            max_render_width: settings.max_render_width,
+            max_render_height: settings.max_render_height,
+            number_of_cores: settings.number_of_cores,
+        }
+    }
+}
+
+fn error_code(result: Result<(), VideoDecoderError>) -> i32 {
+    match result {
+        Ok(()) => sys_vd::VIDEO_CODEC_OK,
+        Err(VideoDecoderError::Uninitialized) => sys_vd::VIDEO_CODEC_UNINITIALIZED,
+        Err(VideoDecoderError::Error) => sys_vd::VIDEO_CODEC_ERROR,
+        Err(VideoDecoderError::FallbackSoftware) => sys_vd::VIDEO_CODEC_FALLBACK_SOFTWARE,
+    }
+}
+
+struct FactoryAdapter {
+    factory: Arc<dyn VideoDecoderFactory>,
+}
+
+impl sys_vd::VideoDecoderFactory for FactoryAdapter {
+    fn supported_formats(&self) -> Vec<sys_ve::ffi::SdpVideoFormat> {
+        self.factory.supported_formats().into_iter().map(Into::into).collect()
+    }
+
+    fn create_decoder(
+        &self,
+        format: &sys_ve::ffi::SdpVideoFormat,
+    ) -> Option<Box<dyn sys_vd::VideoDecoder>> {
+        let decoder = self.factory.create_decoder(&format.clone().into())?;
+        Some(Box::new(DecoderAdapter { decoder }))
+    }
+}
+
+struct DecoderAdapter {
+    decoder: Box<dyn VideoDecoder>,
+}
+
+impl sys_vd::VideoDecoder for DecoderAdapter {
+    fn configure(
+        &mut self,
+        settings: &sys_vd::ffi::VideoDecoderSettings,
+        sink: SharedPtr<sys_vd::ffi::DecodedImageSink>,
+    ) -> bool {
+        self.decoder.configure(&settings.into(), DecodedImageCallback { sys_handle: sink }).is_ok()
+    }
+
+    fn decode(&mut self, data: &[u8], metadata: &sys_vd::ffi::EncodedImageMetadata) -> i32 {
+        error_code(self.decoder.decode(&VideoDecoderInput {
+            data,
+            rtp_timestamp: metadata.rtp_timestamp,
+            render_time_ms: metadata.render_time_ms,
+            is_keyframe: metadata.is_keyframe,
+            width: (metadata.width > 0).then_some(metadata.width),
+            height: (metadata.height > 0).then_some(metadata.height),
+        }))
+    }
+
+    fn release(&mut self) -> i32 {
+        error_code(self.decoder.release())
+    }
+
+    fn decoder_info(&self) -> sys_vd::ffi::VideoDecoderInfo {
+        let info = self.decoder.decoder_info();
+        sys_vd::ffi::VideoDecoderInfo {
+            implementation_name: info.implementation_name,
+            is_hardware_accelerated: info.is_hardware_accelerated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, pin::Pin, time::Duration};
+
+    use livekit_runtime::Stream;
+
+    use crate::{
+        imp::raw_video_codec::{self, RawCodecEvent},
+        peer_connection_factory::native::PeerConnectionFactoryExt,
+        prelude::*,
+        video_source::native::NativeVideoSource,
+        video_stream::native::NativeVideoStream,
+    };
+
+    #[tokio::test]
+    async fn custom_decoder() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut events = raw_video_codec::subscribe();
+        let factory = PeerConnectionFactory::default();
+        let bob = factory.create_peer_connection(RtcConfiguration::default()).unwrap();
+        let alice = factory.create_peer_connection(RtcConfiguration::default()).unwrap();
+
+        let source = NativeVideoSource::new(VideoResolution { width: 320, height: 240 });
+        let track = factory.create_video_track("video", source.clone());
+        let mut tracks =
+            raw_video_codec::connect(&bob, &alice, MediaStreamTrack::Video(track)).await;
+
+        let capture = tokio::spawn(async move {
+            let mut buffer = I420Buffer::new(320, 240);
+            let (y, u, v) = buffer.data_mut();
+            y.fill(100);
+            u.fill(128);
+            v.fill(128);
+            let frame =
+                VideoFrame { rotation: VideoRotation::VideoRotation0, timestamp_us: 0, buffer };
+            let mut interval = tokio::time::interval(Duration::from_millis(33));
+            loop {
+                interval.tick().await;
+                source.capture_frame(&frame);
+            }
+        });
+
+        let Some(MediaStreamTrack::Video(track)) = tracks.recv().await.map(|event| event.track)
+        else {
+            panic!("expected a video track");
+        };
+        let mut stream = NativeVideoStream::new(track);
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            // The first frames may be the black frames sent before the first capture
+            loop {
+                if let RawCodecEvent::Decoded { luma: 100, width, height } =
+                    events.recv().await.unwrap()
+                {
+                    assert_eq!((width, height), (320, 240));
+                    break;
+                }
+            }
+
+            loop {
+                let frame = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await.unwrap();
+                assert_eq!(frame.buffer.buffer_type(), VideoBufferType::NV12);
+                let nv12 = frame.buffer.as_nv12().unwrap();
+                if nv12.data().0[0] == 100 {
+                    assert_eq!((nv12.width(), nv12.height()), (320, 240));
+                    break;
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        capture.abort();
+        stream.close();
+        alice.close();
+        bob.close();
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Video encoders implemented in Rust, e.g. wrapping openh264 or a hardware encoder.
+//!
+//! Factories registered with [`register_video_encoder_factory`] are used by every
+//! `PeerConnectionFactory`, ahead of the built-in encoders. The registry is global to the
+//! process, a factory stays registered until [`unregister_video_encoder_factory`] is called.
+
+use std::{collections::HashMap, sync::Arc};
+
+use cxx::{SharedPtr, UniquePtr};
+use thiserror::Error;
+use webrtc_sys::{
+    encoded_video_sink::ffi::VideoCodecType as SysVideoCodecType, video_encoder as sys_ve,
+    video_frame::ffi::VideoFrame as SysVideoFrame,
+};
+
+use super::video_frame::new_video_frame_buffer;
+use crate::video_frame::{BoxVideoFrame, VideoFrame};
+pub use crate::video_stream::native::VideoCodecType;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SdpVideoFormat {
+    /// Codec name, e.g. "H264"
+    pub name: String,
+    /// fmtp parameters, e.g. "profile-level-id"
+    pub parameters: HashMap<String, String>,
+}
+
+impl SdpVideoFormat {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_owned(), parameters: HashMap::new() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoEncoderSettings {
+    pub codec: VideoCodecType,
+    pub width: u32,
+    pub height: u32,
+    pub max_framerate: u32,
+    pub start_bitrate_bps: u32,
+    pub min_bitrate_bps: u32,
+    pub max_bitrate_bps: u32,
+    pub qp_max: u32,
+    pub number_of_cores: i32,
+    /// Maximum size of a RTP payload
+    pub max_payload_size: usize,
+    pub is_screenshare: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VideoEncoderInfo {
+    pub implementation_name: String,
+    pub is_hardware_accelerated: bool,
+}
+
+/// An encoded frame, with the codec specific info needed to packetize it.
+///
+/// The encoder must produce a single spatial layer. H.264 frames are Annex-B NAL units.
+#[derive(Debug, Clone, Copy)]
+pub struct EncodedImage<'a> {
+    pub data: &'a [u8],
+    pub codec: VideoCodecType,
+    pub is_keyframe: bool,
+    /// RTP timestamp of the input frame
+    pub rtp_timestamp: u32,
+    /// Timestamp of the input frame
+    pub timestamp_us: i64,
+    pub width: u32,
+    pub height: u32,
+    pub qp: Option<u8>,
+    pub temporal_index: Option<u8>,
+    /// Whether the frame only depends on the base temporal layer
+    pub layer_sync: bool,
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum VideoEncoderError {
+    #[error("encoder not initialized")]
+    Uninitialized,
+    #[error("encoder error")]
+    Error,
+}
+
+/// Delivers the output of a [`VideoEncoder`], it may be used from any thread.
+#[derive(Clone)]
+pub struct EncodedImageCallback {
+    sys_handle: SharedPtr<sys_ve::ffi::EncodedImageSink>,
+}
+
+impl EncodedImageCallback {
+    /// Returns false if the image was rejected, e.g. when the encoder was released
+    pub fn on_encoded_image(&self, image: &EncodedImage) -> bool {
+        self.sys_handle.on_encoded_image(
+            image.data,
+            &sys_ve::ffi::EncodedImageInfo {
+                codec: image.codec.into(),
+                is_keyframe: image.is_keyframe,
+                rtp_timestamp: image.rtp_timestamp,
+                capture_time_ms: image.timestamp_us / 1000,
+                width: image.width,
+                height: image.height,
+                qp: image.qp.map_or(-1, i32::from),
+                temporal_index: image.temporal_index.map_or(-1, i32::from),
+                layer_sync: image.layer_sync,
+            },
+        )
+    }
+}
+
+/// A frame to encode, along with its RTP timestamp that must be copied to the encoded image.
+pub struct VideoEncoderInput {
+    pub frame: BoxVideoFrame,
+    pub rtp_timestamp: u32,
+    /// A keyframe was requested, e.g. after packet loss
+    pub keyframe: bool,
+}
+
+pub trait VideoEncoder: Send {
+    fn init_encode(
+        &mut self,
+        settings: &VideoEncoderSettings,
+        callback: EncodedImageCallback,
+    ) -> Result<(), VideoEncoderError>;
+
+    /// Encodes a frame, the output is delivered through the [`EncodedImageCallback`],
+    /// synchronously or not.
+    fn encode(&mut self, input: VideoEncoderInput) -> Result<(), VideoEncoderError>;
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64);
+
+    fn release(&mut self) -> Result<(), VideoEncoderError> {
+        Ok(())
+    }
+
+    fn encoder_info(&self) -> VideoEncoderInfo;
+}
+
+pub trait VideoEncoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat>;
+
+    /// Returns None if the format isn't supported
+    fn create_encoder(&self, format: &SdpVideoFormat) -> Option<Box<dyn VideoEncoder>>;
+}
+
+/// Identifies a registered [`VideoEncoderFactory`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoEncoderFactoryId(u64);
+
+/// Registers a factory used by every `PeerConnectionFactory` ahead of the built-in encoders.
+///
+/// Factories registered last take precedence. It only affects the encoders created afterwards.
+///
+pub fn register_video_encoder_factory(
+    factory: Arc<dyn VideoEncoderFactory>,
+) -> VideoEncoderFactoryId {
+    VideoEncoderFactoryId(sys_ve::ffi::register_video_encoder_factory(Box::new(
+        sys_ve::VideoEncoderFactoryWrapper::new(Arc::new(FactoryAdapter { factory })),
+    )))
+}
+
+/// Stops using a factory for the encoders created afterwards, the existing ones are kept.
+///
+/// Returns false if the factory was already unregistered.
+pub fn unregister_video_encoder_factory(id: VideoEncoderFactoryId) -> bool {
+    sys_ve::ffi::unregister_video_encoder_factory(id.0)
+}
+
+impl From<VideoCodecType> for SysVideoCodecType {
+    fn from(codec: VideoCodecType) -> Self {
+        match codec {
+            VideoCodecType::Generic => Self::Generic,
+            VideoCodecType::VP8 => Self::VP8,
+            VideoCodecType::VP9 => Self::VP9,
+            VideoCodecType::AV1 => Self::AV1,
+            VideoCodecType::H264 => Self::H264,
+            VideoCodecType::H265 => Self::H265,
+        }
+    }
+}
+
+impl From<sys_ve::ffi::SdpVideoFormat> for SdpVideoFormat {
+    fn from(format: sys_ve::ffi::SdpVideoFormat) -> Self {
+        Self {
+            name: format.name,
+            parameters: format.parameters.into_iter().map(|p| (p.key, p.value)).collect(),
+        }
+    }
+}
+
+impl From<SdpVideoFormat> for sys_ve::ffi::SdpVideoFormat {
+    fn from(format: SdpVideoFormat) -> Self {
+        Self {
+            name: format.name,
+            parameters: format
+                .parameters
+                .into_iter()
+                .map(|(key, value)| sys_ve::ffi::SdpVideoFormatParameter { key, value })
+                .collect(),
+        }
+    }
+}
+
+impl From<&sys_ve::ffi::VideoEncoderSettings> for VideoEncoderSettings {
+    fn from(settings: &sys_ve::ffi::VideoEncoderSettings) -> Self {
+        Self {
+            codec: settings.codec.into(),
+            width: settings.width.into(),
+            height: settings.height.into(),
+            max_framerate: settings.max_framerate,
+            start_bitrate_bps: settings.start_bitrate_kbps * 1000,
+            min_bitrate_bps: settings.min_bitrate_kbps * 1000,
+            max_bitrate_bps: settings.max_bitrate_kbps * 1000,
+            qp_max: settings.qp_max,
+            number_of_cores: settings.number_of_cores,
+            max_payload_size: settings.max_payload_size,
+            is_screenshare: settings.is_screenshare,
+        }
+    }
+}
+
+fn error_code(result: Result<(), VideoEncoderError>) -> i32 {
+    match result {
+        Ok(()) => sys_ve::VIDEO_CODEC_OK,
+        Err(VideoEncoderError::Uninitialized) => sys_ve::VIDEO_CODEC_UNINITIALIZED,
+        Err(VideoEncoderError::Error) => sys_ve::VIDEO_CODEC_ERROR,
+    }
+}
+
+struct FactoryAdapter {
+    factory: Arc<dyn VideoEncoderFactory>,
+}
+
+impl sys_ve::VideoEncoderFactory for FactoryAdapter {
+    fn supported_formats(&self) -> Vec<sys_ve::ffi::SdpVideoFormat> {
+        self.factory.supported_formats().into_iter().map(Into::into).collect()
+    }
+
+    fn create_encoder(
+        &self,
+        format: &sys_ve::ffi::SdpVideoFormat,
+    ) -> Option<Box<dyn sys_ve::VideoEncoder>> {
+        let encoder = self.factory.create_encoder(&format.clone().into())?;
+        Some(Box::new(EncoderAdapter { encoder }))
+    }
+}
+
+struct EncoderAdapter {
+    encoder: Box<dyn VideoEncoder>,
+}
+
+impl sys_ve::VideoEncoder for EncoderAdapter {
+    fn init_encode(
+        &mut self,
+        settings: &sys_ve::ffi::VideoEncoderSettings,
+        sink: SharedPtr<sys_ve::ffi::EncodedImageSink>,
+    ) -> i32 {
+        error_code(
+            self.encoder.init_encode(&settings.into(), EncodedImageCallback { sys_handle: sink }),
+        )
+    }
+
+    fn encode(&mut self, frame: UniquePtr<SysVideoFrame>, keyframe: bool) -> i32 {
+        let rtp_timestamp = frame.timestamp();
+        let frame = VideoFrame {
+            rotation: frame.rotation().into(),
+            timestamp_us: frame.timestamp_us(),
+            buffer: new_video_frame_buffer(unsafe { frame.video_frame_buffer() }),
+        };
+        error_code(self.encoder.encode(VideoEncoderInput { frame, rtp_timestamp, keyframe }))
+    }
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64) {
+        self.encoder.set_rates(bitrate_bps, framerate_fps);
+    }
+
+    fn release(&mut self) -> i32 {
+        error_code(self.encoder.release())
+    }
+
+    fn encoder_info(&self) -> sys_ve::ffi::VideoEncoderInfo {
+        let info = self.encoder.encoder_info();
+        sys_ve::ffi::VideoEncoderInfo {
+            implementation_name: info.implementation_name,
+            is_hardware_accelerated: info.is_hardware_accelerated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::{
+        register_video_encoder_factory, unregister_video_encoder_factory, SdpVideoFormat,
+        VideoEncoder, VideoEncoderFactory,
+    };
+    use crate::{
+        imp::raw_video_codec::{self, RawCodecEvent},
+        peer_connection_factory::native::PeerConnectionFactoryExt,
+        prelude::*,
+        video_source::native::NativeVideoSource,
+    };
+
+    #[tokio::test]
+    async fn custom_encoder() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut events = raw_video_codec::subscribe();
+        let factory = PeerConnectionFactory::default();
+        let bob = factory.create_peer_connection(RtcConfiguration::default()).unwrap();
+        let alice = factory.create_peer_connection(RtcConfiguration::default()).unwrap();
+
+        // The source sends black frames until the first captured frame
+        let source = NativeVideoSource::new(VideoResolution { width: 320, height: 240 });
+        let track = factory.create_video_track("video", source);
+        let _tracks = raw_video_codec::connect(&bob, &alice, MediaStreamTrack::Video(track)).await;
+
+        let encoded = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let RawCodecEvent::Encoded { width, height, delivered } =
+                    events.recv().await.unwrap()
+                {
+                    break (width, height, delivered);
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(encoded, (320, 240, true));
+
+        alice.close();
+        bob.close();
+    }
+
+    #[test]
+    fn unregister_factory() {
+        struct NoEncoders;
+
+        impl VideoEncoderFactory for NoEncoders {
+            fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+                Vec::new()
+            }
+
+            fn create_encoder(&self, _: &SdpVideoFormat) -> Option<Box<dyn VideoEncoder>> {
+                None
+            }
+        }
+
+        let id = register_video_encoder_factory(Arc::new(NoEncoders));
+        assert!(unregister_video_encoder_factory(id));
+        assert!(!unregister_video_encoder_factory(id));
+    }
+}
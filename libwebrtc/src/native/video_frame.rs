@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use std::slice;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::{
+    io,
+    os::fd::{AsRawFd, BorrowedFd, OwnedFd},
+};
 
 use cxx::UniquePtr;
 use webrtc_sys::{video_frame as vf_sys, video_frame_buffer as vfb_sys};
 
-use super::yuv_helper;
-use crate::video_frame::{self as vf, VideoFormatType, VideoRotation};
+use super::{video_convert, yuv_helper};
+use crate::video_frame::{self as vf, TransformError, VideoBuffer, VideoFormatType, VideoRotation};
 
 /// We don't use vf::VideoFrameBuffer trait for the types inside this module to avoid confusion
 /// because directly using platform specific types is not valid (e.g user callback)
@@ -119,6 +124,33 @@ pub struct NV12Buffer {
     sys_handle: UniquePtr<vfb_sys::ffi::NV12Buffer>,
 }
 
+pub struct RgbaBuffer {
+    sys_handle: UniquePtr<vfb_sys::ffi::RgbaBuffer>,
+}
+
+impl From<VideoFormatType> for vfb_sys::ffi::RgbaFormat {
+    fn from(format: VideoFormatType) -> Self {
+        match format {
+            VideoFormatType::ARGB => Self::Argb,
+            VideoFormatType::BGRA => Self::Bgra,
+            VideoFormatType::ABGR => Self::Abgr,
+            VideoFormatType::RGBA => Self::Rgba,
+        }
+    }
+}
+
+impl From<vfb_sys::ffi::RgbaFormat> for VideoFormatType {
+    fn from(format: vfb_sys::ffi::RgbaFormat) -> Self {
+        match format {
+            vfb_sys::ffi::RgbaFormat::Argb => Self::ARGB,
+            vfb_sys::ffi::RgbaFormat::Bgra => Self::BGRA,
+            vfb_sys::ffi::RgbaFormat::Abgr => Self::ABGR,
+            vfb_sys::ffi::RgbaFormat::Rgba => Self::RGBA,
+            _ => panic!("invalid RgbaFormat"),
+        }
+    }
+}
+
 macro_rules! impl_to_argb {
     (I420Buffer [$($variant:ident: $fnc:ident),+], $format:ident, $self:ident, $dst:ident, $dst_stride:ident, $dst_width:ident, $dst_height:ident) => {
         match $format {
@@ -215,6 +247,35 @@ impl I420Buffer {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn from_raw_parts(
+        width: u32,
+        height: u32,
+        data_y: *const u8,
+        stride_y: u32,
+        data_u: *const u8,
+        stride_u: u32,
+        data_v: *const u8,
+        stride_v: u32,
+        release: impl FnOnce() + Send + 'static,
+    ) -> vf::I420Buffer {
+        vf::I420Buffer {
+            handle: I420Buffer {
+                sys_handle: vfb_sys::ffi::new_external_i420_buffer(
+                    width.try_into().unwrap(),
+                    height.try_into().unwrap(),
+                    data_y,
+                    stride_y.try_into().unwrap(),
+                    data_u,
+                    stride_u.try_into().unwrap(),
+                    data_v,
+                    stride_v.try_into().unwrap(),
+                    Box::new(vfb_sys::ExternalBufferRelease::new(release)),
+                ),
+            },
+        }
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i420_to_yuv8, yuv8_to_yuv, yuv_to_vfb) }
     }
@@ -323,6 +384,28 @@ impl I420Buffer {
 }
 
 impl I420ABuffer {
+    pub fn new(
+        width: u32,
+        height: u32,
+        stride_y: u32,
+        stride_u: u32,
+        stride_v: u32,
+        stride_a: u32,
+    ) -> vf::I420ABuffer {
+        vf::I420ABuffer {
+            handle: I420ABuffer {
+                sys_handle: vfb_sys::ffi::new_i420a_buffer(
+                    width.try_into().unwrap(),
+                    height.try_into().unwrap(),
+                    stride_y.try_into().unwrap(),
+                    stride_u.try_into().unwrap(),
+                    stride_v.try_into().unwrap(),
+                    stride_a.try_into().unwrap(),
+                ),
+            },
+        }
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i420a_to_yuv8, yuv8_to_yuv, yuv_to_vfb) }
     }
@@ -794,6 +877,30 @@ impl NV12Buffer {
         }
     }
 
+    pub unsafe fn from_raw_parts(
+        width: u32,
+        height: u32,
+        data_y: *const u8,
+        stride_y: u32,
+        data_uv: *const u8,
+        stride_uv: u32,
+        release: impl FnOnce() + Send + 'static,
+    ) -> vf::NV12Buffer {
+        vf::NV12Buffer {
+            handle: NV12Buffer {
+                sys_handle: vfb_sys::ffi::new_external_nv12_buffer(
+                    width.try_into().unwrap(),
+                    height.try_into().unwrap(),
+                    data_y,
+                    stride_y.try_into().unwrap(),
+                    data_uv,
+                    stride_uv.try_into().unwrap(),
+                    Box::new(vfb_sys::ExternalBufferRelease::new(release)),
+                ),
+            },
+        }
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe {
             &*recursive_cast!(&*self.sys_handle, nv12_to_biyuv8, biyuv8_to_biyuv, biyuv_to_vfb)
@@ -891,4 +998,829 @@ impl NV12Buffer {
             },
         }
     }
+
+    pub(super) fn planes_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        let (data_y, data_uv) = self.data();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(data_y.as_ptr() as *mut u8, data_y.len()),
+                slice::from_raw_parts_mut(data_uv.as_ptr() as *mut u8, data_uv.len()),
+            )
+        }
+    }
+}
+
+impl RgbaBuffer {
+    pub fn new(format: VideoFormatType, width: u32, height: u32, stride: u32) -> vf::RgbaBuffer {
+        vf::RgbaBuffer {
+            handle: RgbaBuffer {
+                sys_handle: vfb_sys::ffi::new_rgba_buffer(
+                    format.into(),
+                    width.try_into().unwrap(),
+                    height.try_into().unwrap(),
+                    stride.try_into().unwrap(),
+                ),
+            },
+        }
+    }
+
+    pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
+        unsafe { &*recursive_cast!(&*self.sys_handle, rgba_to_vfb) }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.sys_handle().width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.sys_handle().height()
+    }
+
+    pub fn format(&self) -> VideoFormatType {
+        self.sys_handle.format().into()
+    }
+
+    pub fn stride(&self) -> u32 {
+        self.sys_handle.stride()
+    }
+
+    pub fn to_i420(&self) -> I420Buffer {
+        I420Buffer { sys_handle: unsafe { self.sys_handle().to_i420() } }
+    }
+
+    pub fn to_argb(
+        &self,
+        format: VideoFormatType,
+        dst: &mut [u8],
+        dst_stride: u32,
+        dst_width: i32,
+        dst_height: i32,
+    ) {
+        video_convert::rgba_to_rgba(
+            self.data(),
+            self.stride(),
+            self.format(),
+            dst,
+            dst_stride,
+            format,
+            dst_width,
+            dst_height,
+        )
+    }
+
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(self.sys_handle.data(), (self.stride() * self.height()) as usize)
+        }
+    }
+
+    pub(super) fn data_mut(&mut self) -> &mut [u8] {
+        let data = self.data();
+        unsafe { slice::from_raw_parts_mut(data.as_ptr() as *mut u8, data.len()) }
+    }
+}
+
+/// Mapping of a dmabuf (or any mappable fd), unmapped when dropped
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub struct DmabufMapping {
+    fd: OwnedFd,
+    data: *const u8,
+    size: usize,
+    writable: bool,
+}
+
+// The mapping is only accessed through the buffer wrapping it
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe impl Send for DmabufMapping {}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl DmabufMapping {
+    pub fn new(fd: BorrowedFd<'_>, size: usize, writable: bool) -> io::Result<Self> {
+        let fd = fd.try_clone_to_owned()?;
+        let data = vfb_sys::ffi::map_dmabuf(fd.as_raw_fd(), size, writable)
+            .map_err(|e| io::Error::other(e.what()))?;
+        Ok(Self { fd, data, size, writable })
+    }
+
+    pub fn data(&self) -> *const u8 {
+        self.data
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl Drop for DmabufMapping {
+    fn drop(&mut self) {
+        unsafe {
+            vfb_sys::ffi::unmap_dmabuf(self.fd.as_raw_fd(), self.data, self.size, self.writable)
+        }
+    }
+}
+
+pub struct I420BufferPool {
+    sys_handle: UniquePtr<vfb_sys::ffi::I420BufferPool>,
+}
+
+impl I420BufferPool {
+    pub fn new(
+        width: u32,
+        height: u32,
+        stride_y: u32,
+        stride_u: u32,
+        stride_v: u32,
+        max_buffers: usize,
+    ) -> Self {
+        Self {
+            sys_handle: vfb_sys::ffi::new_i420_buffer_pool(
+                width.try_into().unwrap(),
+                height.try_into().unwrap(),
+                stride_y.try_into().unwrap(),
+                stride_u.try_into().unwrap(),
+                stride_v.try_into().unwrap(),
+                max_buffers,
+            ),
+        }
+    }
+
+    pub fn acquire(&self) -> Option<vf::I420Buffer> {
+        let sys_handle = self.sys_handle.acquire();
+        (!sys_handle.is_null()).then(|| vf::I420Buffer { handle: I420Buffer { sys_handle } })
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.sys_handle.allocated()
+    }
+
+    pub fn release_unused(&self) {
+        self.sys_handle.release_unused()
+    }
+}
+
+pub struct NV12BufferPool {
+    sys_handle: UniquePtr<vfb_sys::ffi::NV12BufferPool>,
+}
+
+impl NV12BufferPool {
+    pub fn new(width: u32, height: u32, stride_y: u32, stride_uv: u32, max_buffers: usize) -> Self {
+        Self {
+            sys_handle: vfb_sys::ffi::new_nv12_buffer_pool(
+                width.try_into().unwrap(),
+                height.try_into().unwrap(),
+                stride_y.try_into().unwrap(),
+                stride_uv.try_into().unwrap(),
+                max_buffers,
+            ),
+        }
+    }
+
+    pub fn acquire(&self) -> Option<vf::NV12Buffer> {
+        let sys_handle = self.sys_handle.acquire();
+        (!sys_handle.is_null()).then(|| vf::NV12Buffer { handle: NV12Buffer { sys_handle } })
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.sys_handle.allocated()
+    }
+
+    pub fn release_unused(&self) {
+        self.sys_handle.release_unused()
+    }
+}
+
+/// Region of a buffer, in luma pixels
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Rect {
+    fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Chroma planes can only start on a sample, so the origin is moved to the previous one
+    fn align(self, shift_x: u32, shift_y: u32) -> Self {
+        Self { x: self.x >> shift_x << shift_x, y: self.y >> shift_y << shift_y, ..self }
+    }
+
+    /// Offset of the origin in a plane subsampled by the given shifts, `sample_size` is the
+    /// number of elements per sample (2 for interleaved UV)
+    fn offset(&self, stride: u32, shift_x: u32, shift_y: u32, sample_size: u32) -> usize {
+        ((self.y >> shift_y) * stride + (self.x >> shift_x) * sample_size) as usize
+    }
+
+    /// Fails if the rect is empty or not inside a `width`x`height` buffer
+    fn check_inside(&self, width: u32, height: u32) -> Result<(), TransformError> {
+        let right = self.x.checked_add(self.width);
+        let bottom = self.y.checked_add(self.height);
+        match (right, bottom) {
+            (Some(right), Some(bottom))
+                if self.width > 0 && self.height > 0 && right <= width && bottom <= height =>
+            {
+                Ok(())
+            }
+            _ => Err(TransformError::InvalidRect {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+                buffer: (width, height),
+            }),
+        }
+    }
+}
+
+fn check_not_empty(width: u32, height: u32) -> Result<(), TransformError> {
+    if width == 0 || height == 0 {
+        return Err(TransformError::EmptyTarget((width, height)));
+    }
+    Ok(())
+}
+
+/// Largest region of a `width`x`height` buffer with the aspect ratio of the source, centered.
+/// Both sizes must not be empty.
+fn letterbox_rect(src_width: u32, src_height: u32, width: u32, height: u32) -> Rect {
+    let (src_width, src_height) = (src_width as u64, src_height as u64);
+    let (scaled_width, scaled_height) = if src_width * height as u64 > src_height * width as u64 {
+        (width, (src_height * width as u64 / src_width) as u32)
+    } else {
+        ((src_width * height as u64 / src_height) as u32, height)
+    };
+    let (scaled_width, scaled_height) = (scaled_width.max(1), scaled_height.max(1));
+    Rect::new((width - scaled_width) / 2, (height - scaled_height) / 2, scaled_width, scaled_height)
+}
+
+fn rotated_size(width: u32, height: u32, rotation: VideoRotation) -> (u32, u32) {
+    match rotation {
+        VideoRotation::VideoRotation90 | VideoRotation::VideoRotation270 => (height, width),
+        _ => (width, height),
+    }
+}
+
+macro_rules! impl_planes {
+    ($type:ident, $t:ty) => {
+        impl $type {
+            /// Whole planes, with strides in number of elements (`data()` halves the I010 planes)
+            pub(super) fn planes(&self) -> (&[$t], &[$t], &[$t]) {
+                let (data_y, data_u, data_v) = self.data();
+                let (height, chroma_height) = (self.height(), self.chroma_height());
+                unsafe {
+                    (
+                        slice::from_raw_parts(data_y.as_ptr(), (self.stride_y() * height) as usize),
+                        slice::from_raw_parts(
+                            data_u.as_ptr(),
+                            (self.stride_u() * chroma_height) as usize,
+                        ),
+                        slice::from_raw_parts(
+                            data_v.as_ptr(),
+                            (self.stride_v() * chroma_height) as usize,
+                        ),
+                    )
+                }
+            }
+
+            pub(super) fn planes_mut(&mut self) -> (&mut [$t], &mut [$t], &mut [$t]) {
+                let (data_y, data_u, data_v) = self.planes();
+                unsafe {
+                    (
+                        slice::from_raw_parts_mut(data_y.as_ptr() as *mut $t, data_y.len()),
+                        slice::from_raw_parts_mut(data_u.as_ptr() as *mut $t, data_u.len()),
+                        slice::from_raw_parts_mut(data_v.as_ptr() as *mut $t, data_v.len()),
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_planes!(I420Buffer, u8);
+impl_planes!(I422Buffer, u8);
+impl_planes!(I444Buffer, u8);
+impl_planes!(I010Buffer, u16);
+
+macro_rules! impl_planar_transforms {
+    ($type:ident, $scale:ident, $rotate:ident, $mirror:ident, $shift_x:expr, $shift_y:expr, $black:expr) => {
+        impl $type {
+            pub fn crop_and_scale(
+                &self,
+                x: u32,
+                y: u32,
+                crop_width: u32,
+                crop_height: u32,
+                scaled_width: u32,
+                scaled_height: u32,
+            ) -> Result<vf::$type, TransformError> {
+                let src_rect = Rect::new(x, y, crop_width, crop_height);
+                src_rect.check_inside(self.width(), self.height())?;
+                check_not_empty(scaled_width, scaled_height)?;
+
+                let mut dst = vf::$type::new(scaled_width, scaled_height);
+                self.scale_into(src_rect, &mut dst, Rect::new(0, 0, scaled_width, scaled_height));
+                Ok(dst)
+            }
+
+            pub fn letterbox(&self, width: u32, height: u32) -> Result<vf::$type, TransformError> {
+                if self.width() == 0 || self.height() == 0 {
+                    return Err(TransformError::EmptySource);
+                }
+                check_not_empty(width, height)?;
+
+                let mut dst = vf::$type::new(width, height);
+                let (dst_y, dst_u, dst_v) = dst.handle.planes_mut();
+                dst_y.fill($black.0);
+                dst_u.fill($black.1);
+                dst_v.fill($black.1);
+
+                let rect = letterbox_rect(self.width(), self.height(), width, height);
+                self.scale_into(Rect::new(0, 0, self.width(), self.height()), &mut dst, rect);
+                Ok(dst)
+            }
+
+            pub fn rotate(&self, rotation: VideoRotation) -> vf::$type {
+                let (width, height) = rotated_size(self.width(), self.height(), rotation);
+                let mut dst = vf::$type::new(width, height);
+                let (src_y, src_u, src_v) = self.planes();
+                let (dst_stride_y, dst_stride_u, dst_stride_v) = dst.strides();
+                let (dst_y, dst_u, dst_v) = dst.handle.planes_mut();
+                yuv_helper::$rotate(
+                    src_y,
+                    self.stride_y(),
+                    src_u,
+                    self.stride_u(),
+                    src_v,
+                    self.stride_v(),
+                    dst_y,
+                    dst_stride_y,
+                    dst_u,
+                    dst_stride_u,
+                    dst_v,
+                    dst_stride_v,
+                    self.width() as i32,
+                    self.height() as i32,
+                    rotation,
+                );
+                dst
+            }
+
+            pub fn mirror(&self) -> vf::$type {
+                let mut dst = vf::$type::new(self.width(), self.height());
+                let (src_y, src_u, src_v) = self.planes();
+                let (dst_stride_y, dst_stride_u, dst_stride_v) = dst.strides();
+                let (dst_y, dst_u, dst_v) = dst.handle.planes_mut();
+                yuv_helper::$mirror(
+                    src_y,
+                    self.stride_y(),
+                    src_u,
+                    self.stride_u(),
+                    src_v,
+                    self.stride_v(),
+                    dst_y,
+                    dst_stride_y,
+                    dst_u,
+                    dst_stride_u,
+                    dst_v,
+                    dst_stride_v,
+                    self.width() as i32,
+                    self.height() as i32,
+                );
+                dst
+            }
+
+            /// Both rects must be inside their buffer
+            fn scale_into(&self, src_rect: Rect, dst: &mut vf::$type, dst_rect: Rect) {
+                let (shift_x, shift_y) = ($shift_x, $shift_y);
+                let src_rect = src_rect.align(shift_x, shift_y);
+                let dst_rect = dst_rect.align(shift_x, shift_y);
+
+                let (src_y, src_u, src_v) = self.planes();
+                let (stride_y, stride_u, stride_v) =
+                    (self.stride_y(), self.stride_u(), self.stride_v());
+                let (dst_stride_y, dst_stride_u, dst_stride_v) = dst.strides();
+                let (dst_y, dst_u, dst_v) = dst.handle.planes_mut();
+                yuv_helper::$scale(
+                    &src_y[src_rect.offset(stride_y, 0, 0, 1)..],
+                    stride_y,
+                    &src_u[src_rect.offset(stride_u, shift_x, shift_y, 1)..],
+                    stride_u,
+                    &src_v[src_rect.offset(stride_v, shift_x, shift_y, 1)..],
+                    stride_v,
+                    src_rect.width as i32,
+                    src_rect.height as i32,
+                    &mut dst_y[dst_rect.offset(dst_stride_y, 0, 0, 1)..],
+                    dst_stride_y,
+                    &mut dst_u[dst_rect.offset(dst_stride_u, shift_x, shift_y, 1)..],
+                    dst_stride_u,
+                    &mut dst_v[dst_rect.offset(dst_stride_v, shift_x, shift_y, 1)..],
+                    dst_stride_v,
+                    dst_rect.width as i32,
+                    dst_rect.height as i32,
+                );
+            }
+        }
+    };
+}
+
+// Black is the lowest luma and the middle chroma value of the limited range
+impl_planar_transforms!(I420Buffer, i420_scale, i420_rotate, i420_mirror, 1, 1, (16, 128));
+impl_planar_transforms!(I422Buffer, i422_scale, i422_rotate, i422_mirror, 1, 0, (16, 128));
+impl_planar_transforms!(I444Buffer, i444_scale, i444_rotate, i444_mirror, 0, 0, (16, 128));
+impl_planar_transforms!(I010Buffer, i010_scale, i010_rotate, i010_mirror, 1, 1, (64, 512));
+
+impl NV12Buffer {
+    pub fn crop_and_scale(
+        &self,
+        x: u32,
+        y: u32,
+        crop_width: u32,
+        crop_height: u32,
+        scaled_width: u32,
+        scaled_height: u32,
+    ) -> Result<vf::NV12Buffer, TransformError> {
+        let src_rect = Rect::new(x, y, crop_width, crop_height);
+        src_rect.check_inside(self.width(), self.height())?;
+        check_not_empty(scaled_width, scaled_height)?;
+
+        let mut dst = vf::NV12Buffer::new(scaled_width, scaled_height);
+        self.scale_into(src_rect, &mut dst, Rect::new(0, 0, scaled_width, scaled_height));
+        Ok(dst)
+    }
+
+    pub fn letterbox(&self, width: u32, height: u32) -> Result<vf::NV12Buffer, TransformError> {
+        if self.width() == 0 || self.height() == 0 {
+            return Err(TransformError::EmptySource);
+        }
+        check_not_empty(width, height)?;
+
+        let mut dst = vf::NV12Buffer::new(width, height);
+        let (dst_y, dst_uv) = dst.data_mut();
+        dst_y.fill(16);
+        dst_uv.fill(128);
+
+        let rect = letterbox_rect(self.width(), self.height(), width, height);
+        self.scale_into(Rect::new(0, 0, self.width(), self.height()), &mut dst, rect);
+        Ok(dst)
+    }
+
+    pub fn rotate(&self, rotation: VideoRotation) -> vf::NV12Buffer {
+        let (width, height) = rotated_size(self.width(), self.height(), rotation);
+        let mut dst = vf::NV12Buffer::new(width, height);
+        let (src_y, src_uv) = self.data();
+        let (dst_stride_y, dst_stride_uv) = dst.strides();
+        let (dst_y, dst_uv) = dst.data_mut();
+        yuv_helper::nv12_rotate(
+            src_y,
+            self.stride_y(),
+            src_uv,
+            self.stride_uv(),
+            dst_y,
+            dst_stride_y,
+            dst_uv,
+            dst_stride_uv,
+            self.width() as i32,
+            self.height() as i32,
+            rotation,
+        );
+        dst
+    }
+
+    pub fn mirror(&self) -> vf::NV12Buffer {
+        let mut dst = vf::NV12Buffer::new(self.width(), self.height());
+        let (src_y, src_uv) = self.data();
+        let (dst_stride_y, dst_stride_uv) = dst.strides();
+        let (dst_y, dst_uv) = dst.data_mut();
+        yuv_helper::nv12_mirror(
+            src_y,
+            self.stride_y(),
+            src_uv,
+            self.stride_uv(),
+            dst_y,
+            dst_stride_y,
+            dst_uv,
+            dst_stride_uv,
+            self.width() as i32,
+            self.height() as i32,
+        );
+        dst
+    }
+
+    /// Both rects must be inside their buffer
+    fn scale_into(&self, src_rect: Rect, dst: &mut vf::NV12Buffer, dst_rect: Rect) {
+        let src_rect = src_rect.align(1, 1);
+        let dst_rect = dst_rect.align(1, 1);
+
+        let (src_y, src_uv) = self.data();
+        let (stride_y, stride_uv) = (self.stride_y(), self.stride_uv());
+        let (dst_stride_y, dst_stride_uv) = dst.strides();
+        let (dst_y, dst_uv) = dst.data_mut();
+        yuv_helper::nv12_scale(
+            &src_y[src_rect.offset(stride_y, 0, 0, 1)..],
+            stride_y,
+            &src_uv[src_rect.offset(stride_uv, 1, 1, 2)..],
+            stride_uv,
+            src_rect.width as i32,
+            src_rect.height as i32,
+            &mut dst_y[dst_rect.offset(dst_stride_y, 0, 0, 1)..],
+            dst_stride_y,
+            &mut dst_uv[dst_rect.offset(dst_stride_uv, 1, 1, 2)..],
+            dst_stride_uv,
+            dst_rect.width as i32,
+            dst_rect.height as i32,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::vf_sys;
+    use crate::video_frame::{
+        I010Buffer, I420Buffer, I420BufferPool, I422Buffer, NV12Buffer, NV12BufferPool,
+        TransformError, VideoBuffer, VideoRotation,
+    };
+
+    fn luma_i420(width: u32, height: u32) -> I420Buffer {
+        let mut buffer = I420Buffer::new(width, height);
+        let (stride_y, _, _) = buffer.strides();
+        let (data_y, data_u, data_v) = buffer.data_mut();
+        for y in 0..height {
+            for x in 0..width {
+                data_y[(y * stride_y + x) as usize] = (y * width + x) as u8 + 20;
+            }
+        }
+        data_u.fill(100);
+        data_v.fill(150);
+        buffer
+    }
+
+    fn luma(buffer: &I420Buffer, x: u32, y: u32) -> u8 {
+        let (stride_y, _, _) = buffer.strides();
+        buffer.data().0[(y * stride_y + x) as usize]
+    }
+
+    #[test]
+    fn rotate() {
+        let src = luma_i420(4, 2);
+        let rotated = src.rotate(VideoRotation::VideoRotation90);
+        assert_eq!((rotated.width(), rotated.height()), (2, 4));
+        for y in 0..4 {
+            for x in 0..2 {
+                assert_eq!(luma(&rotated, x, y), luma(&src, y, 1 - x));
+            }
+        }
+
+        let rotated = src.rotate(VideoRotation::VideoRotation180);
+        assert_eq!(luma(&rotated, 0, 0), luma(&src, 3, 1));
+    }
+
+    #[test]
+    fn rotate_full_turn() {
+        let mut src = I422Buffer::new(6, 4);
+        let (data_y, data_u, data_v) = src.data_mut();
+        for (i, value) in data_y.iter_mut().chain(data_u).chain(data_v).enumerate() {
+            *value = i as u8;
+        }
+
+        let mut rotated = src.rotate(VideoRotation::VideoRotation90);
+        for _ in 0..3 {
+            rotated = rotated.rotate(VideoRotation::VideoRotation90);
+        }
+        assert_eq!(rotated.data().0, src.data().0);
+
+        let mut nv12 = NV12Buffer::new(6, 4);
+        nv12.data_mut().0.iter_mut().enumerate().for_each(|(i, value)| *value = i as u8);
+        let rotated =
+            nv12.rotate(VideoRotation::VideoRotation180).rotate(VideoRotation::VideoRotation180);
+        assert_eq!(rotated.data().0, nv12.data().0);
+    }
+
+    #[test]
+    fn mirror() {
+        let src = luma_i420(4, 2);
+        let mirrored = src.mirror();
+        for y in 0..2 {
+            for x in 0..4 {
+                assert_eq!(luma(&mirrored, x, y), luma(&src, 3 - x, y));
+            }
+        }
+
+        let mut src = I010Buffer::new(4, 2);
+        src.data_mut().0.iter_mut().enumerate().for_each(|(i, value)| *value = i as u16 * 100);
+        let mirrored = src.mirror();
+        assert_eq!(&mirrored.data().0[..4], &[300, 200, 100, 0]);
+    }
+
+    #[test]
+    fn crop() {
+        let src = luma_i420(6, 4);
+        let cropped = src.crop(2, 2, 4, 2).unwrap();
+        assert_eq!((cropped.width(), cropped.height()), (4, 2));
+        for y in 0..2 {
+            for x in 0..4 {
+                assert_eq!(luma(&cropped, x, y), luma(&src, x + 2, y + 2));
+            }
+        }
+
+        // The origin of subsampled formats is aligned on the chroma samples
+        let cropped = src.crop(1, 1, 2, 2).unwrap();
+        assert_eq!(luma(&cropped, 0, 0), luma(&src, 0, 0));
+
+        let scaled = src.crop_and_scale(0, 0, 6, 4, 3, 2).unwrap();
+        assert_eq!((scaled.width(), scaled.height()), (3, 2));
+    }
+
+    #[test]
+    fn invalid_transforms() {
+        let src = luma_i420(6, 4);
+        assert!(matches!(src.crop(4, 0, 4, 2), Err(TransformError::InvalidRect { .. })));
+        assert!(matches!(src.crop(2, 0, u32::MAX, 2), Err(TransformError::InvalidRect { .. })));
+        assert!(matches!(src.crop(0, 0, 0, 2), Err(TransformError::InvalidRect { .. })));
+        assert!(matches!(
+            src.crop_and_scale(0, 0, 6, 4, 0, 2),
+            Err(TransformError::EmptyTarget((0, 2)))
+        ));
+        assert!(matches!(src.letterbox(8, 0), Err(TransformError::EmptyTarget((8, 0)))));
+    }
+
+    #[test]
+    fn letterbox() {
+        let src = luma_i420(8, 4);
+        let boxed = src.letterbox(8, 8).unwrap();
+        assert_eq!((boxed.width(), boxed.height()), (8, 8));
+        for x in 0..8 {
+            assert_eq!(luma(&boxed, x, 0), 16);
+            assert_eq!(luma(&boxed, x, 7), 16);
+            assert_ne!(luma(&boxed, x, 2), 16);
+        }
+        assert_eq!(boxed.data().1[0], 128);
+        assert_eq!(boxed.data().1[4], 100);
+    }
+
+    #[test]
+    fn i420_pool_recycles_released_buffers() {
+        let pool = I420BufferPool::with_strides(6, 4, 8, 4, 4, 2);
+
+        let mut first = pool.acquire().unwrap();
+        assert_eq!(first.strides(), (8, 4, 4));
+        let ptr = first.data_mut().0.as_ptr();
+        let second = pool.acquire().unwrap();
+        assert_ne!(second.data().0.as_ptr(), ptr);
+        assert!(pool.acquire().is_none());
+
+        // A frame built like in NativeVideoSource::capture_frame keeps the buffer in use
+        let mut builder = vf_sys::ffi::new_video_frame_builder();
+        builder.pin_mut().set_video_frame_buffer(first.handle.sys_handle());
+        let frame = builder.pin_mut().build();
+        drop(first);
+        assert!(pool.acquire().is_none());
+
+        drop(frame);
+        let recycled = pool.acquire().unwrap();
+        assert_eq!(recycled.data().0.as_ptr(), ptr);
+        assert_eq!(pool.allocated(), 2);
+
+        drop(second);
+        pool.release_unused();
+        assert_eq!(pool.allocated(), 1);
+    }
+
+    #[test]
+    fn nv12_pool_recycles_released_buffers() {
+        let pool = NV12BufferPool::new(5, 3, 1);
+
+        let buffer = pool.acquire().unwrap();
+        assert_eq!((buffer.width(), buffer.height()), (5, 3));
+        assert_eq!(buffer.strides(), (5, 6));
+        let ptr = buffer.data().0.as_ptr();
+        assert!(pool.acquire().is_none());
+
+        drop(buffer);
+        assert_eq!(pool.acquire().unwrap().data().0.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn external_i420_is_not_copied() {
+        let (width, height) = (4, 2);
+        let mut data = vec![0u8; 4 * 2 + 2 * 2];
+        data[..8].fill(30);
+        data[8..10].fill(100);
+        data[10..].fill(150);
+        let ptr = data.as_ptr();
+
+        let buffer = I420Buffer::from_owned(width, height, data, 4, 2, 2);
+        assert_eq!(buffer.data().0.as_ptr(), ptr);
+        assert_eq!(buffer.data().1, [100, 100]);
+        assert_eq!(buffer.data().2, [150, 150]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the strides are smaller than the rows")]
+    fn external_i420_rejects_short_strides() {
+        I420Buffer::from_owned(4, 2, vec![0u8; 4 * 2 + 2 * 2], 3, 2, 2);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn external_nv12_checks_the_dmabuf() {
+        use std::{
+            fs::{File, OpenOptions},
+            io::{ErrorKind, Write},
+            os::fd::{AsFd, AsRawFd, FromRawFd},
+        };
+
+        use crate::video_frame::DmabufPlane;
+
+        let mut file = unsafe {
+            let fd = libc::memfd_create(c"nv12".as_ptr(), 0);
+            assert!(fd >= 0);
+            File::from_raw_fd(fd)
+        };
+        file.write_all(&[60; 6 * 4 + 6 * 2]).unwrap();
+        let (y, uv) = (DmabufPlane { offset: 0, stride: 6 }, DmabufPlane { offset: 24, stride: 6 });
+
+        assert!(NV12Buffer::from_dmabuf(file.as_fd(), 6, 4, y, uv, || {}).is_ok());
+
+        let short_uv = DmabufPlane { stride: 4, ..uv };
+        let error = NV12Buffer::from_dmabuf(file.as_fd(), 6, 4, y, short_uv, || {}).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        // Reading past the end of the memfd would raise SIGBUS
+        let past_end = DmabufPlane { offset: 4096, ..uv };
+        assert!(NV12Buffer::from_dmabuf(file.as_fd(), 6, 4, y, past_end, || {}).is_err());
+
+        let read_only = OpenOptions::new()
+            .read(true)
+            .open(format!("/proc/self/fd/{}", file.as_raw_fd()))
+            .unwrap();
+        assert!(NV12Buffer::from_dmabuf(read_only.as_fd(), 6, 4, y, uv, || {}).is_err());
+        let buffer =
+            unsafe { NV12Buffer::from_dmabuf_read_only(read_only.as_fd(), 6, 4, y, uv, || {}) }
+                .unwrap();
+        assert_eq!(buffer.data().1, [60; 12]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn external_nv12_from_memfd() {
+        use std::{
+            fs::File,
+            io::Write,
+            os::fd::{AsFd, FromRawFd},
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            },
+        };
+
+        use crate::video_frame::DmabufPlane;
+
+        let (width, height, stride) = (6u32, 4u32, 8u32);
+        let uv_offset = 4096;
+        let mut file = unsafe {
+            let fd = libc::memfd_create(c"nv12".as_ptr(), 0);
+            assert!(fd >= 0);
+            File::from_raw_fd(fd)
+        };
+        let mut data = vec![0u8; uv_offset + (stride * height / 2) as usize];
+        data[..(stride * height) as usize].fill(40);
+        for (i, uv) in data[uv_offset..].chunks_mut(2).enumerate() {
+            uv.copy_from_slice(&[100 + i as u8 % 4, 200]);
+        }
+        file.write_all(&data).unwrap();
+
+        let released = Arc::new(AtomicBool::new(false));
+        let buffer = NV12Buffer::from_dmabuf(
+            file.as_fd(),
+            width,
+            height,
+            DmabufPlane { offset: 0, stride },
+            DmabufPlane { offset: uv_offset, stride },
+            {
+                let released = released.clone();
+                move || released.store(true, Ordering::SeqCst)
+            },
+        )
+        .unwrap();
+        // The buffer keeps its own mapping
+        drop(file);
+
+        assert_eq!(buffer.strides(), (stride, stride));
+        assert_eq!(&buffer.data().0[..6], &[40; 6]);
+        assert_eq!(&buffer.data().1[..6], &[100, 200, 101, 200, 102, 200]);
+
+        let i420 = buffer.to_i420();
+        assert_eq!(i420.data().0[0], 40);
+        assert_eq!(&i420.data().1[..3], &[100, 101, 102]);
+        assert_eq!(i420.data().2[0], 200);
+
+        // A frame built like in NativeVideoSource::capture_frame keeps the memory alive
+        let mut builder = vf_sys::ffi::new_video_frame_builder();
+        builder.pin_mut().set_video_frame_buffer(buffer.handle.sys_handle());
+        let frame = builder.pin_mut().build();
+        drop(buffer);
+        assert!(!released.load(Ordering::SeqCst));
+
+        drop(frame);
+        assert!(released.load(Ordering::SeqCst));
+    }
 }
@@ -20,11 +20,17 @@ use std::{
 use cxx::SharedPtr;
 use livekit_runtime::interval;
 use parking_lot::Mutex;
-use webrtc_sys::{video_frame as vf_sys, video_frame::ffi::VideoRotation, video_track as vt_sys};
+use webrtc_sys::{
+    encoded_video_source as evs_sys, video_frame as vf_sys, video_frame::ffi::VideoRotation,
+    video_track as vt_sys,
+};
 
 use crate::{
     video_frame::{I420Buffer, VideoBuffer, VideoFrame},
-    video_source::VideoResolution,
+    video_source::{
+        native::{EncodedVideoCodec, EncodedVideoFrame},
+        VideoAdaptation, VideoResolution,
+    },
 };
 
 impl From<vt_sys::ffi::VideoResolution> for VideoResolution {
@@ -39,6 +45,21 @@ impl From<VideoResolution> for vt_sys::ffi::VideoResolution {
     }
 }
 
+impl From<VideoAdaptation> for vt_sys::ffi::VideoAdaptation {
+    fn from(adaptation: VideoAdaptation) -> Self {
+        Self {
+            has_max_fps: adaptation.max_fps.is_some(),
+            max_fps: adaptation.max_fps.unwrap_or_default(),
+            has_max_resolution: adaptation.max_resolution.is_some(),
+            max_resolution: adaptation
+                .max_resolution
+                .map(Into::into)
+                .unwrap_or(vt_sys::ffi::VideoResolution { width: 0, height: 0 }),
+            follow_sink_wants: adaptation.follow_sink_wants,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct NativeVideoSource {
     sys_handle: SharedPtr<vt_sys::ffi::VideoTrackSource>,
@@ -47,6 +68,7 @@ pub struct NativeVideoSource {
 
 struct VideoSourceInner {
     captured_frames: usize,
+    adaptation: VideoAdaptation,
 }
 
 impl NativeVideoSource {
@@ -55,7 +77,10 @@ impl NativeVideoSource {
             sys_handle: vt_sys::ffi::new_video_track_source(&vt_sys::ffi::VideoResolution::from(
                 resolution.clone(),
             )),
-            inner: Arc::new(Mutex::new(VideoSourceInner { captured_frames: 0 })),
+            inner: Arc::new(Mutex::new(VideoSourceInner {
+                captured_frames: 0,
+                adaptation: VideoAdaptation::default(),
+            })),
         };
 
         livekit_runtime::spawn({
@@ -113,4 +138,165 @@ impl NativeVideoSource {
     pub fn video_resolution(&self) -> VideoResolution {
         self.sys_handle.video_resolution().into()
     }
+
+    pub fn set_adaptation(&self, adaptation: VideoAdaptation) {
+        let mut inner = self.inner.lock();
+        self.sys_handle.set_adaptation(&adaptation.clone().into());
+        inner.adaptation = adaptation;
+    }
+
+    pub fn adaptation(&self) -> VideoAdaptation {
+        self.inner.lock().adaptation.clone()
+    }
+}
+
+impl From<EncodedVideoCodec> for evs_sys::ffi::EncodedVideoCodec {
+    fn from(codec: EncodedVideoCodec) -> Self {
+        match codec {
+            EncodedVideoCodec::H264 => Self::H264,
+            EncodedVideoCodec::H265 => Self::H265,
+        }
+    }
+}
+
+type KeyFrameRequestHandler = Box<dyn Fn() + Send>;
+
+#[derive(Clone)]
+pub struct EncodedVideoSource {
+    sys_handle: SharedPtr<evs_sys::ffi::EncodedVideoTrackSource>,
+    codec: EncodedVideoCodec,
+    observer: Arc<KeyFrameRequestObserver>,
+}
+
+impl EncodedVideoSource {
+    pub fn new(resolution: VideoResolution, codec: EncodedVideoCodec) -> Self {
+        let sys_handle = evs_sys::ffi::new_encoded_video_track_source(
+            &vt_sys::ffi::VideoResolution::from(resolution),
+            codec.into(),
+        );
+        let observer = Arc::new(KeyFrameRequestObserver::default());
+        sys_handle.set_keyframe_request_observer(Box::new(
+            evs_sys::KeyFrameRequestObserverWrapper::new(observer.clone()),
+        ));
+        Self { sys_handle, codec, observer }
+    }
+
+    pub fn sys_handle(&self) -> SharedPtr<vt_sys::ffi::VideoTrackSource> {
+        self.sys_handle.video_source()
+    }
+
+    pub fn codec(&self) -> EncodedVideoCodec {
+        self.codec
+    }
+
+    pub fn capture_frame(&self, frame: &EncodedVideoFrame) -> bool {
+        let timestamp_us = if frame.timestamp_us == 0 {
+            // If the timestamp is set to 0, default to now
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros() as i64
+        } else {
+            frame.timestamp_us
+        };
+
+        self.sys_handle.capture_frame(
+            frame.data,
+            &evs_sys::ffi::EncodedFrameInfo {
+                is_keyframe: frame.is_keyframe,
+                timestamp_us,
+                width: frame.width,
+                height: frame.height,
+            },
+        )
+    }
+
+    pub fn on_keyframe_request(&self, handler: Option<KeyFrameRequestHandler>) {
+        *self.observer.handler.lock() = handler;
+    }
+
+    pub fn video_resolution(&self) -> VideoResolution {
+        self.sys_handle.video_source().video_resolution().into()
+    }
+}
+
+#[derive(Default)]
+struct KeyFrameRequestObserver {
+    handler: Mutex<Option<KeyFrameRequestHandler>>,
+}
+
+impl evs_sys::KeyFrameRequestObserver for KeyFrameRequestObserver {
+    fn on_keyframe_request(&self) {
+        if let Some(handler) = self.handler.lock().as_ref() {
+            handler();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, pin::Pin};
+
+    use livekit_runtime::Stream;
+
+    use crate::{
+        prelude::*,
+        video_source::native::{
+            EncodedVideoCodec, EncodedVideoFrame, EncodedVideoSource, NativeVideoSource,
+        },
+        video_stream::native::NativeVideoStream,
+    };
+
+    #[tokio::test]
+    async fn adaptation() {
+        let factory = PeerConnectionFactory::default();
+        let source = NativeVideoSource::with_adaptation(
+            VideoResolution { width: 1280, height: 720 },
+            VideoAdaptation {
+                max_fps: Some(10),
+                max_resolution: Some(VideoResolution { width: 640, height: 360 }),
+                follow_sink_wants: true,
+            },
+        );
+        let track = factory.create_video_track("video", source.clone());
+        let mut stream = NativeVideoStream::new(track);
+
+        // One second of a 30 fps camera, delivered all at once
+        let buffer = I420Buffer::new(1280, 720);
+        for i in 0..30 {
+            source.capture_frame(&VideoFrame {
+                rotation: VideoRotation::VideoRotation0,
+                timestamp_us: 1_000_000 + i * 1_000_000 / 30,
+                buffer: &buffer,
+            });
+        }
+        stream.close();
+
+        let mut frames = 0;
+        while let Some(frame) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            assert_eq!((frame.buffer.width(), frame.buffer.height()), (640, 360));
+            frames += 1;
+        }
+        // The first frame, then one in three
+        assert!((10..=11).contains(&frames), "{frames} frames");
+    }
+
+    #[test]
+    fn encoded_resolution() {
+        let source = EncodedVideoSource::new(
+            VideoResolution { width: 32, height: 32 },
+            EncodedVideoCodec::H264,
+        );
+        let frame = |width, height| EncodedVideoFrame {
+            data: &[0, 0, 0, 1, 0x65],
+            is_keyframe: true,
+            timestamp_us: 0,
+            width,
+            height,
+        };
+
+        assert!(source.capture_frame(&frame(64, 48)));
+        let resolution = source.video_resolution();
+        assert_eq!((resolution.width, resolution.height), (64, 48));
+        assert!(source.capture_frame(&frame(0, 0)));
+        let resolution = source.video_resolution();
+        assert_eq!((resolution.width, resolution.height), (64, 48));
+    }
 }
@@ -21,12 +21,15 @@ use std::{
 use cxx::{SharedPtr, UniquePtr};
 use livekit_runtime::Stream;
 use tokio::sync::mpsc;
-use webrtc_sys::video_track as sys_vt;
+use webrtc_sys::{encoded_video_sink as sys_evs, video_track as sys_vt};
 
 use super::video_frame::new_video_frame_buffer;
 use crate::{
+    rtp_receiver::RtpReceiver,
     video_frame::{BoxVideoFrame, VideoFrame},
+    video_stream::native::{EncodedFrame, VideoCodecType},
     video_track::RtcVideoTrack,
+    RtcError, RtcErrorType,
 };
 
 pub struct NativeVideoStream {
@@ -92,3 +95,88 @@ impl sys_vt::VideoSink for VideoTrackObserver {
 
     fn on_constraints_changed(&self, _constraints: sys_vt::ffi::VideoTrackSourceConstraints) {}
 }
+
+impl From<sys_evs::ffi::VideoCodecType> for VideoCodecType {
+    fn from(codec: sys_evs::ffi::VideoCodecType) -> Self {
+        match codec {
+            sys_evs::ffi::VideoCodecType::VP8 => Self::VP8,
+            sys_evs::ffi::VideoCodecType::VP9 => Self::VP9,
+            sys_evs::ffi::VideoCodecType::AV1 => Self::AV1,
+            sys_evs::ffi::VideoCodecType::H264 => Self::H264,
+            sys_evs::ffi::VideoCodecType::H265 => Self::H265,
+            _ => Self::Generic,
+        }
+    }
+}
+
+pub struct NativeEncodedVideoStream {
+    sys_handle: SharedPtr<sys_evs::ffi::EncodedVideoSink>,
+    receiver: RtpReceiver,
+    frame_rx: mpsc::UnboundedReceiver<EncodedFrame>,
+}
+
+impl NativeEncodedVideoStream {
+    pub fn new(receiver: RtpReceiver) -> Result<Self, RtcError> {
+        let (frame_tx, frame_rx) = mpsc::unbounded_channel();
+        let observer = Arc::new(EncodedVideoSinkObserver { frame_tx });
+        let sys_handle = sys_evs::ffi::new_encoded_video_sink(
+            Box::new(sys_evs::EncodedVideoSinkWrapper::new(observer)),
+            receiver.handle.sys_handle.clone(),
+        )
+        .map_err(|e| RtcError {
+            error_type: RtcErrorType::InvalidState,
+            message: e.what().to_owned(),
+        })?;
+
+        Ok(Self { sys_handle, receiver, frame_rx })
+    }
+
+    pub fn receiver(&self) -> RtpReceiver {
+        self.receiver.clone()
+    }
+
+    pub fn request_keyframe(&self) {
+        self.sys_handle.request_keyframe();
+    }
+
+    pub fn close(&mut self) {
+        self.sys_handle.close();
+        self.frame_rx.close();
+    }
+}
+
+impl Drop for NativeEncodedVideoStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Stream for NativeEncodedVideoStream {
+    type Item = EncodedFrame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.frame_rx.poll_recv(cx)
+    }
+}
+
+struct EncodedVideoSinkObserver {
+    frame_tx: mpsc::UnboundedSender<EncodedFrame>,
+}
+
+impl sys_evs::EncodedVideoSink for EncodedVideoSinkObserver {
+    fn on_encoded_frame(&self, metadata: &sys_evs::ffi::EncodedVideoFrameMetadata, data: &[u8]) {
+        let layer = |index: i32| u8::try_from(index).ok();
+        let _ = self.frame_tx.send(EncodedFrame {
+            codec: metadata.codec.into(),
+            is_keyframe: metadata.is_keyframe,
+            rtp_timestamp: metadata.rtp_timestamp,
+            ssrc: metadata.ssrc,
+            payload_type: metadata.payload_type,
+            width: metadata.width as u32,
+            height: metadata.height as u32,
+            spatial_index: layer(metadata.spatial_index),
+            temporal_index: layer(metadata.temporal_index),
+            data: data.to_vec(),
+        });
+    }
+}
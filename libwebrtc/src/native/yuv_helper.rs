@@ -16,6 +16,8 @@
 
 use webrtc_sys::yuv_helper as yuv_sys;
 
+use crate::video_frame::VideoRotation;
+
 fn argb_assert_safety(src: &[u8], src_stride: u32, _width: i32, height: i32) {
     let height_abs = height.unsigned_abs();
     let min = (src_stride * height_abs) as usize;
@@ -122,6 +124,20 @@ fn i010_assert_safety(
     assert!(src_v.len() >= min_v, "src_v isn't large enough");
 }
 
+/// Checks a plane that may start at an offset of a larger plane (e.g. cropping), so the last row
+/// only needs to be `width` long
+fn plane_assert_safety<T>(plane: &[T], stride: u32, width: u32, height: u32, name: &str) {
+    if height == 0 {
+        return;
+    }
+    let min = (stride * (height - 1) + width) as usize;
+    assert!(plane.len() >= min, "{} isn't large enough", name);
+}
+
+fn chroma_size(size: i32, shift: u32) -> u32 {
+    (size.unsigned_abs() + (1 << shift) - 1) >> shift
+}
+
 macro_rules! i420_to_rgba {
     ($x:ident) => {
         pub fn $x(
@@ -239,6 +255,8 @@ pub fn argb_to_rgb24(
 // I420 <> RGB conversion
 rgba_to_i420!(argb_to_i420);
 rgba_to_i420!(abgr_to_i420);
+rgba_to_i420!(bgra_to_i420);
+rgba_to_i420!(rgba_to_i420);
 
 i420_to_rgba!(i420_to_argb);
 i420_to_rgba!(i420_to_bgra);
@@ -866,3 +884,443 @@ pub fn argb_to_nv12(
         .unwrap()
     }
 }
+
+macro_rules! planar_scale {
+    ($x:ident, $t:ty, $shift_x:expr, $shift_y:expr) => {
+        pub fn $x(
+            src_y: &[$t],
+            src_stride_y: u32,
+            src_u: &[$t],
+            src_stride_u: u32,
+            src_v: &[$t],
+            src_stride_v: u32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: &mut [$t],
+            dst_stride_y: u32,
+            dst_u: &mut [$t],
+            dst_stride_u: u32,
+            dst_v: &mut [$t],
+            dst_stride_v: u32,
+            dst_width: i32,
+            dst_height: i32,
+        ) {
+            let (src_cw, src_ch) =
+                (chroma_size(src_width, $shift_x), chroma_size(src_height, $shift_y));
+            let (dst_cw, dst_ch) =
+                (chroma_size(dst_width, $shift_x), chroma_size(dst_height, $shift_y));
+            let (src_w, src_h) = (src_width.unsigned_abs(), src_height.unsigned_abs());
+            let (dst_w, dst_h) = (dst_width.unsigned_abs(), dst_height.unsigned_abs());
+            plane_assert_safety(src_y, src_stride_y, src_w, src_h, "src_y");
+            plane_assert_safety(src_u, src_stride_u, src_cw, src_ch, "src_u");
+            plane_assert_safety(src_v, src_stride_v, src_cw, src_ch, "src_v");
+            plane_assert_safety(dst_y, dst_stride_y, dst_w, dst_h, "dst_y");
+            plane_assert_safety(dst_u, dst_stride_u, dst_cw, dst_ch, "dst_u");
+            plane_assert_safety(dst_v, dst_stride_v, dst_cw, dst_ch, "dst_v");
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_u.as_ptr(),
+                    src_stride_u as i32,
+                    src_v.as_ptr(),
+                    src_stride_v as i32,
+                    src_width,
+                    src_height,
+                    dst_y.as_mut_ptr(),
+                    dst_stride_y as i32,
+                    dst_u.as_mut_ptr(),
+                    dst_stride_u as i32,
+                    dst_v.as_mut_ptr(),
+                    dst_stride_v as i32,
+                    dst_width,
+                    dst_height,
+                )
+                .unwrap();
+            }
+        }
+    };
+}
+
+planar_scale!(i420_scale, u8, 1, 1);
+planar_scale!(i422_scale, u8, 1, 0);
+planar_scale!(i444_scale, u8, 0, 0);
+planar_scale!(i010_scale, u16, 1, 1);
+
+pub fn nv12_scale(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    src_width: i32,
+    src_height: i32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    dst_width: i32,
+    dst_height: i32,
+) {
+    let (src_w, src_h) = (src_width.unsigned_abs(), src_height.unsigned_abs());
+    let (dst_w, dst_h) = (dst_width.unsigned_abs(), dst_height.unsigned_abs());
+    plane_assert_safety(src_y, src_stride_y, src_w, src_h, "src_y");
+    plane_assert_safety(
+        src_uv,
+        src_stride_uv,
+        chroma_size(src_width, 1) * 2,
+        chroma_size(src_height, 1),
+        "src_uv",
+    );
+    plane_assert_safety(dst_y, dst_stride_y, dst_w, dst_h, "dst_y");
+    plane_assert_safety(
+        dst_uv,
+        dst_stride_uv,
+        chroma_size(dst_width, 1) * 2,
+        chroma_size(dst_height, 1),
+        "dst_uv",
+    );
+
+    unsafe {
+        yuv_sys::ffi::nv12_scale(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_uv.as_ptr(),
+            src_stride_uv as i32,
+            src_width,
+            src_height,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            dst_width,
+            dst_height,
+        )
+        .unwrap();
+    }
+}
+
+/// Rotation needs a destination with swapped dimensions for 90 and 270 degrees
+fn rotated_size(width: i32, height: i32, rotation: VideoRotation) -> (i32, i32) {
+    match rotation {
+        VideoRotation::VideoRotation90 | VideoRotation::VideoRotation270 => (height, width),
+        _ => (width, height),
+    }
+}
+
+macro_rules! planar_transform {
+    ($x:ident, $t:ty, $shift_x:expr, $shift_y:expr $(, $rotation:ident)?) => {
+        pub fn $x(
+            src_y: &[$t],
+            src_stride_y: u32,
+            src_u: &[$t],
+            src_stride_u: u32,
+            src_v: &[$t],
+            src_stride_v: u32,
+            dst_y: &mut [$t],
+            dst_stride_y: u32,
+            dst_u: &mut [$t],
+            dst_stride_u: u32,
+            dst_v: &mut [$t],
+            dst_stride_v: u32,
+            width: i32,
+            height: i32,
+            $($rotation: VideoRotation,)?
+        ) {
+            let (dst_width, dst_height) = (width, height);
+            $(let (dst_width, dst_height) = rotated_size(width, height, $rotation);)?
+            let (cw, ch) = (chroma_size(width, $shift_x), chroma_size(height, $shift_y));
+            let (dst_cw, dst_ch) =
+                (chroma_size(dst_width, $shift_x), chroma_size(dst_height, $shift_y));
+            let (w, h) = (width.unsigned_abs(), height.unsigned_abs());
+            let (dst_w, dst_h) = (dst_width.unsigned_abs(), dst_height.unsigned_abs());
+            plane_assert_safety(src_y, src_stride_y, w, h, "src_y");
+            plane_assert_safety(src_u, src_stride_u, cw, ch, "src_u");
+            plane_assert_safety(src_v, src_stride_v, cw, ch, "src_v");
+            plane_assert_safety(dst_y, dst_stride_y, dst_w, dst_h, "dst_y");
+            plane_assert_safety(dst_u, dst_stride_u, dst_cw, dst_ch, "dst_u");
+            plane_assert_safety(dst_v, dst_stride_v, dst_cw, dst_ch, "dst_v");
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_u.as_ptr(),
+                    src_stride_u as i32,
+                    src_v.as_ptr(),
+                    src_stride_v as i32,
+                    dst_y.as_mut_ptr(),
+                    dst_stride_y as i32,
+                    dst_u.as_mut_ptr(),
+                    dst_stride_u as i32,
+                    dst_v.as_mut_ptr(),
+                    dst_stride_v as i32,
+                    width,
+                    height,
+                    $($rotation as i32,)?
+                )
+                .unwrap();
+            }
+        }
+    };
+}
+
+planar_transform!(i420_rotate, u8, 1, 1, rotation);
+planar_transform!(i422_rotate, u8, 1, 0, rotation);
+planar_transform!(i444_rotate, u8, 0, 0, rotation);
+planar_transform!(i010_rotate, u16, 1, 1, rotation);
+planar_transform!(i420_mirror, u8, 1, 1);
+planar_transform!(i422_mirror, u8, 1, 0);
+planar_transform!(i444_mirror, u8, 0, 0);
+planar_transform!(i010_mirror, u16, 1, 1);
+
+macro_rules! nv12_transform {
+    ($x:ident $(, $rotation:ident)?) => {
+        pub fn $x(
+            src_y: &[u8],
+            src_stride_y: u32,
+            src_uv: &[u8],
+            src_stride_uv: u32,
+            dst_y: &mut [u8],
+            dst_stride_y: u32,
+            dst_uv: &mut [u8],
+            dst_stride_uv: u32,
+            width: i32,
+            height: i32,
+            $($rotation: VideoRotation,)?
+        ) {
+            let (dst_width, dst_height) = (width, height);
+            $(let (dst_width, dst_height) = rotated_size(width, height, $rotation);)?
+            plane_assert_safety(
+                src_y,
+                src_stride_y,
+                width.unsigned_abs(),
+                height.unsigned_abs(),
+                "src_y",
+            );
+            plane_assert_safety(
+                src_uv,
+                src_stride_uv,
+                chroma_size(width, 1) * 2,
+                chroma_size(height, 1),
+                "src_uv",
+            );
+            plane_assert_safety(
+                dst_y,
+                dst_stride_y,
+                dst_width.unsigned_abs(),
+                dst_height.unsigned_abs(),
+                "dst_y",
+            );
+            plane_assert_safety(
+                dst_uv,
+                dst_stride_uv,
+                chroma_size(dst_width, 1) * 2,
+                chroma_size(dst_height, 1),
+                "dst_uv",
+            );
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_uv.as_ptr(),
+                    src_stride_uv as i32,
+                    dst_y.as_mut_ptr(),
+                    dst_stride_y as i32,
+                    dst_uv.as_mut_ptr(),
+                    dst_stride_uv as i32,
+                    width,
+                    height,
+                    $($rotation as i32,)?
+                )
+                .unwrap();
+            }
+        }
+    };
+}
+
+nv12_transform!(nv12_rotate, rotation);
+nv12_transform!(nv12_mirror);
+
+macro_rules! i420_to_planar {
+    ($x:ident, $t:ty, $shift_x:expr, $shift_y:expr) => {
+        pub fn $x(
+            src_y: &[u8],
+            src_stride_y: u32,
+            src_u: &[u8],
+            src_stride_u: u32,
+            src_v: &[u8],
+            src_stride_v: u32,
+            dst_y: &mut [$t],
+            dst_stride_y: u32,
+            dst_u: &mut [$t],
+            dst_stride_u: u32,
+            dst_v: &mut [$t],
+            dst_stride_v: u32,
+            width: i32,
+            height: i32,
+        ) {
+            i420_assert_safety(
+                src_y,
+                src_stride_y,
+                src_u,
+                src_stride_u,
+                src_v,
+                src_stride_v,
+                width,
+                height,
+            );
+            let (w, h) = (width.unsigned_abs(), height.unsigned_abs());
+            let (cw, ch) = (chroma_size(width, $shift_x), chroma_size(height, $shift_y));
+            plane_assert_safety(dst_y, dst_stride_y, w, h, "dst_y");
+            plane_assert_safety(dst_u, dst_stride_u, cw, ch, "dst_u");
+            plane_assert_safety(dst_v, dst_stride_v, cw, ch, "dst_v");
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_u.as_ptr(),
+                    src_stride_u as i32,
+                    src_v.as_ptr(),
+                    src_stride_v as i32,
+                    dst_y.as_mut_ptr(),
+                    dst_stride_y as i32,
+                    dst_u.as_mut_ptr(),
+                    dst_stride_u as i32,
+                    dst_v.as_mut_ptr(),
+                    dst_stride_v as i32,
+                    width,
+                    height,
+                )
+                .unwrap();
+            }
+        }
+    };
+}
+
+i420_to_planar!(i420_to_i422, u8, 1, 0);
+i420_to_planar!(i420_to_i444, u8, 0, 0);
+// I010 strides are in number of samples
+i420_to_planar!(i420_to_i010, u16, 1, 1);
+
+macro_rules! i420a_to_rgba {
+    ($x:ident) => {
+        pub fn $x(
+            src_y: &[u8],
+            src_stride_y: u32,
+            src_u: &[u8],
+            src_stride_u: u32,
+            src_v: &[u8],
+            src_stride_v: u32,
+            src_a: &[u8],
+            src_stride_a: u32,
+            dst: &mut [u8],
+            dst_stride: u32,
+            width: i32,
+            height: i32,
+        ) {
+            i420_assert_safety(
+                src_y,
+                src_stride_y,
+                src_u,
+                src_stride_u,
+                src_v,
+                src_stride_v,
+                width,
+                height,
+            );
+            plane_assert_safety(
+                src_a,
+                src_stride_a,
+                width.unsigned_abs(),
+                height.unsigned_abs(),
+                "src_a",
+            );
+            argb_assert_safety(dst, dst_stride, width, height);
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_u.as_ptr(),
+                    src_stride_u as i32,
+                    src_v.as_ptr(),
+                    src_stride_v as i32,
+                    src_a.as_ptr(),
+                    src_stride_a as i32,
+                    dst.as_mut_ptr(),
+                    dst_stride as i32,
+                    width,
+                    height,
+                )
+                .unwrap();
+            }
+        }
+    };
+}
+
+i420a_to_rgba!(i420a_to_argb);
+i420a_to_rgba!(i420a_to_abgr);
+
+/// Copies the alpha channel of ARGB or ABGR pixels, which is the last byte for both
+pub fn argb_extract_alpha(
+    src_argb: &[u8],
+    src_stride_argb: u32,
+    dst_a: &mut [u8],
+    dst_stride_a: u32,
+    width: i32,
+    height: i32,
+) {
+    argb_assert_safety(src_argb, src_stride_argb, width, height);
+    plane_assert_safety(dst_a, dst_stride_a, width.unsigned_abs(), height.unsigned_abs(), "dst_a");
+
+    unsafe {
+        yuv_sys::ffi::argb_extract_alpha(
+            src_argb.as_ptr(),
+            src_stride_argb as i32,
+            dst_a.as_mut_ptr(),
+            dst_stride_a as i32,
+            width,
+            height,
+        )
+        .unwrap();
+    }
+}
+
+macro_rules! rgba_shuffle {
+    ($x:ident) => {
+        pub fn $x(
+            src: &[u8],
+            src_stride: u32,
+            dst: &mut [u8],
+            dst_stride: u32,
+            width: i32,
+            height: i32,
+        ) {
+            argb_assert_safety(src, src_stride, width, height);
+            argb_assert_safety(dst, dst_stride, width, height);
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src.as_ptr(),
+                    src_stride as i32,
+                    dst.as_mut_ptr(),
+                    dst_stride as i32,
+                    width,
+                    height,
+                )
+                .unwrap();
+            }
+        }
+    };
+}
+
+// RGB <> RGB conversion, ARGB is used as the intermediate format
+rgba_shuffle!(argb_to_abgr);
+rgba_shuffle!(argb_to_bgra);
+rgba_shuffle!(argb_to_rgba);
+rgba_shuffle!(abgr_to_argb);
+rgba_shuffle!(bgra_to_argb);
+rgba_shuffle!(rgba_to_argb);
@@ -87,13 +87,44 @@ impl PeerConnectionFactory {
 pub mod native {
     use super::PeerConnectionFactory;
     use crate::{
-        audio_source::native::NativeAudioSource, audio_track::RtcAudioTrack,
-        video_source::native::NativeVideoSource, video_track::RtcVideoTrack,
+        audio_source::native::NativeAudioSource,
+        audio_track::RtcAudioTrack,
+        imp::{
+            codec_backend::VideoCodecBackends,
+            codec_support::{VideoCodecSupport, VideoCodecType},
+        },
+        video_source::native::{EncodedVideoSource, NativeVideoSource},
+        video_track::RtcVideoTrack,
     };
 
     pub trait PeerConnectionFactoryExt {
         fn create_video_track(&self, label: &str, source: NativeVideoSource) -> RtcVideoTrack;
+        fn create_encoded_video_track(
+            &self,
+            label: &str,
+            source: EncodedVideoSource,
+        ) -> RtcVideoTrack;
         fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack;
+
+        /// Lists the implementation used for every video format, per direction.
+        ///
+        /// Formats advertised in the SDP but without an available implementation are omitted.
+        /// Encoders and decoders are instantiated to be queried, so this isn't free.
+        ///
+        fn codec_support(&self) -> Vec<VideoCodecSupport>;
+
+        /// Whether an encoder is available for `codec`.
+        ///
+        /// Unlike [`PeerConnectionFactoryExt::codec_support`], no encoder is instantiated.
+        ///
+        fn has_video_encoder(&self, codec: VideoCodecType) -> bool;
+
+        /// Lists the backends tried, in order, for every video format and direction.
+        ///
+        /// Reflects the options of [`crate::native::codec_backend`], disabled backends are
+        /// omitted.
+        ///
+        fn codec_backends(&self) -> Vec<VideoCodecBackends>;
     }
 
     impl PeerConnectionFactoryExt for PeerConnectionFactory {
@@ -101,8 +132,28 @@ pub mod native {
             self.handle.create_video_track(label, source)
         }
 
+        fn create_encoded_video_track(
+            &self,
+            label: &str,
+            source: EncodedVideoSource,
+        ) -> RtcVideoTrack {
+            self.handle.create_encoded_video_track(label, source)
+        }
+
         fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack {
             self.handle.create_audio_track(label, source)
         }
+
+        fn codec_support(&self) -> Vec<VideoCodecSupport> {
+            self.handle.codec_support()
+        }
+
+        fn has_video_encoder(&self, codec: VideoCodecType) -> bool {
+            self.handle.has_video_encoder(codec)
+        }
+
+        fn codec_backends(&self) -> Vec<VideoCodecBackends> {
+            self.handle.codec_backends()
+        }
     }
 }
@@ -13,9 +13,13 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::os::fd::BorrowedFd;
 
 use thiserror::Error;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::imp::video_convert as vc_imp;
 use crate::imp::video_frame as vf_imp;
 
 #[derive(Debug, Error)]
@@ -24,6 +28,24 @@ pub enum SinkError {
     Platform(String),
 }
 
+#[derive(Debug, Error)]
+pub enum ConvertError {
+    #[error("cannot convert to a {0:?} buffer")]
+    UnsupportedTarget(VideoBufferType),
+    #[error("the destination is {dst:?} but the source is {src:?}")]
+    SizeMismatch { src: (u32, u32), dst: (u32, u32) },
+}
+
+#[derive(Debug, Error)]
+pub enum TransformError {
+    #[error("the {width}x{height} region at ({x}, {y}) isn't inside the {buffer:?} buffer")]
+    InvalidRect { x: u32, y: u32, width: u32, height: u32, buffer: (u32, u32) },
+    #[error("cannot scale to an empty {0:?} buffer")]
+    EmptyTarget((u32, u32)),
+    #[error("cannot scale an empty buffer")]
+    EmptySource,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum VideoRotation {
     VideoRotation0 = 0,
@@ -50,6 +72,27 @@ pub enum VideoBufferType {
     I444,
     I010,
     NV12,
+    /// Packed 32 bits RGB, see [`RgbaBuffer::format`]
+    Rgba,
+}
+
+/// Type of the buffer created by [`VideoBuffer::convert_to`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConvertTarget {
+    Buffer(VideoBufferType),
+    Format(VideoFormatType),
+}
+
+impl From<VideoBufferType> for ConvertTarget {
+    fn from(buffer_type: VideoBufferType) -> Self {
+        Self::Buffer(buffer_type)
+    }
+}
+
+impl From<VideoFormatType> for ConvertTarget {
+    fn from(format: VideoFormatType) -> Self {
+        Self::Format(format)
+    }
 }
 
 #[derive(Debug)]
@@ -75,6 +118,9 @@ pub(crate) mod internal {
         #[cfg(not(target_arch = "wasm32"))]
         fn to_i420(&self) -> I420Buffer;
 
+        #[cfg(not(target_arch = "wasm32"))]
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+
         #[cfg(not(target_arch = "wasm32"))]
         fn to_argb(
             &self,
@@ -120,10 +166,35 @@ pub trait VideoBuffer: internal::BufferSealed + Debug {
     fn as_nv12(&self) -> Option<&NV12Buffer> {
         None
     }
+
+    fn as_rgba(&self) -> Option<&RgbaBuffer> {
+        None
+    }
+
+    /// Converts to a new buffer of the same size.
+    ///
+    /// A [`VideoFormatType`] target creates an [`RgbaBuffer`]. Pairs without a direct libyuv
+    /// conversion go through I420, so 4:2:2 and 4:4:4 targets may lose chroma resolution.
+    ///
+    /// There is no buffer type for 24 bits RGB, so RGB24 and RAW aren't targets. Convert to ARGB
+    /// (or ABGR for RAW) and drop the alpha bytes with `yuv_helper::argb_to_rgb24`.
+    ///
+    /// Fails with [`ConvertError::UnsupportedTarget`] for [`VideoBufferType::Native`] and
+    /// [`VideoBufferType::Rgba`], use a [`VideoFormatType`] for the latter.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn convert_to(&self, target: ConvertTarget) -> Result<BoxVideoBuffer, ConvertError>;
+
+    /// Same as [`VideoBuffer::convert_to`], but writes into an existing buffer to avoid an
+    /// allocation. The target is the type (and format) of `dst`.
+    ///
+    /// Fails if `dst` is a native buffer or doesn't have the same size.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn convert_into(&self, dst: &mut dyn VideoBuffer) -> Result<(), ConvertError>;
 }
 
 macro_rules! new_buffer_type {
-    ($type:ident, $variant:ident, $as:ident) => {
+    ($(#[$attr:meta])* $type:ident, $variant:ident, $as:ident) => {
+        $(#[$attr])*
         pub struct $type {
             pub(crate) handle: vf_imp::$type,
         }
@@ -139,6 +210,11 @@ macro_rules! new_buffer_type {
                 I420Buffer { handle: self.handle.to_i420() }
             }
 
+            #[cfg(not(target_arch = "wasm32"))]
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+
             #[cfg(not(target_arch = "wasm32"))]
             fn to_argb(
                 &self,
@@ -168,6 +244,16 @@ macro_rules! new_buffer_type {
             fn $as(&self) -> Option<&$type> {
                 Some(self)
             }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            fn convert_to(&self, target: ConvertTarget) -> Result<BoxVideoBuffer, ConvertError> {
+                vc_imp::convert_to(self, target)
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            fn convert_into(&self, dst: &mut dyn VideoBuffer) -> Result<(), ConvertError> {
+                vc_imp::convert_into(self, dst)
+            }
         }
 
         impl Debug for $type {
@@ -193,6 +279,14 @@ new_buffer_type!(I422Buffer, I422, as_i422);
 new_buffer_type!(I444Buffer, I444, as_i444);
 new_buffer_type!(I010Buffer, I010, as_i010);
 new_buffer_type!(NV12Buffer, NV12, as_nv12);
+new_buffer_type!(
+    /// Packed RGB buffer with 4 bytes per pixel, ordered as described by its [`VideoFormatType`].
+    ///
+    /// WebRTC sees it as a native buffer, which is converted to I420 when needed.
+    RgbaBuffer,
+    Rgba,
+    as_rgba
+);
 
 impl I420Buffer {
     pub fn with_strides(
@@ -242,6 +336,21 @@ impl I420Buffer {
 }
 
 impl I420ABuffer {
+    pub fn with_strides(
+        width: u32,
+        height: u32,
+        stride_y: u32,
+        stride_u: u32,
+        stride_v: u32,
+        stride_a: u32,
+    ) -> I420ABuffer {
+        vf_imp::I420ABuffer::new(width, height, stride_y, stride_u, stride_v, stride_a)
+    }
+
+    pub fn new(width: u32, height: u32) -> I420ABuffer {
+        Self::with_strides(width, height, width, (width + 1) / 2, (width + 1) / 2, width)
+    }
+
     pub fn chroma_width(&self) -> u32 {
         self.handle.chroma_width()
     }
@@ -465,11 +574,530 @@ impl NV12Buffer {
     }
 }
 
+impl RgbaBuffer {
+    pub fn with_stride(
+        format: VideoFormatType,
+        width: u32,
+        height: u32,
+        stride: u32,
+    ) -> RgbaBuffer {
+        vf_imp::RgbaBuffer::new(format, width, height, stride)
+    }
+
+    pub fn new(format: VideoFormatType, width: u32, height: u32) -> RgbaBuffer {
+        Self::with_stride(format, width, height, width * 4)
+    }
+
+    pub fn format(&self) -> VideoFormatType {
+        self.handle.format()
+    }
+
+    pub fn stride(&self) -> u32 {
+        self.handle.stride()
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.handle.data()
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        let data = self.handle.data();
+        unsafe { std::slice::from_raw_parts_mut(data.as_ptr() as *mut u8, data.len()) }
+    }
+}
+
+macro_rules! impl_transforms {
+    ($type:ident) => {
+        #[cfg(not(target_arch = "wasm32"))]
+        impl $type {
+            /// Copies the `width`x`height` region at (`x`, `y`).
+            ///
+            /// With chroma subsampling, the region starts at the previous even coordinates. Fails
+            /// if the region is empty or not inside the buffer.
+            pub fn crop(
+                &self,
+                x: u32,
+                y: u32,
+                width: u32,
+                height: u32,
+            ) -> Result<$type, TransformError> {
+                self.handle.crop_and_scale(x, y, width, height, width, height)
+            }
+
+            /// Crops like [`Self::crop`], then scales the region to `scaled_width`x`scaled_height`.
+            pub fn crop_and_scale(
+                &self,
+                x: u32,
+                y: u32,
+                crop_width: u32,
+                crop_height: u32,
+                scaled_width: u32,
+                scaled_height: u32,
+            ) -> Result<$type, TransformError> {
+                self.handle.crop_and_scale(
+                    x,
+                    y,
+                    crop_width,
+                    crop_height,
+                    scaled_width,
+                    scaled_height,
+                )
+            }
+
+            /// Scales to fit `width`x`height` while keeping the aspect ratio, centered between
+            /// black bars. Fails if either buffer is empty.
+            pub fn letterbox(&self, width: u32, height: u32) -> Result<$type, TransformError> {
+                self.handle.letterbox(width, height)
+            }
+
+            /// Rotates the pixels clockwise, the dimensions are swapped for 90 and 270 degrees.
+            pub fn rotate(&self, rotation: VideoRotation) -> $type {
+                self.handle.rotate(rotation)
+            }
+
+            /// Mirrors horizontally.
+            pub fn mirror(&self) -> $type {
+                self.handle.mirror()
+            }
+        }
+    };
+}
+
+impl_transforms!(I420Buffer);
+impl_transforms!(I422Buffer);
+impl_transforms!(I444Buffer);
+impl_transforms!(I010Buffer);
+impl_transforms!(NV12Buffer);
+
+/// Location of a plane inside a dmabuf, see [`I420Buffer::from_dmabuf`]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DmabufPlane {
+    /// Offset of the first row, in bytes
+    pub offset: usize,
+    pub stride: u32,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl DmabufPlane {
+    fn end(&self, rows: u32) -> usize {
+        self.offset + self.stride as usize * rows as usize
+    }
+}
+
+/// Whether the rows of `width` luma and `chroma_width` chroma bytes fit in their strides
+#[cfg(not(target_arch = "wasm32"))]
+fn strides_fit(width: u32, stride_y: u32, chroma_width: u32, chroma_strides: &[u32]) -> bool {
+    stride_y >= width && chroma_strides.iter().all(|stride| *stride >= chroma_width)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn invalid_strides() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, "the strides are smaller than the rows")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl I420Buffer {
+    /// Wraps planes owned by the caller without copying them, e.g. V4L2 or MPP buffers.
+    ///
+    /// `release` is called once this buffer and every frame using it are dropped, including
+    /// the frames passed to `NativeVideoSource::capture_frame`. It may run on a WebRTC thread.
+    ///
+    /// # Safety
+    /// Each plane must be valid for `stride * rows` bytes until `release` is called, with
+    /// `(height + 1) / 2` chroma rows. [`Self::data_mut`] writes directly into the planes.
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn from_raw_parts(
+        width: u32,
+        height: u32,
+        data_y: *const u8,
+        stride_y: u32,
+        data_u: *const u8,
+        stride_u: u32,
+        data_v: *const u8,
+        stride_v: u32,
+        release: impl FnOnce() + Send + 'static,
+    ) -> I420Buffer {
+        vf_imp::I420Buffer::from_raw_parts(
+            width, height, data_y, stride_y, data_u, stride_u, data_v, stride_v, release,
+        )
+    }
+
+    /// Wraps a frame stored in `data` without copying it, with the planes stored one after
+    /// the other. `data` is dropped once the buffer is released.
+    ///
+    /// # Panics
+    ///
+    /// If a stride is smaller than the width of its plane, or `data` is too small.
+    pub fn from_owned<T: AsMut<[u8]> + Send + 'static>(
+        width: u32,
+        height: u32,
+        data: T,
+        stride_y: u32,
+        stride_u: u32,
+        stride_v: u32,
+    ) -> I420Buffer {
+        assert!(
+            strides_fit(width, stride_y, (width + 1) / 2, &[stride_u, stride_v]),
+            "the strides are smaller than the rows"
+        );
+        let chroma_height = (height as usize + 1) / 2;
+        let size_y = stride_y as usize * height as usize;
+        let size_u = stride_u as usize * chroma_height;
+        let size_v = stride_v as usize * chroma_height;
+
+        // Boxed so the planes don't move with `data`
+        let mut data = Box::new(data);
+        let planes = AsMut::<[u8]>::as_mut(&mut *data);
+        assert!(planes.len() >= size_y + size_u + size_v, "data is too small for the planes");
+        let data_y = planes.as_ptr();
+        unsafe {
+            Self::from_raw_parts(
+                width,
+                height,
+                data_y,
+                stride_y,
+                data_y.add(size_y),
+                stride_u,
+                data_y.add(size_y + size_u),
+                stride_v,
+                move || drop(data),
+            )
+        }
+    }
+
+    /// Wraps the planes of a dmabuf, or of any mappable fd such as a memfd.
+    ///
+    /// The fd is duplicated and mapped for reading and writing until the buffer is released,
+    /// `release` is called after unmapping it. Fails if the strides are smaller than the rows
+    /// or the fd is smaller than the planes.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_dmabuf(
+        fd: BorrowedFd<'_>,
+        width: u32,
+        height: u32,
+        y: DmabufPlane,
+        u: DmabufPlane,
+        v: DmabufPlane,
+        release: impl FnOnce() + Send + 'static,
+    ) -> std::io::Result<I420Buffer> {
+        unsafe { Self::wrap_dmabuf(fd, width, height, y, u, v, true, release) }
+    }
+
+    /// Same as [`Self::from_dmabuf`], but maps the fd read-only, e.g. for V4L2 buffers
+    /// exported without write access.
+    ///
+    /// # Safety
+    /// The planes must not be written, neither with [`Self::data_mut`] nor as the destination
+    /// of [`VideoBuffer::convert_into`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub unsafe fn from_dmabuf_read_only(
+        fd: BorrowedFd<'_>,
+        width: u32,
+        height: u32,
+        y: DmabufPlane,
+        u: DmabufPlane,
+        v: DmabufPlane,
+        release: impl FnOnce() + Send + 'static,
+    ) -> std::io::Result<I420Buffer> {
+        Self::wrap_dmabuf(fd, width, height, y, u, v, false, release)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn wrap_dmabuf(
+        fd: BorrowedFd<'_>,
+        width: u32,
+        height: u32,
+        y: DmabufPlane,
+        u: DmabufPlane,
+        v: DmabufPlane,
+        writable: bool,
+        release: impl FnOnce() + Send + 'static,
+    ) -> std::io::Result<I420Buffer> {
+        if !strides_fit(width, y.stride, (width + 1) / 2, &[u.stride, v.stride]) {
+            return Err(invalid_strides());
+        }
+        let chroma_height = (height + 1) / 2;
+        let size = y.end(height).max(u.end(chroma_height)).max(v.end(chroma_height));
+        let mapping = vf_imp::DmabufMapping::new(fd, size, writable)?;
+        let data = mapping.data();
+        unsafe {
+            Ok(Self::from_raw_parts(
+                width,
+                height,
+                data.add(y.offset),
+                y.stride,
+                data.add(u.offset),
+                u.stride,
+                data.add(v.offset),
+                v.stride,
+                move || {
+                    drop(mapping);
+                    release();
+                },
+            ))
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NV12Buffer {
+    /// Wraps planes owned by the caller without copying them, see
+    /// [`I420Buffer::from_raw_parts`].
+    ///
+    /// # Safety
+    /// Each plane must be valid for `stride * rows` bytes until `release` is called, with
+    /// `(height + 1) / 2` chroma rows. [`Self::data_mut`] writes directly into the planes.
+    pub unsafe fn from_raw_parts(
+        width: u32,
+        height: u32,
+        data_y: *const u8,
+        stride_y: u32,
+        data_uv: *const u8,
+        stride_uv: u32,
+        release: impl FnOnce() + Send + 'static,
+    ) -> NV12Buffer {
+        vf_imp::NV12Buffer::from_raw_parts(
+            width, height, data_y, stride_y, data_uv, stride_uv, release,
+        )
+    }
+
+    /// Wraps a frame stored in `data` without copying it, with the interleaved chroma plane
+    /// following the luma plane. `data` is dropped once the buffer is released.
+    ///
+    /// # Panics
+    ///
+    /// If a stride is smaller than the width of its plane, or `data` is too small.
+    pub fn from_owned<T: AsMut<[u8]> + Send + 'static>(
+        width: u32,
+        height: u32,
+        data: T,
+        stride_y: u32,
+        stride_uv: u32,
+    ) -> NV12Buffer {
+        assert!(
+            strides_fit(width, stride_y, (width + 1) / 2 * 2, &[stride_uv]),
+            "the strides are smaller than the rows"
+        );
+        let size_y = stride_y as usize * height as usize;
+        let size_uv = stride_uv as usize * ((height as usize + 1) / 2);
+
+        // Boxed so the planes don't move with `data`
+        let mut data = Box::new(data);
+        let planes = AsMut::<[u8]>::as_mut(&mut *data);
+        assert!(planes.len() >= size_y + size_uv, "data is too small for the planes");
+        let data_y = planes.as_ptr();
+        unsafe {
+            Self::from_raw_parts(
+                width,
+                height,
+                data_y,
+                stride_y,
+                data_y.add(size_y),
+                stride_uv,
+                move || drop(data),
+            )
+        }
+    }
+
+    /// Wraps the planes of a dmabuf, see [`I420Buffer::from_dmabuf`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_dmabuf(
+        fd: BorrowedFd<'_>,
+        width: u32,
+        height: u32,
+        y: DmabufPlane,
+        uv: DmabufPlane,
+        release: impl FnOnce() + Send + 'static,
+    ) -> std::io::Result<NV12Buffer> {
+        unsafe { Self::wrap_dmabuf(fd, width, height, y, uv, true, release) }
+    }
+
+    /// Wraps the planes of a read-only dmabuf, see [`I420Buffer::from_dmabuf_read_only`].
+    ///
+    /// # Safety
+    /// The planes must not be written, neither with [`Self::data_mut`] nor as the destination
+    /// of [`VideoBuffer::convert_into`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub unsafe fn from_dmabuf_read_only(
+        fd: BorrowedFd<'_>,
+        width: u32,
+        height: u32,
+        y: DmabufPlane,
+        uv: DmabufPlane,
+        release: impl FnOnce() + Send + 'static,
+    ) -> std::io::Result<NV12Buffer> {
+        Self::wrap_dmabuf(fd, width, height, y, uv, false, release)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    unsafe fn wrap_dmabuf(
+        fd: BorrowedFd<'_>,
+        width: u32,
+        height: u32,
+        y: DmabufPlane,
+        uv: DmabufPlane,
+        writable: bool,
+        release: impl FnOnce() + Send + 'static,
+    ) -> std::io::Result<NV12Buffer> {
+        if !strides_fit(width, y.stride, (width + 1) / 2 * 2, &[uv.stride]) {
+            return Err(invalid_strides());
+        }
+        let size = y.end(height).max(uv.end((height + 1) / 2));
+        let mapping = vf_imp::DmabufMapping::new(fd, size, writable)?;
+        let data = mapping.data();
+        unsafe {
+            Ok(Self::from_raw_parts(
+                width,
+                height,
+                data.add(y.offset),
+                y.stride,
+                data.add(uv.offset),
+                uv.stride,
+                move || {
+                    drop(mapping);
+                    release();
+                },
+            ))
+        }
+    }
+}
+
+macro_rules! new_buffer_pool {
+    ($(#[$attr:meta])* $pool:ident, $buffer:ident) => {
+        $(#[$attr])*
+        #[cfg(not(target_arch = "wasm32"))]
+        pub struct $pool {
+            handle: vf_imp::$pool,
+            width: u32,
+            height: u32,
+            max_buffers: usize,
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        impl $pool {
+            /// Returns a recycled buffer, or a new one if none is free.
+            ///
+            /// Returns `None` when `max_buffers` are already in use, the frame should then be
+            /// dropped. The content of a recycled buffer is left as is.
+            pub fn acquire(&self) -> Option<$buffer> {
+                self.handle.acquire()
+            }
+
+            /// Number of buffers owned by the pool, free or in use
+            pub fn allocated(&self) -> usize {
+                self.handle.allocated()
+            }
+
+            /// Frees the buffers that are not in use, e.g. after a burst of frames
+            pub fn release_unused(&self) {
+                self.handle.release_unused()
+            }
+
+            pub fn width(&self) -> u32 {
+                self.width
+            }
+
+            pub fn height(&self) -> u32 {
+                self.height
+            }
+
+            pub fn max_buffers(&self) -> usize {
+                self.max_buffers
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        impl Debug for $pool {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($pool))
+                    .field("width", &self.width)
+                    .field("height", &self.height)
+                    .field("max_buffers", &self.max_buffers)
+                    .field("allocated", &self.allocated())
+                    .finish()
+            }
+        }
+    };
+}
+
+new_buffer_pool!(
+    /// Recycles [`I420Buffer`]s of a fixed size to avoid an allocation per frame.
+    ///
+    /// A buffer returns to the pool once every handle to it is dropped, including the frames
+    /// passed to [`crate::video_source::native::NativeVideoSource::capture_frame`] once the
+    /// WebRTC pipeline is done with them. The pool can be shared between threads.
+    I420BufferPool,
+    I420Buffer
+);
+
+new_buffer_pool!(
+    /// Recycles [`NV12Buffer`]s of a fixed size, see [`I420BufferPool`].
+    NV12BufferPool,
+    NV12Buffer
+);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl I420BufferPool {
+    pub fn with_strides(
+        width: u32,
+        height: u32,
+        stride_y: u32,
+        stride_u: u32,
+        stride_v: u32,
+        max_buffers: usize,
+    ) -> I420BufferPool {
+        I420BufferPool {
+            handle: vf_imp::I420BufferPool::new(
+                width,
+                height,
+                stride_y,
+                stride_u,
+                stride_v,
+                max_buffers,
+            ),
+            width,
+            height,
+            max_buffers,
+        }
+    }
+
+    pub fn new(width: u32, height: u32, max_buffers: usize) -> I420BufferPool {
+        Self::with_strides(width, height, width, (width + 1) / 2, (width + 1) / 2, max_buffers)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NV12BufferPool {
+    pub fn with_strides(
+        width: u32,
+        height: u32,
+        stride_y: u32,
+        stride_uv: u32,
+        max_buffers: usize,
+    ) -> NV12BufferPool {
+        NV12BufferPool {
+            handle: vf_imp::NV12BufferPool::new(width, height, stride_y, stride_uv, max_buffers),
+            width,
+            height,
+            max_buffers,
+        }
+    }
+
+    pub fn new(width: u32, height: u32, max_buffers: usize) -> NV12BufferPool {
+        Self::with_strides(width, height, width, width + width % 2, max_buffers)
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
     use std::fmt::Debug;
 
-    use super::{vf_imp, I420Buffer, VideoBuffer, VideoBufferType, VideoFormatType};
+    use super::{
+        vc_imp, vf_imp, BoxVideoBuffer, ConvertTarget, I420Buffer, VideoBuffer, VideoBufferType,
+        VideoFormatType,
+    };
 
     new_buffer_type!(NativeBuffer, Native, as_native);
 
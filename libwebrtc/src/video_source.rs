@@ -29,18 +29,41 @@ impl Default for VideoResolution {
     }
 }
 
+/// Adaptation applied by a [`native::NativeVideoSource`] to the captured frames, to reduce the
+/// load of the encoder.
+#[derive(Debug, Clone)]
+pub struct VideoAdaptation {
+    /// Frames are dropped based on their `timestamp_us` to stay below this frame rate, a limit of
+    /// 0 is raised to 1 fps
+    pub max_fps: Option<u32>,
+    /// Frames are downscaled, keeping their aspect ratio, until they have at most as many
+    /// pixels as this resolution
+    pub max_resolution: Option<VideoResolution>,
+    /// Also apply the frame rate and resolution requested by the encoder, e.g. when WebRTC
+    /// detects that the CPU is overused
+    pub follow_sink_wants: bool,
+}
+
+impl Default for VideoAdaptation {
+    fn default() -> Self {
+        Self { max_fps: None, max_resolution: None, follow_sink_wants: true }
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 pub enum RtcVideoSource {
     // TODO(theomonnom): Web video sources (eq. to tracks on browsers?)
     #[cfg(not(target_arch = "wasm32"))]
     Native(native::NativeVideoSource),
+    #[cfg(not(target_arch = "wasm32"))]
+    Encoded(native::EncodedVideoSource),
 }
 
 // TODO(theomonnom): Support enum dispatch with conditional compilation?
 impl RtcVideoSource {
     enum_dispatch!(
-        [Native];
+        [Native, Encoded];
         pub fn video_resolution(self: &Self) -> VideoResolution;
     );
 }
@@ -74,6 +97,12 @@ pub mod native {
             Self { handle: vs_imp::NativeVideoSource::new(resolution) }
         }
 
+        pub fn with_adaptation(resolution: VideoResolution, adaptation: VideoAdaptation) -> Self {
+            let source = Self::new(resolution);
+            source.set_adaptation(adaptation);
+            source
+        }
+
         pub fn capture_frame<T: AsRef<dyn VideoBuffer>>(&self, frame: &VideoFrame<T>) {
             self.handle.capture_frame(frame)
         }
@@ -81,6 +110,77 @@ pub mod native {
         pub fn video_resolution(&self) -> VideoResolution {
             self.handle.video_resolution()
         }
+
+        /// Changes the adaptation of the next captured frames
+        pub fn set_adaptation(&self, adaptation: VideoAdaptation) {
+            self.handle.set_adaptation(adaptation)
+        }
+
+        pub fn adaptation(&self) -> VideoAdaptation {
+            self.handle.adaptation()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EncodedVideoCodec {
+        H264,
+        H265,
+    }
+
+    /// An Annex-B access unit, i.e. all the NAL units of a frame.
+    #[derive(Debug, Clone, Copy)]
+    pub struct EncodedVideoFrame<'a> {
+        pub data: &'a [u8],
+        /// Keyframes must start with their parameter sets (SPS/PPS, and VPS for H.265)
+        pub is_keyframe: bool,
+        /// Capture timestamp, defaults to now when 0
+        pub timestamp_us: i64,
+        /// Size of the frame, 0 keeps the size of the previous frame (initially the resolution of
+        /// the source). Set it on the keyframe that changes the resolution.
+        pub width: u32,
+        pub height: u32,
+    }
+
+    /// A video source for frames that are already encoded, they are sent without re-encoding.
+    ///
+    /// The frames must match the codec negotiated for the track, and the track must be published
+    /// without simulcast since a single layer is produced.
+    /// Use [`EncodedVideoSource::on_keyframe_request`] to know when subscribers need a keyframe
+    /// (e.g. a new subscriber or packet loss), otherwise they have to wait for the next one.
+    ///
+    #[derive(Clone)]
+    pub struct EncodedVideoSource {
+        pub(crate) handle: vs_imp::EncodedVideoSource,
+    }
+
+    impl Debug for EncodedVideoSource {
+        fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.debug_struct("EncodedVideoSource").field("codec", &self.codec()).finish()
+        }
+    }
+
+    impl EncodedVideoSource {
+        pub fn new(resolution: VideoResolution, codec: EncodedVideoCodec) -> Self {
+            Self { handle: vs_imp::EncodedVideoSource::new(resolution, codec) }
+        }
+
+        pub fn codec(&self) -> EncodedVideoCodec {
+            self.handle.codec()
+        }
+
+        /// Returns false if the frame couldn't be pushed into the pipeline
+        pub fn capture_frame(&self, frame: &EncodedVideoFrame) -> bool {
+            self.handle.capture_frame(frame)
+        }
+
+        /// Called from the encoder thread, the handler must not block
+        pub fn on_keyframe_request(&self, handler: Option<Box<dyn Fn() + Send>>) {
+            self.handle.on_keyframe_request(handler)
+        }
+
+        pub fn video_resolution(&self) -> VideoResolution {
+            self.handle.video_resolution()
+        }
     }
 }
 
@@ -26,7 +26,9 @@ pub mod native {
     };
 
     use super::stream_imp;
-    use crate::{video_frame::BoxVideoFrame, video_track::RtcVideoTrack};
+    use crate::{
+        rtp_receiver::RtpReceiver, video_frame::BoxVideoFrame, video_track::RtcVideoTrack, RtcError,
+    };
     use livekit_runtime::Stream;
 
     pub struct NativeVideoStream {
@@ -60,6 +62,77 @@ pub mod native {
             Pin::new(&mut self.get_mut().handle).poll_next(cx)
         }
     }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum VideoCodecType {
+        Generic,
+        VP8,
+        VP9,
+        AV1,
+        H264,
+        H265,
+    }
+
+    /// A frame as received from the network, before decoding.
+    #[derive(Debug, Clone)]
+    pub struct EncodedFrame {
+        pub codec: VideoCodecType,
+        pub is_keyframe: bool,
+        pub rtp_timestamp: u32,
+        pub ssrc: u32,
+        pub payload_type: u8,
+        /// Usually only set on keyframes, 0 otherwise
+        pub width: u32,
+        pub height: u32,
+        pub spatial_index: Option<u8>,
+        pub temporal_index: Option<u8>,
+        /// The bitstream of the frame, e.g. Annex-B NAL units for H.264
+        pub data: Vec<u8>,
+    }
+
+    /// Yields the encoded frames of a video receiver, e.g. to record them without decoding.
+    ///
+    /// The frames are observed through the frame transformer of the receiver, so this fails on a
+    /// receiver that already has one, e.g. for E2EE, and a frame cryptor can't be attached to the
+    /// receiver afterwards. Only one stream observes a receiver at a time, another one can be
+    /// opened once it is closed. The frames are still forwarded to the decoder.
+    ///
+    pub struct NativeEncodedVideoStream {
+        pub(crate) handle: stream_imp::NativeEncodedVideoStream,
+    }
+
+    impl Debug for NativeEncodedVideoStream {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_struct("NativeEncodedVideoStream").finish()
+        }
+    }
+
+    impl NativeEncodedVideoStream {
+        pub fn new(receiver: RtpReceiver) -> Result<Self, RtcError> {
+            Ok(Self { handle: stream_imp::NativeEncodedVideoStream::new(receiver)? })
+        }
+
+        pub fn receiver(&self) -> RtpReceiver {
+            self.handle.receiver()
+        }
+
+        /// Asks the remote sender for a keyframe (PLI), e.g. to start a recording
+        pub fn request_keyframe(&self) {
+            self.handle.request_keyframe();
+        }
+
+        pub fn close(&mut self) {
+            self.handle.close();
+        }
+    }
+
+    impl Stream for NativeEncodedVideoStream {
+        type Item = EncodedFrame;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.get_mut().handle).poll_next(cx)
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
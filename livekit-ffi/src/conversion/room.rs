@@ -176,6 +176,7 @@ impl From<proto::RoomOptions> for RoomOptions {
                 } else {
                     KeyProvider::new(provider_opts.into())
                 },
+                frame_transformer: None,
             })
         });
 
@@ -191,6 +192,7 @@ impl From<proto::RoomOptions> for RoomOptions {
                 } else {
                     KeyProvider::new(provider_opts.into())
                 },
+                frame_transformer: None,
             })
         });
 
@@ -67,6 +67,7 @@ impl FfiParticipant {
                         .response_timeout_ms
                         .map(|ms| Duration::from_millis(ms as u64))
                         .unwrap_or(PerformRpcData::default().response_timeout),
+                    ..Default::default()
                 })
                 .await;
 
@@ -18,7 +18,8 @@ pub use crate::{
     id::*,
     participant::{
         ConnectionQuality, DisconnectReason, LocalParticipant, Participant, PerformRpcData,
-        RemoteParticipant, RpcError, RpcErrorCode, RpcInvocationData,
+        PerformTypedRpcData, RemoteParticipant, RpcCancellationToken, RpcError, RpcErrorCode,
+        RpcFuture, RpcInterceptor, RpcInvocationData, RpcMethodInfo, RpcNext, RpcTargets,
     },
     publication::{LocalTrackPublication, RemoteTrackPublication, TrackPublication},
     track::{
@@ -15,7 +15,7 @@
 use super::{
     AnyStreamInfo, ByteStreamInfo, StreamError, StreamProgress, StreamResult, TextStreamInfo,
 };
-use crate::{e2ee::EncryptionType, TakeCell};
+use crate::{e2ee::EncryptionType, id::ParticipantIdentity, TakeCell};
 use bytes::{Bytes, BytesMut};
 use futures_util::{Stream, StreamExt};
 use livekit_protocol::data_stream as proto;
@@ -23,11 +23,15 @@ use parking_lot::Mutex;
 use std::{
     collections::HashMap,
     fmt::Debug,
+    future::Future,
     pin::Pin,
     sync::Arc,
-    task::{Context, Poll},
+    task::{ready, Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, ReadBuf},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
 };
-use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
 /// Reader for an incoming data stream.
 ///
@@ -70,9 +74,15 @@ where
 }
 
 /// Reader for an incoming byte data stream.
+///
+/// In addition to being a [`Stream`] of chunks, this reader implements [`AsyncRead`],
+/// allowing it to be used with `tokio::io::copy` and other I/O utilities.
+///
 pub struct ByteStreamReader {
     info: ByteStreamInfo,
     chunk_rx: UnboundedReceiver<StreamResult<Bytes>>,
+    /// Remainder of a chunk partially consumed through [`AsyncRead`].
+    pending: Bytes,
 }
 
 /// Reader for an incoming text data stream.
@@ -139,6 +149,9 @@ impl Stream for ByteStreamReader {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
+        if !this.pending.is_empty() {
+            return Poll::Ready(Some(Ok(std::mem::take(&mut this.pending))));
+        }
         match Pin::new(&mut this.chunk_rx).poll_recv(cx) {
             Poll::Ready(Some(Ok(chunk))) => Poll::Ready(Some(Ok(chunk))),
             Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
@@ -148,6 +161,26 @@ impl Stream for ByteStreamReader {
     }
 }
 
+impl AsyncRead for ByteStreamReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        while this.pending.is_empty() {
+            match ready!(Pin::new(&mut this.chunk_rx).poll_recv(cx)) {
+                Some(Ok(chunk)) => this.pending = chunk,
+                Some(Err(e)) => return Poll::Ready(Err(e.into())),
+                None => return Poll::Ready(Ok(())), // EOF
+            }
+        }
+        let len = std::cmp::min(this.pending.len(), buf.remaining());
+        buf.put_slice(&this.pending.split_to(len));
+        Poll::Ready(Ok(()))
+    }
+}
+
 impl StreamReader for TextStreamReader {
     type Output = String;
     type Info = TextStreamInfo;
@@ -206,6 +239,18 @@ impl Debug for TextStreamReader {
     }
 }
 
+pub(crate) type ByteStreamHandler = Arc<
+    dyn Fn(ByteStreamReader, ParticipantIdentity) -> Pin<Box<dyn Future<Output = ()> + Send>>
+        + Send
+        + Sync,
+>;
+
+pub(crate) type TextStreamHandler = Arc<
+    dyn Fn(TextStreamReader, ParticipantIdentity) -> Pin<Box<dyn Future<Output = ()> + Send>>
+        + Send
+        + Sync,
+>;
+
 pub(crate) enum AnyStreamReader {
     Byte(ByteStreamReader),
     Text(TextStreamReader),
@@ -216,7 +261,9 @@ impl AnyStreamReader {
     pub(super) fn from(info: AnyStreamInfo) -> (Self, UnboundedSender<StreamResult<Bytes>>) {
         let (chunk_tx, chunk_rx) = mpsc::unbounded_channel();
         let reader = match info {
-            AnyStreamInfo::Byte(info) => Self::Byte(ByteStreamReader { info, chunk_rx }),
+            AnyStreamInfo::Byte(info) => {
+                Self::Byte(ByteStreamReader { info, chunk_rx, pending: Bytes::new() })
+            }
             AnyStreamInfo::Text(info) => Self::Text(TextStreamReader { info, chunk_rx }),
         };
         return (reader, chunk_tx);
@@ -238,6 +285,10 @@ pub(crate) struct IncomingStreamManager {
 #[derive(Default)]
 struct ManagerInner {
     open_streams: HashMap<String, Descriptor>,
+    byte_handlers: HashMap<String, ByteStreamHandler>,
+    text_handlers: HashMap<String, TextStreamHandler>,
+    /// Handlers for text streams on topics used internally by the SDK.
+    reserved_text_handlers: HashMap<String, TextStreamHandler>,
 }
 
 impl IncomingStreamManager {
@@ -270,7 +321,29 @@ impl IncomingStreamManager {
         }
 
         let (reader, chunk_tx) = AnyStreamReader::from(info);
-        let _ = self.open_tx.send((reader, identity));
+        match reader {
+            AnyStreamReader::Byte(reader) => {
+                if let Some(handler) = inner.byte_handlers.get(&reader.info.topic).cloned() {
+                    livekit_runtime::spawn(handler(reader, identity.into()));
+                } else {
+                    inner.warn_if_unhandled(&reader.info.topic);
+                    let _ = self.open_tx.send((AnyStreamReader::Byte(reader), identity));
+                }
+            }
+            AnyStreamReader::Text(reader) => {
+                if let Some(handler) = inner
+                    .reserved_text_handlers
+                    .get(&reader.info.topic)
+                    .or_else(|| inner.text_handlers.get(&reader.info.topic))
+                    .cloned()
+                {
+                    livekit_runtime::spawn(handler(reader, identity.into()));
+                } else {
+                    inner.warn_if_unhandled(&reader.info.topic);
+                    let _ = self.open_tx.send((AnyStreamReader::Text(reader), identity));
+                }
+            }
+        }
 
         let descriptor = Descriptor {
             progress: StreamProgress { bytes_total, ..Default::default() },
@@ -280,6 +353,56 @@ impl IncomingStreamManager {
         inner.open_streams.insert(id, descriptor);
     }
 
+    /// Registers a handler for byte streams opened on the given topic.
+    pub fn register_byte_stream_handler(
+        &self,
+        topic: String,
+        handler: ByteStreamHandler,
+    ) -> StreamResult<()> {
+        let mut inner = self.inner.lock();
+        if inner.reserved_text_handlers.contains_key(&topic) {
+            Err(StreamError::ReservedTopic(topic))?
+        }
+        if inner.byte_handlers.contains_key(&topic) {
+            Err(StreamError::HandlerAlreadyRegistered(topic))?
+        }
+        inner.byte_handlers.insert(topic, handler);
+        Ok(())
+    }
+
+    /// Registers a handler for text streams opened on the given topic.
+    pub fn register_text_stream_handler(
+        &self,
+        topic: String,
+        handler: TextStreamHandler,
+    ) -> StreamResult<()> {
+        let mut inner = self.inner.lock();
+        if inner.reserved_text_handlers.contains_key(&topic) {
+            Err(StreamError::ReservedTopic(topic))?
+        }
+        if inner.text_handlers.contains_key(&topic) {
+            Err(StreamError::HandlerAlreadyRegistered(topic))?
+        }
+        inner.text_handlers.insert(topic, handler);
+        Ok(())
+    }
+
+    /// Routes text streams opened on a topic used internally by the SDK to the given handler.
+    ///
+    /// Reserved topics take precedence over the application's handlers, can't be registered
+    /// or unregistered by the application, and are never emitted as room events.
+    pub(crate) fn set_reserved_text_stream_handler(&self, topic: &str, handler: TextStreamHandler) {
+        self.inner.lock().reserved_text_handlers.insert(topic.to_string(), handler);
+    }
+
+    pub fn unregister_byte_stream_handler(&self, topic: &str) {
+        self.inner.lock().byte_handlers.remove(topic);
+    }
+
+    pub fn unregister_text_stream_handler(&self, topic: &str) {
+        self.inner.lock().text_handlers.remove(topic);
+    }
+
     /// Handles an incoming chunk packet.
     pub fn handle_chunk(
         &self,
@@ -340,6 +463,15 @@ impl IncomingStreamManager {
 }
 
 impl ManagerInner {
+    /// Warns about a stream whose topic has no handler once the application has opted
+    /// into handler-based routing; the stream is still emitted as a room event.
+    fn warn_if_unhandled(&self, topic: &str) {
+        if self.byte_handlers.is_empty() && self.text_handlers.is_empty() {
+            return;
+        }
+        log::warn!("No stream handler registered for topic '{}', emitting room event", topic);
+    }
+
     fn yield_chunk(&mut self, id: &str, chunk: Bytes) {
         let Some(descriptor) = self.open_streams.get_mut(id) else {
             return;
@@ -21,6 +21,7 @@ mod incoming;
 mod outgoing;
 
 pub use incoming::*;
+pub(crate) use outgoing::PacketReceiver;
 pub use outgoing::*;
 
 use crate::e2ee::EncryptionType;
@@ -64,6 +65,21 @@ pub enum StreamError {
 
     #[error("encryption type mismatch")]
     EncryptionTypeMismatch,
+
+    #[error("a handler is already registered for topic '{0}'")]
+    HandlerAlreadyRegistered(String),
+
+    #[error("topic '{0}' is reserved")]
+    ReservedTopic(String),
+}
+
+impl From<StreamError> for std::io::Error {
+    fn from(error: StreamError) -> Self {
+        match error {
+            StreamError::Io(e) => e,
+            other => std::io::Error::new(std::io::ErrorKind::Other, other),
+        }
+    }
 }
 
 /// Progress of a data stream.
@@ -22,8 +22,21 @@ use bmrng::unbounded::{UnboundedRequestReceiver, UnboundedRequestSender};
 use chrono::Utc;
 use libwebrtc::native::create_random_uuid;
 use livekit_protocol as proto;
-use std::{collections::HashMap, path::Path, sync::Arc};
-use tokio::{io::AsyncReadExt, sync::Mutex};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite},
+    sync::{
+        oneshot::{self, error::TryRecvError},
+        Mutex,
+    },
+};
 
 /// Writer for an open data stream.
 pub trait StreamWriter<'a> {
@@ -52,11 +65,41 @@ pub trait StreamWriter<'a> {
     ) -> impl std::future::Future<Output = StreamResult<()>> + Send;
 }
 
-#[derive(Clone)]
 /// Writer for an open byte data stream.
+///
+/// In addition to [`StreamWriter`], this writer implements [`AsyncWrite`], allowing
+/// it to be used with `tokio::io::copy` and other I/O utilities. Shutting down the
+/// writer closes the stream normally.
+///
 pub struct ByteStreamWriter {
     info: Arc<ByteStreamInfo>,
     stream: Arc<Mutex<RawStream>>,
+    /// Operations in flight on behalf of the [`AsyncWrite`] implementation.
+    io_state: parking_lot::Mutex<IoState>,
+}
+
+impl Clone for ByteStreamWriter {
+    fn clone(&self) -> Self {
+        Self { info: self.info.clone(), stream: self.stream.clone(), io_state: Default::default() }
+    }
+}
+
+type IoFuture = Pin<Box<dyn Future<Output = StreamResult<()>> + Send>>;
+
+#[derive(Default)]
+struct IoState {
+    /// Pending chunk write started by `poll_write`.
+    write: Option<PendingWrite>,
+    /// Pending trailer write started by `poll_shutdown`.
+    shutdown: Option<IoFuture>,
+}
+
+struct PendingWrite {
+    future: IoFuture,
+    /// Number of bytes of the buffer sent in the chunk.
+    len: usize,
+    /// Length of the buffer the write was started for.
+    buf_len: usize,
 }
 
 #[derive(Clone)]
@@ -92,13 +135,12 @@ impl<'a> StreamWriter<'a> for ByteStreamWriter {
 }
 
 impl ByteStreamWriter {
-    /// Writes the contents of the file incrementally.
-    async fn write_file_contents(&self, path: impl AsRef<Path>) -> StreamResult<()> {
+    /// Writes the contents of the reader incrementally until it reaches EOF.
+    async fn write_reader_contents(&self, mut reader: impl AsyncRead + Unpin) -> StreamResult<()> {
         let mut stream = self.stream.lock().await;
-        let mut file = tokio::fs::File::open(path).await?;
         let mut buffer = vec![0; 8192]; // 8KB
         loop {
-            let bytes_read = file.read(&mut buffer).await?;
+            let bytes_read = reader.read(&mut buffer).await?;
             if bytes_read == 0 {
                 break;
             }
@@ -106,6 +148,75 @@ impl ByteStreamWriter {
         }
         Ok(())
     }
+
+    /// Drives the pending chunk write (if any) to completion.
+    fn poll_pending_write(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<usize>> {
+        let io_state = self.io_state.get_mut();
+        let Some(write) = io_state.write.as_mut() else {
+            return Poll::Ready(Ok(0));
+        };
+        let result = ready!(write.future.as_mut().poll(cx));
+        let len = write.len;
+        io_state.write = None;
+        Poll::Ready(result.map(|_| len).map_err(Into::into))
+    }
+}
+
+impl AsyncWrite for ByteStreamWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        match &this.io_state.get_mut().write {
+            None => {
+                if buf.is_empty() {
+                    return Poll::Ready(Ok(0));
+                }
+                let len = std::cmp::min(buf.len(), CHUNK_SIZE);
+                let chunk = buf[..len].to_vec();
+                let stream = this.stream.clone();
+                let future: IoFuture =
+                    Box::pin(async move { stream.lock().await.write_chunk(&chunk).await });
+                this.io_state.get_mut().write =
+                    Some(PendingWrite { future, len, buf_len: buf.len() });
+            }
+            // The chunk is already sent, the count would apply to another buffer
+            Some(write) if write.buf_len != buf.len() => {
+                return Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "poll_write retried with another buffer while a chunk is pending",
+                )));
+            }
+            Some(_) => {}
+        }
+        // Only one chunk is in flight at a time; a caller retrying after `Pending`
+        // is told how much of the original buffer was consumed.
+        this.poll_pending_write(cx)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().poll_pending_write(cx).map_ok(|_| ())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending_write(cx))?;
+
+        let io_state = this.io_state.get_mut();
+        let future = io_state.shutdown.get_or_insert_with(|| {
+            let stream = this.stream.clone();
+            let future: IoFuture = Box::pin(async move { stream.lock().await.close(None).await });
+            future
+        });
+        let result = ready!(future.as_mut().poll(cx));
+        io_state.shutdown = None;
+        match result {
+            Ok(()) | Err(StreamError::AlreadyClosed) => Poll::Ready(Ok(())),
+            Err(e) => Poll::Ready(Err(e.into())),
+        }
+    }
 }
 
 impl<'a> StreamWriter<'a> for TextStreamWriter {
@@ -133,10 +244,21 @@ impl<'a> StreamWriter<'a> for TextStreamWriter {
     }
 }
 
+/// Resolves once an enqueued packet has been handed to the data channel.
+pub(crate) type PacketCompletion = oneshot::Receiver<Result<(), EngineError>>;
+
+/// Request channel for enqueuing packets, which responds once the engine has accepted
+/// the packet. Acceptance is subject to flow control on the reliable data channel.
+type PacketSender =
+    UnboundedRequestSender<proto::DataPacket, Result<PacketCompletion, EngineError>>;
+
+pub(crate) type PacketReceiver =
+    UnboundedRequestReceiver<proto::DataPacket, Result<PacketCompletion, EngineError>>;
+
 struct RawStreamOpenOptions {
     header: proto::data_stream::Header,
     destination_identities: Vec<ParticipantIdentity>,
-    packet_tx: UnboundedRequestSender<proto::DataPacket, Result<(), EngineError>>,
+    packet_tx: PacketSender,
 }
 
 struct RawStream {
@@ -144,7 +266,11 @@ struct RawStream {
     progress: StreamProgress,
     is_closed: bool,
     /// Request channel for sending packets.
-    packet_tx: UnboundedRequestSender<proto::DataPacket, Result<(), EngineError>>,
+    packet_tx: PacketSender,
+    /// Completions of the chunks not yet handed to the data channel, in order.
+    chunk_completions: VecDeque<PacketCompletion>,
+    /// First chunk that couldn't be sent, the stream is incomplete from then on.
+    chunk_error: Option<StreamError>,
 }
 
 impl RawStream {
@@ -160,35 +286,99 @@ impl RawStream {
             progress: StreamProgress { bytes_total, ..Default::default() },
             is_closed: false,
             packet_tx: options.packet_tx,
+            chunk_completions: VecDeque::new(),
+            chunk_error: None,
         })
     }
 
+    /// Writes a chunk, returning once it has been accepted for sending.
+    ///
+    /// Chunks are pipelined: this waits for backpressure from the reliable data channel
+    /// rather than for each chunk to be handed to the data channel.
+    ///
     async fn write_chunk(&mut self, bytes: &[u8]) -> StreamResult<()> {
+        self.poll_sent_chunks();
+        if self.chunk_error.is_some() {
+            Err(StreamError::SendFailed)?
+        }
         let packet = Self::create_chunk_packet(&self.id, self.progress.chunk_index, bytes);
-        Self::send_packet(&self.packet_tx, packet).await?;
+        match Self::enqueue_packet(&self.packet_tx, packet).await {
+            Ok(completion) => self.chunk_completions.push_back(completion),
+            Err(e) => {
+                self.chunk_error = Some(Self::copy_send_error(&e));
+                Err(e)?
+            }
+        }
         self.progress.bytes_processed += bytes.len() as u64;
         self.progress.chunk_index += 1;
         Ok(())
     }
 
+    /// Closes the stream, or returns the error of the first chunk that couldn't be sent after
+    /// closing it abnormally.
     async fn close(&mut self, reason: Option<&str>) -> StreamResult<()> {
         if self.is_closed {
             Err(StreamError::AlreadyClosed)?
         }
+        while let Some(completion) = self.chunk_completions.pop_front() {
+            if let Err(e) = Self::completion_result(completion.await) {
+                self.chunk_error.get_or_insert(e);
+            }
+        }
+        let reason = match self.chunk_error {
+            Some(_) => Some(reason.unwrap_or(CHUNK_FAILED_REASON)),
+            None => reason,
+        };
         let packet = Self::create_trailer_packet(&self.id, reason);
         Self::send_packet(&self.packet_tx, packet).await?;
         self.is_closed = true;
-        Ok(())
+        match self.chunk_error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Drops the completions of the chunks handed to the data channel, recording the first
+    /// one that failed.
+    fn poll_sent_chunks(&mut self) {
+        while let Some(completion) = self.chunk_completions.front_mut() {
+            let result = match completion.try_recv() {
+                Err(TryRecvError::Empty) => break,
+                result => Self::completion_result(result),
+            };
+            self.chunk_completions.pop_front();
+            if let Err(e) = result {
+                self.chunk_error.get_or_insert(e);
+            }
+        }
+    }
+
+    /// Copies an error of [`Self::enqueue_packet`], to return it from [`Self::close`] as well.
+    fn copy_send_error(e: &StreamError) -> StreamError {
+        match e {
+            StreamError::SendFailed => StreamError::SendFailed,
+            _ => StreamError::Internal,
+        }
+    }
+
+    fn completion_result<E>(result: Result<Result<(), EngineError>, E>) -> StreamResult<()> {
+        result
+            .map_err(|_| StreamError::Internal)? // engine dropped the request
+            .map_err(|_| StreamError::SendFailed) // data channel error
+    }
+
+    async fn send_packet(tx: &PacketSender, packet: proto::DataPacket) -> StreamResult<()> {
+        Self::completion_result(Self::enqueue_packet(tx, packet).await?.await)
     }
 
-    async fn send_packet(
-        tx: &UnboundedRequestSender<proto::DataPacket, Result<(), EngineError>>,
+    async fn enqueue_packet(
+        tx: &PacketSender,
         packet: proto::DataPacket,
-    ) -> StreamResult<()> {
+    ) -> StreamResult<PacketCompletion> {
         tx.send_receive(packet)
             .await
             .map_err(|_| StreamError::Internal)? // request channel closed
-            .map_err(|_| StreamError::SendFailed) // data channel error
+            .map_err(|_| StreamError::SendFailed) // engine error
     }
 
     fn create_header_packet(
@@ -236,12 +426,13 @@ impl RawStream {
 }
 
 impl Drop for RawStream {
-    /// Close stream normally if not already closed.
+    /// Close stream normally if not already closed, unless a chunk couldn't be sent.
     fn drop(&mut self) {
         if self.is_closed {
             return;
         }
-        let packet = Self::create_trailer_packet(&self.id, None);
+        let reason = self.chunk_error.as_ref().map(|_| CHUNK_FAILED_REASON);
+        let packet = Self::create_trailer_packet(&self.id, reason);
         let packet_tx = self.packet_tx.clone();
         tokio::spawn(async move { Self::send_packet(&packet_tx, packet).await });
     }
@@ -276,11 +467,11 @@ pub struct StreamTextOptions {
 #[derive(Clone)]
 pub(crate) struct OutgoingStreamManager {
     /// Request channel for sending packets.
-    packet_tx: UnboundedRequestSender<proto::DataPacket, Result<(), EngineError>>,
+    packet_tx: PacketSender,
 }
 
 impl OutgoingStreamManager {
-    pub fn new() -> (Self, UnboundedRequestReceiver<proto::DataPacket, Result<(), EngineError>>) {
+    pub fn new() -> (Self, PacketReceiver) {
         let (packet_tx, packet_rx) = bmrng::unbounded_channel();
         let manager = Self { packet_tx };
         (manager, packet_rx)
@@ -341,6 +532,7 @@ impl OutgoingStreamManager {
         let writer = ByteStreamWriter {
             info: Arc::new(ByteStreamInfo::from_headers(header, byte_header)),
             stream: Arc::new(Mutex::new(RawStream::open(open_options).await?)),
+            io_state: Default::default(),
         };
         Ok(writer)
     }
@@ -426,6 +618,7 @@ impl OutgoingStreamManager {
         let writer = ByteStreamWriter {
             info: Arc::new(ByteStreamInfo::from_headers(header, byte_header)),
             stream: Arc::new(Mutex::new(RawStream::open(open_options).await?)),
+            io_state: Default::default(),
         };
 
         let info = (*writer.info).clone();
@@ -469,10 +662,53 @@ impl OutgoingStreamManager {
         let writer = ByteStreamWriter {
             info: Arc::new(ByteStreamInfo::from_headers(header, byte_header)),
             stream: Arc::new(Mutex::new(RawStream::open(open_options).await?)),
+            io_state: Default::default(),
         };
 
+        let file = tokio::fs::File::open(path).await?;
         let info = (*writer.info).clone();
-        writer.write_file_contents(path).await?;
+        writer.write_reader_contents(file).await?;
+        writer.close().await?;
+
+        Ok(info)
+    }
+
+    /// Send the contents of a reader to participants in the room.
+    ///
+    /// The reader is consumed until EOF; unless `options.total_length` is
+    /// specified, the stream is opened without a known length.
+    pub async fn send_reader(
+        &self,
+        reader: impl AsyncRead + Unpin,
+        options: StreamByteOptions,
+    ) -> StreamResult<ByteStreamInfo> {
+        let byte_header = proto::data_stream::ByteHeader { name: options.name.unwrap_or_default() };
+        let header = proto::data_stream::Header {
+            stream_id: options.id.unwrap_or_else(|| create_random_uuid()),
+            timestamp: Utc::now().timestamp_millis(),
+            topic: options.topic,
+            mime_type: options.mime_type.unwrap_or_else(|| BYTE_MIME_TYPE.to_owned()),
+            total_length: options.total_length,
+            encryption_type: proto::encryption::Type::None.into(),
+            attributes: options.attributes,
+            content_header: Some(proto::data_stream::header::ContentHeader::ByteHeader(
+                byte_header.clone(),
+            )),
+        };
+
+        let open_options = RawStreamOpenOptions {
+            header: header.clone(),
+            destination_identities: options.destination_identities,
+            packet_tx: self.packet_tx.clone(),
+        };
+        let writer = ByteStreamWriter {
+            info: Arc::new(ByteStreamInfo::from_headers(header, byte_header)),
+            stream: Arc::new(Mutex::new(RawStream::open(open_options).await?)),
+            io_state: Default::default(),
+        };
+
+        let info = (*writer.info).clone();
+        writer.write_reader_contents(reader).await?;
         writer.close().await?;
 
         Ok(info)
@@ -482,8 +718,97 @@ impl OutgoingStreamManager {
 /// Maximum number of bytes to send in a single chunk.
 static CHUNK_SIZE: usize = 15000;
 
+/// Reason of the trailer closing a stream with a chunk that couldn't be sent.
+static CHUNK_FAILED_REASON: &str = "failed to send a chunk";
+
 // Default MIME type to use for byte streams.
 static BYTE_MIME_TYPE: &str = "application/octet-stream";
 
 /// Default MIME type to use for text streams.
 static TEXT_MIME_TYPE: &str = "text/plain";
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, io::ErrorKind};
+
+    use tokio::{io::AsyncWriteExt, task::JoinHandle};
+
+    use super::*;
+
+    /// Answers the packets like the engine, the chunks with `chunk_result` once `release`
+    /// resolves, and returns them once the stream is dropped.
+    fn engine(
+        mut packet_rx: PacketReceiver,
+        release: oneshot::Receiver<()>,
+        chunk_result: fn() -> Result<(), EngineError>,
+    ) -> JoinHandle<Vec<proto::DataPacket>> {
+        tokio::spawn(async move {
+            let mut release = Some(release);
+            let mut packets = Vec::new();
+            while let Ok((packet, responder)) = packet_rx.recv().await {
+                let result = match &packet.value {
+                    Some(proto::data_packet::Value::StreamChunk(_)) => {
+                        if let Some(release) = release.take() {
+                            let _ = release.await;
+                        }
+                        chunk_result()
+                    }
+                    _ => Ok(()),
+                };
+                let (tx, rx) = oneshot::channel();
+                let _ = tx.send(result);
+                let _ = responder.respond(Ok(rx));
+                packets.push(packet);
+            }
+            packets
+        })
+    }
+
+    #[tokio::test]
+    async fn close_returns_the_chunk_error() {
+        let (manager, packet_rx) = OutgoingStreamManager::new();
+        let (release, released) = oneshot::channel();
+        let engine = engine(packet_rx, released, || {
+            Err(EngineError::Internal("data channel closed".into()))
+        });
+        let _ = release.send(());
+
+        let writer = manager.stream_bytes(StreamByteOptions::default()).await.unwrap();
+        // Chunks are pipelined, the failure is only known once the chunk is handed over
+        StreamWriter::write(&writer, &[1, 2, 3]).await.unwrap();
+        assert!(matches!(writer.close().await, Err(StreamError::SendFailed)));
+
+        drop(manager);
+        let packets = engine.await.unwrap();
+        let Some(proto::data_packet::Value::StreamTrailer(trailer)) = &packets[2].value else {
+            panic!("expected a trailer");
+        };
+        assert_eq!(trailer.reason, CHUNK_FAILED_REASON);
+    }
+
+    #[tokio::test]
+    async fn poll_write_retried_with_another_buffer() {
+        let (manager, packet_rx) = OutgoingStreamManager::new();
+        let (release, released) = oneshot::channel();
+        let engine = engine(packet_rx, released, || Ok(()));
+
+        let mut writer = manager.stream_bytes(StreamByteOptions::default()).await.unwrap();
+        poll_fn(|cx| {
+            let mut writer = Pin::new(&mut writer);
+            assert!(writer.as_mut().poll_write(cx, &[0; 10]).is_pending());
+            match writer.as_mut().poll_write(cx, &[0; 5]) {
+                Poll::Ready(Err(e)) => assert_eq!(e.kind(), ErrorKind::InvalidInput),
+                _ => panic!("expected an error"),
+            }
+            Poll::Ready(())
+        })
+        .await;
+
+        let _ = release.send(());
+        assert_eq!(AsyncWriteExt::write(&mut writer, &[0; 10]).await.unwrap(), 10);
+        writer.shutdown().await.unwrap();
+
+        drop((writer, manager));
+        assert_eq!(engine.await.unwrap().len(), 3);
+    }
+}
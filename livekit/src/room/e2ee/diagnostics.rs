@@ -0,0 +1,77 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use libwebrtc::native::frame_cryptor::EncryptionState;
+
+/// Counters of a single frame cryptor, i.e. of a (participant, track) pair.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameCryptorDiagnostics {
+    /// Last state reported by the frame cryptor
+    pub state: Option<EncryptionState>,
+    /// Key index used to encrypt for a sender, or carried by the last frame that changed the
+    /// state of a receiver
+    pub key_index: i32,
+    pub encryption_failures: u64,
+    pub decryption_failures: u64,
+    pub missing_key_events: u64,
+    /// Number of times the key was ratcheted to decrypt a frame
+    pub ratchet_attempts: u64,
+    pub internal_errors: u64,
+    /// Number of times decryption was retried after a new key was set
+    pub retries: u64,
+}
+
+impl FrameCryptorDiagnostics {
+    pub(crate) fn new(key_index: i32) -> Self {
+        Self { key_index, ..Default::default() }
+    }
+
+    pub(crate) fn record(&mut self, state: EncryptionState) {
+        match state {
+            EncryptionState::EncryptionFailed => self.encryption_failures += 1,
+            EncryptionState::DecryptionFailed => self.decryption_failures += 1,
+            EncryptionState::MissingKey => self.missing_key_events += 1,
+            EncryptionState::KeyRatcheted => self.ratchet_attempts += 1,
+            EncryptionState::InternalError => self.internal_errors += 1,
+            EncryptionState::New | EncryptionState::Ok => {}
+        }
+        self.state = Some(state);
+    }
+
+    /// Whether the frame cryptor failed to decrypt because it didn't have the right key
+    pub fn needs_key(&self) -> bool {
+        matches!(self.state, Some(EncryptionState::MissingKey | EncryptionState::DecryptionFailed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_states() {
+        let mut diagnostics = FrameCryptorDiagnostics::new(2);
+        diagnostics.record(EncryptionState::MissingKey);
+        diagnostics.record(EncryptionState::KeyRatcheted);
+        diagnostics.record(EncryptionState::DecryptionFailed);
+        assert!(diagnostics.needs_key());
+        assert_eq!(diagnostics.missing_key_events, 1);
+        assert_eq!(diagnostics.ratchet_attempts, 1);
+        assert_eq!(diagnostics.decryption_failures, 1);
+
+        diagnostics.record(EncryptionState::Ok);
+        assert!(!diagnostics.needs_key());
+        assert_eq!(diagnostics.key_index, 2);
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Distribution of per-participant sender keys.
+//!
+//! Each participant generates an X25519 keypair and publishes the public key in its
+//! attributes. Sender keys are wrapped for every other participant with a key derived from
+//! the X25519 shared secret, and delivered over RPC.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use super::key_rotation::next_key_index;
+use crate::{id::ParticipantIdentity, participant::LocalParticipant, prelude::PerformRpcData};
+
+/// Attribute holding the base64 encoded X25519 public key of a participant.
+pub const PUBLIC_KEY_ATTRIBUTE: &str = "lk.e2ee.public_key";
+
+/// Reserved RPC method used to deliver wrapped sender keys.
+pub const KEY_EXCHANGE_RPC_METHOD: &str = "lk.e2ee.wrapped_key";
+
+/// Number of slots in the key ring of a frame cryptor. Sender key indices wrap around it.
+pub(crate) const KEY_RING_SIZE: i32 = 16;
+
+const SENDER_KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const HKDF_INFO: &[u8] = b"LKKeyExchange";
+
+#[derive(Debug, Error)]
+pub enum KeyExchangeError {
+    #[error("e2ee is not initialized")]
+    NotInitialized,
+    #[error("key exchange is not enabled")]
+    NotEnabled,
+    #[error("invalid public key")]
+    InvalidPublicKey,
+    #[error("invalid key exchange message: {0}")]
+    InvalidMessage(String),
+    #[error("failed to unwrap sender key")]
+    UnwrapFailed,
+}
+
+/// Delivers key exchange data to other participants.
+///
+/// Methods are called without any lock held and shouldn't block; implementations are
+/// expected to send in the background.
+pub trait KeyExchangeTransport: Send + Sync {
+    /// Publishes the base64 encoded public key of the local participant.
+    fn publish_public_key(&self, public_key: String);
+
+    /// Sends a wrapped sender key to a single participant, who should pass it to
+    /// [`E2eeManager::handle_key_exchange_message`](super::manager::E2eeManager::handle_key_exchange_message).
+    fn send_wrapped_key(&self, destination: ParticipantIdentity, message: String);
+}
+
+/// A sender key, encrypted for a single recipient.
+#[derive(Serialize, Deserialize)]
+struct WrappedKey {
+    /// Public key of the sender, so the recipient doesn't depend on attribute updates.
+    public_key: String,
+    key_index: i32,
+    nonce: String,
+    ciphertext: String,
+}
+
+pub(crate) struct KeyExchange {
+    local_identity: ParticipantIdentity,
+    secret: StaticSecret,
+    public_key: PublicKey,
+    sender_key: Vec<u8>,
+    key_index: i32,
+    peers: HashMap<ParticipantIdentity, PublicKey>,
+}
+
+impl KeyExchange {
+    pub fn new(local_identity: ParticipantIdentity) -> Self {
+        let secret = StaticSecret::from(rand::random::<[u8; 32]>());
+        let public_key = PublicKey::from(&secret);
+        Self {
+            local_identity,
+            secret,
+            public_key,
+            sender_key: random_sender_key(),
+            key_index: 0,
+            peers: HashMap::new(),
+        }
+    }
+
+    pub fn local_identity(&self) -> &ParticipantIdentity {
+        &self.local_identity
+    }
+
+    pub fn public_key(&self) -> String {
+        BASE64.encode(self.public_key.as_bytes())
+    }
+
+    pub fn sender_key(&self) -> (i32, Vec<u8>) {
+        (self.key_index, self.sender_key.clone())
+    }
+
+    /// Replaces the current sender key without changing its index, e.g. after a ratchet.
+    ///
+    /// Peers are expected to derive the new key themselves, so it is only sent to peers added
+    /// afterwards.
+    pub fn set_sender_key(&mut self, sender_key: Vec<u8>) {
+        self.sender_key = sender_key;
+    }
+
+    /// Adds or updates a peer, returning the current sender key wrapped for it.
+    ///
+    /// Returns `None` if the peer is already known with the same public key.
+    ///
+    pub fn add_peer(
+        &mut self,
+        identity: ParticipantIdentity,
+        public_key: &str,
+    ) -> Result<Option<String>, KeyExchangeError> {
+        let public_key = decode_public_key(public_key)?;
+        if self.peers.get(&identity) == Some(&public_key) {
+            return Ok(None);
+        }
+        let message = self.wrap(&identity, &public_key);
+        self.peers.insert(identity, public_key);
+        Ok(Some(message))
+    }
+
+    /// Removes a peer, returning whether it was known.
+    pub fn remove_peer(&mut self, identity: &ParticipantIdentity) -> bool {
+        self.peers.remove(identity).is_some()
+    }
+
+    /// Generates a new sender key in the next key ring slot, returning it wrapped for every peer.
+    pub fn rotate(&mut self) -> Vec<(ParticipantIdentity, String)> {
+        self.sender_key = random_sender_key();
+        self.key_index = next_key_index(self.key_index);
+        self.peers
+            .iter()
+            .map(|(identity, public_key)| (identity.clone(), self.wrap(identity, public_key)))
+            .collect()
+    }
+
+    /// Unwraps a sender key received from `sender`, returning its key index and value.
+    ///
+    /// Once a peer was added with [`Self::add_peer`], messages carrying another public key are
+    /// rejected. Until then, the public key embedded in the message is trusted on first use:
+    /// nothing binds it to `sender` except the participant identity authenticated by the
+    /// server, so a compromised server could impersonate peers it hasn't announced yet.
+    ///
+    pub fn unwrap(
+        &self,
+        sender: &ParticipantIdentity,
+        message: &str,
+    ) -> Result<(i32, Vec<u8>), KeyExchangeError> {
+        let wrapped: WrappedKey = serde_json::from_str(message)
+            .map_err(|e| KeyExchangeError::InvalidMessage(e.to_string()))?;
+        let sender_public_key = decode_public_key(&wrapped.public_key)?;
+        if let Some(known_public_key) = self.peers.get(sender) {
+            if *known_public_key != sender_public_key {
+                Err(KeyExchangeError::InvalidMessage("unexpected public key".to_string()))?
+            }
+        }
+
+        let nonce = BASE64
+            .decode(&wrapped.nonce)
+            .ok()
+            .filter(|nonce| nonce.len() == NONCE_LEN)
+            .ok_or_else(|| KeyExchangeError::InvalidMessage("invalid nonce".to_string()))?;
+        let ciphertext = BASE64
+            .decode(&wrapped.ciphertext)
+            .map_err(|e| KeyExchangeError::InvalidMessage(e.to_string()))?;
+
+        let cipher = self.cipher(&sender_public_key);
+        let aad = associated_data(sender, &self.local_identity, wrapped.key_index);
+        let sender_key = cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+            .map_err(|_| KeyExchangeError::UnwrapFailed)?;
+        Ok((wrapped.key_index, sender_key))
+    }
+
+    fn wrap(&self, recipient: &ParticipantIdentity, public_key: &PublicKey) -> String {
+        let nonce = rand::random::<[u8; NONCE_LEN]>();
+        let aad = associated_data(&self.local_identity, recipient, self.key_index);
+        let ciphertext = self
+            .cipher(public_key)
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: &self.sender_key, aad: &aad })
+            .expect("encryption with a valid key cannot fail");
+
+        let wrapped = WrappedKey {
+            public_key: self.public_key(),
+            key_index: self.key_index,
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        serde_json::to_string(&wrapped).unwrap()
+    }
+
+    /// Derives the wrapping cipher shared with a peer.
+    fn cipher(&self, public_key: &PublicKey) -> ChaCha20Poly1305 {
+        let shared_secret = self.secret.diffie_hellman(public_key);
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, shared_secret.as_bytes())
+            .expand(HKDF_INFO, &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        ChaCha20Poly1305::new(&key.into())
+    }
+}
+
+/// Binds a wrapped key to its sender, recipient and key index.
+fn associated_data(
+    sender: &ParticipantIdentity,
+    recipient: &ParticipantIdentity,
+    key_index: i32,
+) -> Vec<u8> {
+    format!("{}\n{}\n{}", sender, recipient, key_index).into_bytes()
+}
+
+fn decode_public_key(public_key: &str) -> Result<PublicKey, KeyExchangeError> {
+    let bytes: [u8; 32] = BASE64
+        .decode(public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(KeyExchangeError::InvalidPublicKey)?;
+    Ok(PublicKey::from(bytes))
+}
+
+fn random_sender_key() -> Vec<u8> {
+    rand::random::<[u8; SENDER_KEY_LEN]>().to_vec()
+}
+
+/// Publishes the public key as an attribute of the local participant and delivers wrapped
+/// keys with [`KEY_EXCHANGE_RPC_METHOD`].
+pub(crate) struct RpcKeyExchangeTransport {
+    pub local_participant: LocalParticipant,
+}
+
+impl KeyExchangeTransport for RpcKeyExchangeTransport {
+    fn publish_public_key(&self, public_key: String) {
+        let local_participant = self.local_participant.clone();
+        livekit_runtime::spawn(async move {
+            // The server only updates the attributes that are set, the others are kept
+            let attributes = HashMap::from([(PUBLIC_KEY_ATTRIBUTE.to_string(), public_key)]);
+            if let Err(e) = local_participant.set_attributes(attributes).await {
+                log::error!("failed to publish e2ee public key: {}", e);
+            }
+        });
+    }
+
+    fn send_wrapped_key(&self, destination: ParticipantIdentity, message: String) {
+        let local_participant = self.local_participant.clone();
+        livekit_runtime::spawn(async move {
+            let data = PerformRpcData {
+                destination_identity: destination.to_string(),
+                method: KEY_EXCHANGE_RPC_METHOD.to_string(),
+                payload: message,
+                ..Default::default()
+            };
+            if let Err(e) = local_participant.perform_rpc(data).await {
+                log::error!("failed to send e2ee key to {}: {}", destination, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::e2ee::{
+        key_provider::{KeyProvider, KeyProviderOptions},
+        manager::E2eeManager,
+        E2eeOptions, EncryptionType,
+    };
+
+    /// Records what would be sent so the test can deliver it.
+    #[derive(Default)]
+    struct FakeTransport {
+        public_key: Mutex<Option<String>>,
+        wrapped_keys: Mutex<Vec<(ParticipantIdentity, String)>>,
+    }
+
+    impl KeyExchangeTransport for FakeTransport {
+        fn publish_public_key(&self, public_key: String) {
+            *self.public_key.lock() = Some(public_key);
+        }
+
+        fn send_wrapped_key(&self, destination: ParticipantIdentity, message: String) {
+            self.wrapped_keys.lock().push((destination, message));
+        }
+    }
+
+    struct TestParticipant {
+        identity: ParticipantIdentity,
+        manager: E2eeManager,
+        transport: Arc<FakeTransport>,
+    }
+
+    impl TestParticipant {
+        fn new(identity: &str) -> Self {
+            let identity = ParticipantIdentity(identity.to_string());
+            let options = E2eeOptions {
+                encryption_type: EncryptionType::Gcm,
+                key_provider: KeyProvider::new(KeyProviderOptions::default()),
+                frame_transformer: None,
+            };
+            let manager = E2eeManager::new(Some(options), false);
+            let transport = Arc::new(FakeTransport::default());
+            manager.enable_key_exchange(identity.clone(), transport.clone()).unwrap();
+            Self { identity, manager, transport }
+        }
+
+        fn public_key(&self) -> String {
+            self.transport.public_key.lock().clone().unwrap()
+        }
+
+        fn key(&self, identity: &ParticipantIdentity, key_index: i32) -> Option<Vec<u8>> {
+            self.manager.key_provider().unwrap().get_key(identity, key_index)
+        }
+
+        /// Delivers the wrapped keys sent by this participant.
+        fn deliver_to(&self, participants: &[&TestParticipant]) {
+            for (destination, message) in self.transport.wrapped_keys.lock().drain(..) {
+                let recipient = participants.iter().find(|p| p.identity == destination).unwrap();
+                recipient.manager.handle_key_exchange_message(&self.identity, &message).unwrap();
+            }
+        }
+    }
+
+    fn connect(a: &TestParticipant, b: &TestParticipant) {
+        a.manager.handle_remote_public_key(b.identity.clone(), &b.public_key()).unwrap();
+        b.manager.handle_remote_public_key(a.identity.clone(), &a.public_key()).unwrap();
+    }
+
+    #[test]
+    fn exchange_and_rotate() {
+        let alice = TestParticipant::new("alice");
+        let bob = TestParticipant::new("bob");
+        let carol = TestParticipant::new("carol");
+        connect(&alice, &bob);
+        connect(&alice, &carol);
+        alice.deliver_to(&[&bob, &carol]);
+        bob.deliver_to(&[&alice]);
+
+        let alice_key = alice.key(&alice.identity, 0).unwrap();
+        assert_eq!(bob.key(&alice.identity, 0), Some(alice_key.clone()));
+        assert_eq!(carol.key(&alice.identity, 0), Some(alice_key.clone()));
+        assert_eq!(alice.key(&bob.identity, 0), bob.key(&bob.identity, 0));
+
+        // Carol leaves, Alice's new key only reaches Bob
+        alice.manager.handle_participant_left(&carol.identity);
+        let wrapped_keys = alice.transport.wrapped_keys.lock().clone();
+        assert_eq!(wrapped_keys.len(), 1);
+        assert_eq!(wrapped_keys[0].0, bob.identity);
+        alice.deliver_to(&[&bob]);
+
+        let rotated_key = alice.key(&alice.identity, 1).unwrap();
+        assert_ne!(rotated_key, alice_key);
+        assert_eq!(bob.key(&alice.identity, 1), Some(rotated_key));
+    }
+
+    #[test]
+    fn reject_wrong_sender() {
+        let alice = TestParticipant::new("alice");
+        let bob = TestParticipant::new("bob");
+        let mallory = TestParticipant::new("mallory");
+        connect(&alice, &bob);
+
+        // A wrapped key is bound to its sender
+        let (_, message) = alice.transport.wrapped_keys.lock().pop().unwrap();
+        let result = bob.manager.handle_key_exchange_message(&mallory.identity, &message);
+        assert!(result.is_err());
+    }
+}
@@ -12,18 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use libwebrtc::native::frame_cryptor as fc;
-use std::sync::{
-    atomic::{AtomicI32, Ordering},
-    Arc,
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    },
 };
+use thiserror::Error;
 
+use super::key_exchange::KEY_RING_SIZE;
 use crate::id::ParticipantIdentity;
 
 const DEFAULT_RATCHET_SALT: &str = "LKFrameEncryptionKey";
 const DEFAULT_RATCHET_WINDOW_SIZE: i32 = 16;
 const DEFAULT_FAILURE_TOLERANCE: i32 = -1; // no tolerance by default
 
+pub(crate) type KeySetHandler = Box<dyn Fn(Option<&ParticipantIdentity>, i32) + Send>;
+
+/// Version of the format produced by [`KeyProvider::export_keys`]
+pub const KEY_EXPORT_VERSION: u32 = 1;
+
 #[derive(Clone)]
 pub struct KeyProviderOptions {
     pub ratchet_window_size: i32,
@@ -41,40 +54,124 @@ impl Default for KeyProviderOptions {
     }
 }
 
+#[derive(Debug, Error)]
+pub enum KeyImportError {
+    #[error("unsupported key export version: {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid key material: {0}")]
+    InvalidKey(String),
+}
+
+/// Key material of a [`KeyProvider`], in a format suitable for a device keystore.
+///
+/// With the `zeroize` feature, the key material is wiped from memory on drop. The key
+/// material is redacted from the `Debug` output.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "zeroize", derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop))]
+pub struct ExportedKeys {
+    pub version: u32,
+    pub shared_key: bool,
+    pub ratchet_window_size: i32,
+    /// Base64 encoded
+    pub ratchet_salt: String,
+    pub failure_tolerance: i32,
+    pub latest_key_index: i32,
+    pub keys: Vec<ExportedKey>,
+}
+
+impl std::fmt::Debug for ExportedKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportedKeys")
+            .field("version", &self.version)
+            .field("shared_key", &self.shared_key)
+            .field("ratchet_window_size", &self.ratchet_window_size)
+            .field("ratchet_salt", &"<redacted>")
+            .field("failure_tolerance", &self.failure_tolerance)
+            .field("latest_key_index", &self.latest_key_index)
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "zeroize", derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop))]
+pub struct ExportedKey {
+    /// None for shared keys
+    pub participant_identity: Option<String>,
+    pub key_index: i32,
+    /// Base64 encoded, as currently ratcheted
+    pub key: String,
+}
+
+impl std::fmt::Debug for ExportedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportedKey")
+            .field("participant_identity", &self.participant_identity)
+            .field("key_index", &self.key_index)
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
 #[derive(Clone)]
 pub struct KeyProvider {
     pub(crate) handle: fc::KeyProvider,
     latest_key_index: Arc<AtomicI32>,
+    shared_key: bool,
+    options: KeyProviderOptions,
+    identities: Arc<Mutex<HashSet<ParticipantIdentity>>>, // Participants with keys, for exports
+    key_set: Arc<Mutex<Option<KeySetHandler>>>,
 }
 
 impl KeyProvider {
     /// By default, the key provider is not shared
     pub fn new(options: KeyProviderOptions) -> Self {
+        Self::with_options(options, false)
+    }
+
+    pub fn with_shared_key(options: KeyProviderOptions, shared_key: Vec<u8>) -> Self {
+        let key_provider = Self::with_options(options, true);
+        key_provider.handle.set_shared_key(0, shared_key);
+        key_provider
+    }
+
+    /// Creates a shared key provider from a passphrase.
+    ///
+    /// The passphrase is used as key material, from which the frame cryptors derive the AES key
+    /// with PBKDF2-SHA256 (100,000 iterations, salted with `ratchet_salt`) like the JS and Swift
+    /// SDKs do. Using the same passphrase and options on every SDK is enough to interoperate.
+    ///
+    pub fn with_passphrase(options: KeyProviderOptions, passphrase: &str) -> Self {
+        Self::with_shared_key(options, passphrase.as_bytes().to_vec())
+    }
+
+    fn with_options(options: KeyProviderOptions, shared_key: bool) -> Self {
         Self {
             handle: fc::KeyProvider::new(fc::KeyProviderOptions {
-                shared_key: false,
+                shared_key,
                 ratchet_window_size: options.ratchet_window_size,
-                ratchet_salt: options.ratchet_salt,
+                ratchet_salt: options.ratchet_salt.clone(),
                 failure_tolerance: options.failure_tolerance,
             }),
             latest_key_index: Arc::new(AtomicI32::new(0)),
+            shared_key,
+            options,
+            identities: Default::default(),
+            key_set: Default::default(),
         }
     }
 
-    pub fn with_shared_key(options: KeyProviderOptions, shared_key: Vec<u8>) -> Self {
-        let handle = fc::KeyProvider::new(fc::KeyProviderOptions {
-            shared_key: true,
-            ratchet_window_size: options.ratchet_window_size,
-            ratchet_salt: options.ratchet_salt,
-            failure_tolerance: options.failure_tolerance,
-        });
-        handle.set_shared_key(0, shared_key);
-        Self { handle, latest_key_index: Arc::new(AtomicI32::new(0)) }
-    }
-
     pub fn set_shared_key(&self, shared_key: Vec<u8>, key_index: i32) {
         self.latest_key_index.store(key_index, Ordering::Relaxed);
         self.handle.set_shared_key(key_index, shared_key);
+        if let Some(key_set) = self.key_set.lock().as_ref() {
+            key_set(None, key_index);
+        }
+    }
+
+    /// Sets the shared key from a passphrase, see [`Self::with_passphrase`]
+    pub fn set_shared_passphrase(&self, passphrase: &str, key_index: i32) {
+        self.set_shared_key(passphrase.as_bytes().to_vec(), key_index);
     }
 
     pub fn ratchet_shared_key(&self, key_index: i32) -> Option<Vec<u8>> {
@@ -87,7 +184,12 @@ impl KeyProvider {
 
     pub fn set_key(&self, identity: &ParticipantIdentity, key_index: i32, key: Vec<u8>) -> bool {
         self.latest_key_index.store(key_index, Ordering::Relaxed);
-        self.handle.set_key(identity.to_string(), key_index, key)
+        self.identities.lock().insert(identity.clone());
+        let result = self.handle.set_key(identity.to_string(), key_index, key);
+        if let Some(key_set) = self.key_set.lock().as_ref() {
+            key_set(Some(identity), key_index);
+        }
+        result
     }
 
     pub fn ratchet_key(&self, identity: &ParticipantIdentity, key_index: i32) -> Option<Vec<u8>> {
@@ -105,4 +207,143 @@ impl KeyProvider {
     pub fn get_latest_key_index(&self) -> i32 {
         self.latest_key_index.load(Ordering::Relaxed)
     }
+
+    /// Register to keys set through [`Self::set_key`] and [`Self::set_shared_key`]
+    /// Used by the e2ee manager to retry decryption
+    pub(crate) fn on_key_set(&self, handler: Option<KeySetHandler>) {
+        *self.key_set.lock() = handler;
+    }
+
+    pub fn is_shared_key(&self) -> bool {
+        self.shared_key
+    }
+
+    /// Exports the options and the current key material of this key provider.
+    pub fn export_keys(&self) -> ExportedKeys {
+        let mut keys = Vec::new();
+        if self.shared_key {
+            for key_index in 0..KEY_RING_SIZE {
+                if let Some(key) = self.get_shared_key(key_index).filter(|key| !key.is_empty()) {
+                    keys.push(ExportedKey {
+                        participant_identity: None,
+                        key_index,
+                        key: BASE64.encode(key),
+                    });
+                }
+            }
+        } else {
+            for identity in self.identities.lock().iter() {
+                for key_index in 0..KEY_RING_SIZE {
+                    if let Some(key) = self.get_key(identity, key_index).filter(|k| !k.is_empty()) {
+                        keys.push(ExportedKey {
+                            participant_identity: Some(identity.to_string()),
+                            key_index,
+                            key: BASE64.encode(key),
+                        });
+                    }
+                }
+            }
+        }
+
+        ExportedKeys {
+            version: KEY_EXPORT_VERSION,
+            shared_key: self.shared_key,
+            ratchet_window_size: self.options.ratchet_window_size,
+            ratchet_salt: BASE64.encode(&self.options.ratchet_salt),
+            failure_tolerance: self.options.failure_tolerance,
+            latest_key_index: self.get_latest_key_index(),
+            keys,
+        }
+    }
+
+    /// Creates a key provider from key material previously returned by [`Self::export_keys`].
+    pub fn import_keys(exported: &ExportedKeys) -> Result<Self, KeyImportError> {
+        if exported.version != KEY_EXPORT_VERSION {
+            Err(KeyImportError::UnsupportedVersion(exported.version))?
+        }
+
+        let decode = |value: &str| {
+            BASE64.decode(value).map_err(|e| KeyImportError::InvalidKey(e.to_string()))
+        };
+        let options = KeyProviderOptions {
+            ratchet_window_size: exported.ratchet_window_size,
+            ratchet_salt: decode(&exported.ratchet_salt)?,
+            failure_tolerance: exported.failure_tolerance,
+        };
+        let key_provider = Self::with_options(options, exported.shared_key);
+        key_provider.latest_key_index.store(exported.latest_key_index, Ordering::Relaxed);
+
+        for key in &exported.keys {
+            if !(0..KEY_RING_SIZE).contains(&key.key_index) {
+                Err(KeyImportError::InvalidKey(format!("invalid key index {}", key.key_index)))?
+            }
+
+            let value = decode(&key.key)?;
+            match (&key.participant_identity, exported.shared_key) {
+                (None, true) => {
+                    key_provider.handle.set_shared_key(key.key_index, value);
+                }
+                (Some(identity), false) => {
+                    let identity = ParticipantIdentity(identity.clone());
+                    key_provider.identities.lock().insert(identity.clone());
+                    key_provider.handle.set_key(identity.to_string(), key.key_index, value);
+                }
+                _ => Err(KeyImportError::InvalidKey("unexpected participant identity".into()))?,
+            }
+        }
+        Ok(key_provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passphrase() {
+        let key_provider = KeyProvider::with_passphrase(KeyProviderOptions::default(), "secret");
+        assert!(key_provider.is_shared_key());
+        assert_eq!(key_provider.get_shared_key(0), Some(b"secret".to_vec()));
+    }
+
+    #[test]
+    fn export_import() {
+        let alice = ParticipantIdentity("alice".to_string());
+        let key_provider = KeyProvider::new(KeyProviderOptions::default());
+        key_provider.set_key(&alice, 0, vec![1; 32]);
+        key_provider.set_key(&alice, 3, vec![2; 32]);
+        key_provider.ratchet_key(&alice, 3);
+
+        let exported = key_provider.export_keys();
+        assert_eq!(exported.keys.len(), 2);
+
+        let json = serde_json::to_string(&exported).unwrap();
+        let imported = serde_json::from_str::<ExportedKeys>(&json).unwrap();
+        let imported = KeyProvider::import_keys(&imported).unwrap();
+        assert_eq!(imported.get_key(&alice, 0), Some(vec![1; 32]));
+        assert_eq!(imported.get_key(&alice, 3), key_provider.get_key(&alice, 3));
+        assert_eq!(imported.export_keys(), exported);
+    }
+
+    #[test]
+    fn exported_keys_debug_is_redacted() {
+        let key_provider = KeyProvider::with_shared_key(KeyProviderOptions::default(), vec![7; 32]);
+        let exported = key_provider.export_keys();
+        assert_eq!(exported.keys.len(), 1);
+
+        let debug = format!("{:?}", exported);
+        assert!(!debug.contains(&exported.keys[0].key));
+        assert!(!debug.contains(&exported.ratchet_salt));
+        assert!(debug.contains("key_index: 0"));
+    }
+
+    #[test]
+    fn import_unsupported_version() {
+        let mut exported = KeyProvider::new(KeyProviderOptions::default()).export_keys();
+        exported.version = KEY_EXPORT_VERSION + 1;
+        assert!(matches!(
+            KeyProvider::import_keys(&exported),
+            Err(KeyImportError::UnsupportedVersion(_))
+        ));
+    }
 }
@@ -0,0 +1,180 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use thiserror::Error;
+
+use super::key_exchange::KEY_RING_SIZE;
+
+pub const DEFAULT_KEY_ROTATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often the sent frame count is polled when rotating after a number of frames.
+pub(crate) const FRAME_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyRotationMode {
+    /// Ratchets the current key in place. Receivers derive the new key themselves, as long as
+    /// they don't fall behind by more than `ratchet_window_size` ratchets.
+    #[default]
+    Ratchet,
+    /// Generates a new random key in the next key index and sends it to the other participants.
+    /// Requires key exchange, see `E2eeManager::enable_key_exchange`.
+    Replace,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyRotationOptions {
+    pub mode: KeyRotationMode,
+    /// Rotates the key after this duration
+    pub interval: Option<Duration>,
+    /// Rotates the key after this many frames were sent across all local tracks
+    pub frame_interval: Option<u64>,
+}
+
+impl Default for KeyRotationOptions {
+    fn default() -> Self {
+        Self {
+            mode: KeyRotationMode::default(),
+            interval: Some(DEFAULT_KEY_ROTATION_INTERVAL),
+            frame_interval: None,
+        }
+    }
+}
+
+impl KeyRotationOptions {
+    /// Delay between two checks of the scheduler
+    pub(crate) fn poll_interval(&self) -> Option<Duration> {
+        match (self.interval, self.frame_interval) {
+            (Some(interval), None) => Some(interval),
+            (Some(interval), Some(_)) => Some(interval.min(FRAME_POLL_INTERVAL)),
+            (None, Some(_)) => Some(FRAME_POLL_INTERVAL),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A rotation of the local key, reported to the room for auditing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyRotation {
+    pub mode: KeyRotationMode,
+    /// Key index used to encrypt after the rotation
+    pub key_index: i32,
+}
+
+#[derive(Debug, Error)]
+pub enum KeyRotationError {
+    #[error("e2ee is not initialized")]
+    NotInitialized,
+    #[error("invalid key rotation options: {0}")]
+    InvalidOptions(&'static str),
+    #[error("no key to ratchet at index {0}")]
+    MissingKey(i32),
+    #[error("replacing the key requires key exchange to distribute it")]
+    KeyExchangeRequired,
+}
+
+/// Returns the key index following `key_index`, wrapping around the key ring of the frame
+/// cryptors.
+pub(crate) fn next_key_index(key_index: i32) -> i32 {
+    (key_index + 1).rem_euclid(KEY_RING_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::{
+        e2ee::{
+            key_exchange::KeyExchangeTransport,
+            key_provider::{KeyProvider, KeyProviderOptions},
+            manager::E2eeManager,
+            E2eeOptions, EncryptionType,
+        },
+        id::ParticipantIdentity,
+    };
+
+    #[test]
+    fn key_index_wraps_around_key_ring() {
+        assert_eq!(next_key_index(0), 1);
+        assert_eq!(next_key_index(3), 4);
+        assert_eq!(next_key_index(KEY_RING_SIZE - 1), 0);
+    }
+
+    #[test]
+    fn poll_interval() {
+        let options = KeyRotationOptions::default();
+        assert_eq!(options.poll_interval(), Some(DEFAULT_KEY_ROTATION_INTERVAL));
+
+        let options = KeyRotationOptions { frame_interval: Some(1000), ..Default::default() };
+        assert_eq!(options.poll_interval(), Some(FRAME_POLL_INTERVAL));
+
+        let options = KeyRotationOptions { interval: None, ..Default::default() };
+        assert_eq!(options.poll_interval(), None);
+    }
+
+    #[tokio::test]
+    async fn rotate_key() {
+        let identity = ParticipantIdentity("alice".to_string());
+        let key_provider =
+            KeyProvider::new(KeyProviderOptions { ratchet_window_size: 2, ..Default::default() });
+        key_provider.set_key(&identity, 0, vec![1; 32]);
+        let options = E2eeOptions {
+            encryption_type: EncryptionType::Gcm,
+            key_provider,
+            frame_transformer: None,
+        };
+        let manager = E2eeManager::new(Some(options), false);
+
+        let rotations = Arc::new(Mutex::new(Vec::new()));
+        manager.on_key_rotated({
+            let rotations = rotations.clone();
+            move |_, rotation| rotations.lock().push(rotation)
+        });
+        let key = |key_index| manager.key_provider().unwrap().get_key(&identity, key_index);
+
+        assert_eq!(manager.rotate_key(&identity, KeyRotationMode::Ratchet).unwrap(), 0);
+        let ratcheted_key = key(0).unwrap();
+        assert_ne!(ratcheted_key, vec![1; 32]);
+
+        // Nobody else would get a replaced key
+        assert!(matches!(
+            manager.rotate_key(&identity, KeyRotationMode::Replace),
+            Err(KeyRotationError::KeyExchangeRequired)
+        ));
+        assert_eq!(key(0).unwrap(), ratcheted_key);
+
+        manager.enable_key_exchange(identity.clone(), Arc::new(NoopTransport)).unwrap();
+        let (sender_key_index, _) = manager.sender_key_index();
+        let key_index = manager.rotate_key(&identity, KeyRotationMode::Replace).unwrap();
+        assert_eq!(key_index, next_key_index(sender_key_index));
+        // Still encrypting with the previous key until receivers had time to get the new one
+        assert_eq!(manager.sender_key_index(), (sender_key_index, Some(key_index)));
+
+        let rotations = rotations.lock();
+        assert_eq!(rotations.len(), 2);
+        assert_eq!(rotations[1], KeyRotation { mode: KeyRotationMode::Replace, key_index });
+    }
+
+    struct NoopTransport;
+
+    impl KeyExchangeTransport for NoopTransport {
+        fn publish_public_key(&self, _: String) {}
+
+        fn send_wrapped_key(&self, _: ParticipantIdentity, _: String) {}
+    }
+}
@@ -12,18 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use libwebrtc::{
-    native::frame_cryptor::{
-        DataPacketCryptor, EncryptedPacket, EncryptionAlgorithm, EncryptionState, FrameCryptor,
+    native::{
+        frame_cryptor::{
+            DataPacketCryptor, EncryptedPacket, EncryptionAlgorithm, EncryptionState, FrameCryptor,
+        },
+        frame_transformer::{FrameMetadata, FrameTransformer, RtpFrameTransformer},
     },
     rtp_receiver::RtpReceiver,
     rtp_sender::RtpSender,
+    stats::RtcStats,
+    RtcError,
 };
 use parking_lot::Mutex;
-
-use super::{key_provider::KeyProvider, EncryptionType};
+use tokio::sync::{mpsc, oneshot};
+
+use super::{
+    diagnostics::FrameCryptorDiagnostics,
+    key_exchange::{KeyExchange, KeyExchangeError, KeyExchangeTransport},
+    key_provider::KeyProvider,
+    key_rotation::{KeyRotation, KeyRotationError, KeyRotationMode, KeyRotationOptions},
+    EncryptionType,
+};
 use crate::{
     e2ee::E2eeOptions,
     id::{ParticipantIdentity, TrackSid},
@@ -33,19 +49,36 @@ use crate::{
 };
 
 type StateChangedHandler = Box<dyn Fn(ParticipantIdentity, EncryptionState) + Send>;
+type KeyRotatedHandler = Box<dyn Fn(ParticipantIdentity, KeyRotation) + Send>;
+type KeyMissingHandler = Box<dyn Fn(ParticipantIdentity, i32) + Send>;
+
+/// Delay between distributing a new local key and encrypting with it, so that the other
+/// participants already have the key when the first frames encrypted with it arrive.
+const SENDER_KEY_SWITCH_DELAY: Duration = Duration::from_secs(2);
 
 struct ManagerInner {
     options: Option<E2eeOptions>, // If Some, it means the e2ee was initialized
     enabled: bool,                // Used to enable/disable e2ee
     dc_encryption_enabled: bool,
     frame_cryptors: HashMap<(ParticipantIdentity, TrackSid), FrameCryptor>,
+    frame_transformers: HashMap<(ParticipantIdentity, TrackSid), RtpFrameTransformer>, // Custom
     data_packet_cryptor: Option<DataPacketCryptor>,
+    key_exchange: Option<(KeyExchange, Arc<dyn KeyExchangeTransport>)>,
+    local_senders: HashMap<TrackSid, RtpSender>,
+    key_rotation_close_tx: Option<oneshot::Sender<()>>, // Dropping it stops the scheduler
+    /// Key index used to encrypt local tracks
+    sender_key_index: i32,
+    /// Key index local tracks switch to after [`SENDER_KEY_SWITCH_DELAY`]
+    pending_sender_key_index: Option<i32>,
 }
 
 #[derive(Clone)]
 pub struct E2eeManager {
     inner: Arc<Mutex<ManagerInner>>,
     state_changed: Arc<Mutex<Option<StateChangedHandler>>>,
+    key_rotated: Arc<Mutex<Option<KeyRotatedHandler>>>,
+    key_missing: Arc<Mutex<Option<KeyMissingHandler>>>,
+    diagnostics: Arc<Mutex<HashMap<(ParticipantIdentity, TrackSid), FrameCryptorDiagnostics>>>,
 }
 
 impl E2eeManager {
@@ -64,9 +97,18 @@ impl E2eeManager {
                 dc_encryption_enabled: options.is_some() && with_dc_encryption,
                 options,
                 frame_cryptors: HashMap::new(),
+                frame_transformers: HashMap::new(),
                 data_packet_cryptor,
+                key_exchange: None,
+                local_senders: HashMap::new(),
+                key_rotation_close_tx: None,
+                sender_key_index: 0,
+                pending_sender_key_index: None,
             })),
             state_changed: Default::default(),
+            key_rotated: Default::default(),
+            key_missing: Default::default(),
+            diagnostics: Default::default(),
         }
     }
 
@@ -76,6 +118,16 @@ impl E2eeManager {
             cryptor.set_enabled(false);
         }
         inner.frame_cryptors.clear();
+        for transformer in inner.frame_transformers.values() {
+            transformer.set_enabled(false);
+        }
+        inner.frame_transformers.clear();
+        inner.local_senders.clear();
+        inner.key_rotation_close_tx = None;
+        if let Some(options) = inner.options.as_ref() {
+            options.key_provider.on_key_set(None);
+        }
+        self.diagnostics.lock().clear();
     }
 
     /// Register to e2ee state changes
@@ -87,6 +139,24 @@ impl E2eeManager {
         *self.state_changed.lock() = Some(Box::new(handler));
     }
 
+    /// Register to rotations of the local key
+    /// Used by the room to dispatch the event to the room dispatcher
+    pub(crate) fn on_key_rotated(
+        &self,
+        handler: impl Fn(ParticipantIdentity, KeyRotation) + Send + 'static,
+    ) {
+        *self.key_rotated.lock() = Some(Box::new(handler));
+    }
+
+    /// Register to frame cryptors missing a key
+    /// Used by the room to dispatch the event to the room dispatcher
+    pub(crate) fn on_key_missing(
+        &self,
+        handler: impl Fn(ParticipantIdentity, i32) + Send + 'static,
+    ) {
+        *self.key_missing.lock() = Some(Box::new(handler));
+    }
+
     pub(crate) fn initialized(&self) -> bool {
         self.inner.lock().options.is_some()
     }
@@ -108,8 +178,40 @@ impl E2eeManager {
 
         let identity = participant.identity();
         let receiver = track.transceiver().unwrap().receiver();
-        let frame_cryptor = self.setup_rtp_receiver(&identity, receiver);
-        self.setup_cryptor(&frame_cryptor);
+        if publication.encryption_type() == EncryptionType::Custom {
+            let transformer = self.frame_transformer().unwrap_or_else(|| {
+                log::error!(
+                    "no frame transformer for custom encrypted track {}, dropping its frames",
+                    publication.sid()
+                );
+                self.report_state(&identity, EncryptionState::InternalError);
+                Arc::new(DropFrames)
+            });
+            let transformer = match RtpFrameTransformer::new_for_rtp_receiver(
+                identity.to_string(),
+                transformer,
+                receiver,
+            ) {
+                Ok(transformer) => transformer,
+                Err(e) => {
+                    log::error!("failed to transform the frames of {}: {}", publication.sid(), e);
+                    self.report_state(&identity, EncryptionState::InternalError);
+                    return;
+                }
+            };
+            self.add_frame_transformer(identity, publication.sid(), transformer);
+            return;
+        }
+
+        let frame_cryptor = match self.setup_rtp_receiver(&identity, receiver) {
+            Ok(frame_cryptor) => frame_cryptor,
+            Err(e) => {
+                log::error!("failed to decrypt the frames of {}: {}", publication.sid(), e);
+                self.report_state(&identity, EncryptionState::InternalError);
+                return;
+            }
+        };
+        self.setup_cryptor(&frame_cryptor, &identity, &publication.sid());
 
         let mut inner = self.inner.lock();
         inner.frame_cryptors.insert((identity, publication.sid()), frame_cryptor.clone());
@@ -132,20 +234,170 @@ impl E2eeManager {
 
         let identity = participant.identity();
         let sender = track.transceiver().unwrap().sender();
-        let frame_cryptor = self.setup_rtp_sender(&identity, sender);
-        self.setup_cryptor(&frame_cryptor);
+        if publication.encryption_type() == EncryptionType::Custom {
+            // publish_track refuses custom encrypted tracks without a transformer
+            let transformer = self.frame_transformer().unwrap_or_else(|| {
+                log::error!(
+                    "no frame transformer for custom encrypted track {}, dropping its frames",
+                    publication.sid()
+                );
+                self.report_state(&identity, EncryptionState::InternalError);
+                Arc::new(DropFrames)
+            });
+            let transformer =
+                RtpFrameTransformer::new_for_rtp_sender(identity.to_string(), transformer, sender);
+            self.add_frame_transformer(identity, publication.sid(), transformer);
+            return;
+        }
+
+        let frame_cryptor = self.setup_rtp_sender(&identity, sender.clone());
+        self.setup_cryptor(&frame_cryptor, &identity, &publication.sid());
 
         let mut inner = self.inner.lock();
+        inner.local_senders.insert(publication.sid(), sender);
         inner.frame_cryptors.insert((identity, publication.sid()), frame_cryptor.clone());
     }
 
-    fn setup_cryptor(&self, frame_cryptor: &FrameCryptor) {
+    pub(crate) fn frame_transformer(&self) -> Option<Arc<dyn FrameTransformer>> {
+        let inner = self.inner.lock();
+        inner.options.as_ref().and_then(|opts| opts.frame_transformer.clone())
+    }
+
+    fn add_frame_transformer(
+        &self,
+        identity: ParticipantIdentity,
+        track_sid: TrackSid,
+        transformer: RtpFrameTransformer,
+    ) {
+        let mut inner = self.inner.lock();
+        transformer.set_enabled(inner.enabled);
+        inner.frame_transformers.insert((identity, track_sid), transformer);
+    }
+
+    fn report_state(&self, identity: &ParticipantIdentity, state: EncryptionState) {
+        if let Some(state_changed) = self.state_changed.lock().as_ref() {
+            state_changed(identity.clone(), state);
+        }
+    }
+
+    fn setup_cryptor(
+        &self,
+        frame_cryptor: &FrameCryptor,
+        identity: &ParticipantIdentity,
+        track_sid: &TrackSid,
+    ) {
+        let key = (identity.clone(), track_sid.clone());
+        self.diagnostics
+            .lock()
+            .insert(key.clone(), FrameCryptorDiagnostics::new(frame_cryptor.key_index()));
+
         let state_changed = self.state_changed.clone();
-        frame_cryptor.on_state_change(Some(Box::new(move |participant_identity, state| {
-            if let Some(state_changed) = state_changed.lock().as_ref() {
-                state_changed(participant_identity.into(), state);
+        let key_missing = self.key_missing.clone();
+        let diagnostics = Arc::downgrade(&self.diagnostics);
+        frame_cryptor.on_state_change_with_key_index(Some(Box::new(
+            move |participant_identity, state, key_index| {
+                if let Some(diagnostics) = diagnostics.upgrade() {
+                    if let Some(entry) = diagnostics.lock().get_mut(&key) {
+                        entry.record(state);
+                        if key_index >= 0 {
+                            entry.key_index = key_index;
+                        }
+                    }
+                }
+
+                // For receivers, this is the index carried by the frame that couldn't be
+                // decrypted
+                if state == EncryptionState::MissingKey {
+                    if let Some(key_missing) = key_missing.lock().as_ref() {
+                        key_missing(participant_identity.clone().into(), key_index.max(0));
+                    }
+                }
+                if let Some(state_changed) = state_changed.lock().as_ref() {
+                    state_changed(participant_identity.into(), state);
+                }
+            },
+        )));
+    }
+
+    /// Returns the counters of every frame cryptor, by participant and track
+    pub fn diagnostics(&self) -> HashMap<(ParticipantIdentity, TrackSid), FrameCryptorDiagnostics> {
+        let inner = self.inner.lock();
+        let mut diagnostics = self.diagnostics.lock().clone();
+        for (key, entry) in diagnostics.iter_mut() {
+            // Receivers report the key index of the frames they get instead
+            if !inner.local_senders.contains_key(&key.1) {
+                continue;
+            }
+            if let Some(cryptor) = inner.frame_cryptors.get(key) {
+                entry.key_index = cryptor.key_index();
             }
+        }
+        diagnostics
+    }
+
+    /// Retries decrypting tracks that are missing a key whenever a new key is set on the key
+    /// provider, clearing the decryption failures of their participant so the new key is tried
+    /// even after the failure tolerance was exceeded.
+    ///
+    /// Disabled by default.
+    ///
+    pub fn set_retry_on_new_key(&self, enabled: bool) {
+        let Some(key_provider) = self.key_provider() else {
+            return;
+        };
+        if !enabled {
+            key_provider.on_key_set(None);
+            return;
+        }
+
+        // Keys can be set while the manager is locked, so retries happen on a separate task
+        let (key_set_tx, key_set_rx) = mpsc::unbounded_channel();
+        key_provider.on_key_set(Some(Box::new(move |identity, key_index| {
+            let _ = key_set_tx.send((identity.cloned(), key_index));
         })));
+        livekit_runtime::spawn(self.clone().key_retry_task(key_set_rx));
+    }
+
+    async fn key_retry_task(
+        self,
+        mut key_set_rx: mpsc::UnboundedReceiver<(Option<ParticipantIdentity>, i32)>,
+    ) {
+        // Stops once the handler is cleared and the sender is dropped
+        while let Some((identity, key_index)) = key_set_rx.recv().await {
+            self.retry_decryption(identity.as_ref(), key_index);
+        }
+    }
+
+    /// Resets the key status of the participants whose frame cryptors are missing a key, so
+    /// that their next frames are decrypted with the new key at `key_index`.
+    /// If `identity` is None, the key is shared and every participant is retried.
+    fn retry_decryption(&self, identity: Option<&ParticipantIdentity>, key_index: i32) {
+        let (key_provider, participants) = {
+            let inner = self.inner.lock();
+            let Some(options) = inner.options.as_ref() else {
+                return;
+            };
+            let mut diagnostics = self.diagnostics.lock();
+            let mut participants = Vec::new();
+            for key in inner.frame_cryptors.keys() {
+                if identity.is_some_and(|identity| identity != &key.0) {
+                    continue;
+                }
+                let Some(entry) = diagnostics.get_mut(key).filter(|entry| entry.needs_key()) else {
+                    continue;
+                };
+                entry.retries += 1;
+                if !participants.contains(&key.0) {
+                    participants.push(key.0.clone());
+                }
+            }
+            (options.key_provider.handle.clone(), participants)
+        };
+
+        for identity in participants {
+            log::debug!("retrying decryption for {} with key_index {}", identity, key_index);
+            key_provider.reset_key_status(identity.to_string());
+        }
     }
 
     /// Called by the room
@@ -154,6 +406,7 @@ impl E2eeManager {
         publication: LocalTrackPublication,
         participant: LocalParticipant,
     ) {
+        self.inner.lock().local_senders.remove(&publication.sid());
         self.remove_frame_cryptor(participant.identity(), publication.sid());
     }
 
@@ -188,6 +441,9 @@ impl E2eeManager {
         for (_, cryptor) in inner.frame_cryptors.iter() {
             cryptor.set_enabled(enabled);
         }
+        for (_, transformer) in inner.frame_transformers.iter() {
+            transformer.set_enabled(enabled);
+        }
     }
 
     pub fn key_provider(&self) -> Option<KeyProvider> {
@@ -200,6 +456,316 @@ impl E2eeManager {
         inner.options.as_ref().map(|opts| opts.encryption_type).unwrap_or(EncryptionType::None)
     }
 
+    /// Enables the automatic exchange of sender keys with other participants.
+    ///
+    /// A keypair and a sender key are generated for `local_identity`, and the public key is
+    /// published through `transport`. Sender keys are then wrapped for each participant whose
+    /// public key is passed to [`Self::handle_remote_public_key`], and rotated when a
+    /// participant leaves.
+    ///
+    pub fn enable_key_exchange(
+        &self,
+        local_identity: ParticipantIdentity,
+        transport: Arc<dyn KeyExchangeTransport>,
+    ) -> Result<(), KeyExchangeError> {
+        let public_key = {
+            let mut inner = self.inner.lock();
+            let key_provider = inner
+                .options
+                .as_ref()
+                .map(|opts| opts.key_provider.clone())
+                .ok_or(KeyExchangeError::NotInitialized)?;
+
+            let key_exchange = KeyExchange::new(local_identity.clone());
+            let (key_index, sender_key) = key_exchange.sender_key();
+            key_provider.set_key(&local_identity, key_index, sender_key);
+            Self::set_sender_key_index(&mut inner, &local_identity, key_index);
+
+            let public_key = key_exchange.public_key();
+            inner.key_exchange = Some((key_exchange, transport.clone()));
+            public_key
+        };
+        transport.publish_public_key(public_key);
+        Ok(())
+    }
+
+    pub fn key_exchange_enabled(&self) -> bool {
+        self.inner.lock().key_exchange.is_some()
+    }
+
+    /// Returns the base64 encoded public key of the local participant, if key exchange is enabled.
+    pub fn key_exchange_public_key(&self) -> Option<String> {
+        self.inner.lock().key_exchange.as_ref().map(|(key_exchange, _)| key_exchange.public_key())
+    }
+
+    /// Sends the local sender key to a participant that published its public key.
+    pub fn handle_remote_public_key(
+        &self,
+        identity: ParticipantIdentity,
+        public_key: &str,
+    ) -> Result<(), KeyExchangeError> {
+        let (transport, message) = {
+            let mut inner = self.inner.lock();
+            let (key_exchange, transport) =
+                inner.key_exchange.as_mut().ok_or(KeyExchangeError::NotEnabled)?;
+            if &identity == key_exchange.local_identity() {
+                return Ok(());
+            }
+            let Some(message) = key_exchange.add_peer(identity.clone(), public_key)? else {
+                return Ok(());
+            };
+            (transport.clone(), message)
+        };
+        transport.send_wrapped_key(identity, message);
+        Ok(())
+    }
+
+    /// Installs a sender key wrapped by another participant.
+    pub fn handle_key_exchange_message(
+        &self,
+        sender: &ParticipantIdentity,
+        message: &str,
+    ) -> Result<(), KeyExchangeError> {
+        let inner = self.inner.lock();
+        let (key_exchange, _) = inner.key_exchange.as_ref().ok_or(KeyExchangeError::NotEnabled)?;
+        let (key_index, key) = key_exchange.unwrap(sender, message)?;
+        let key_provider = &inner.options.as_ref().unwrap().key_provider;
+        key_provider.set_key(sender, key_index, key);
+        Ok(())
+    }
+
+    /// Rotates the local sender key if the participant that left had received it.
+    pub fn handle_participant_left(&self, identity: &ParticipantIdentity) {
+        let removed = match self.inner.lock().key_exchange.as_mut() {
+            Some((key_exchange, _)) => key_exchange.remove_peer(identity),
+            None => false,
+        };
+        if removed {
+            let _ = self.rotate_sender_key();
+        }
+    }
+
+    /// Generates a new local sender key and sends it to every known participant.
+    ///
+    /// Local tracks keep being encrypted with the previous key until the new one had time to
+    /// reach the other participants.
+    ///
+    pub fn rotate_sender_key(&self) -> Result<(), KeyExchangeError> {
+        let (transport, messages, local_identity, key_index) = {
+            let mut inner = self.inner.lock();
+            let (key_exchange, transport) =
+                inner.key_exchange.as_mut().ok_or(KeyExchangeError::NotEnabled)?;
+            let messages = key_exchange.rotate();
+            let local_identity = key_exchange.local_identity().clone();
+            let (key_index, sender_key) = key_exchange.sender_key();
+            let transport = transport.clone();
+
+            let key_provider = &inner.options.as_ref().unwrap().key_provider;
+            key_provider.set_key(&local_identity, key_index, sender_key);
+            (transport, messages, local_identity, key_index)
+        };
+        for (identity, message) in messages {
+            transport.send_wrapped_key(identity, message);
+        }
+        self.switch_sender_key_index_later(&mut self.inner.lock(), local_identity, key_index);
+        Ok(())
+    }
+
+    /// Starts rotating the local key according to `options`, or stops rotating if `None`.
+    ///
+    /// Replaces any previously configured schedule. Each rotation is reported through
+    /// `RoomEvent::EncryptionKeyRotated`.
+    ///
+    pub fn set_key_rotation(
+        &self,
+        local_identity: ParticipantIdentity,
+        options: Option<KeyRotationOptions>,
+    ) -> Result<(), KeyRotationError> {
+        let mut inner = self.inner.lock();
+        if inner.options.is_none() {
+            Err(KeyRotationError::NotInitialized)?
+        }
+
+        inner.key_rotation_close_tx = None;
+        let Some(options) = options else {
+            return Ok(());
+        };
+        if options.poll_interval().is_none() {
+            Err(KeyRotationError::InvalidOptions("either interval or frame_interval is required"))?
+        }
+        if options.frame_interval == Some(0) {
+            Err(KeyRotationError::InvalidOptions("frame_interval must be positive"))?
+        }
+        if options.mode == KeyRotationMode::Replace && inner.key_exchange.is_none() {
+            Err(KeyRotationError::KeyExchangeRequired)?
+        }
+
+        let (close_tx, close_rx) = oneshot::channel();
+        inner.key_rotation_close_tx = Some(close_tx);
+        livekit_runtime::spawn(self.clone().key_rotation_task(local_identity, options, close_rx));
+        Ok(())
+    }
+
+    /// Rotates the local key once, returning the key index of the new key.
+    ///
+    /// With [`KeyRotationMode::Ratchet`], the key at the current index is ratcheted in place.
+    /// With [`KeyRotationMode::Replace`], a new random key is set in the next index, wrapping
+    /// around the key ring of the frame cryptors, and sent to the other participants. This
+    /// requires key exchange, nobody else could decrypt with the new key otherwise. Local
+    /// tracks switch to a replaced key after a short delay, so that receivers already have it.
+    ///
+    pub fn rotate_key(
+        &self,
+        local_identity: &ParticipantIdentity,
+        mode: KeyRotationMode,
+    ) -> Result<i32, KeyRotationError> {
+        if mode == KeyRotationMode::Replace {
+            self.rotate_sender_key().map_err(|_| KeyRotationError::KeyExchangeRequired)?;
+        } else {
+            let mut inner = self.inner.lock();
+            let key_provider = inner
+                .options
+                .as_ref()
+                .map(|opts| opts.key_provider.clone())
+                .ok_or(KeyRotationError::NotInitialized)?;
+            let key_index = match inner.key_exchange.as_ref() {
+                Some((key_exchange, _)) => key_exchange.sender_key().0,
+                None => key_provider.get_latest_key_index(),
+            };
+
+            let key = if key_provider.is_shared_key() {
+                key_provider.ratchet_shared_key(key_index)
+            } else {
+                key_provider.ratchet_key(local_identity, key_index)
+            }
+            .ok_or(KeyRotationError::MissingKey(key_index))?;
+
+            if let Some((key_exchange, _)) = inner.key_exchange.as_mut() {
+                key_exchange.set_sender_key(key);
+            }
+        }
+
+        let key_index = self.local_key_index();
+        if let Some(key_rotated) = self.key_rotated.lock().as_ref() {
+            key_rotated(local_identity.clone(), KeyRotation { mode, key_index });
+        }
+        Ok(key_index)
+    }
+
+    /// Key index used to encrypt local tracks, and the one they are about to switch to
+    #[cfg(test)]
+    pub(crate) fn sender_key_index(&self) -> (i32, Option<i32>) {
+        let inner = self.inner.lock();
+        (inner.sender_key_index, inner.pending_sender_key_index)
+    }
+
+    /// Key index of the latest local key
+    fn local_key_index(&self) -> i32 {
+        let inner = self.inner.lock();
+        match (inner.key_exchange.as_ref(), inner.options.as_ref()) {
+            (Some((key_exchange, _)), _) => key_exchange.sender_key().0,
+            (None, Some(options)) => options.key_provider.get_latest_key_index(),
+            (None, None) => 0,
+        }
+    }
+
+    /// Number of frames sent across all local tracks.
+    /// Audio packets are counted as frames.
+    async fn local_frames_sent(&self) -> u64 {
+        let senders: Vec<RtpSender> = self.inner.lock().local_senders.values().cloned().collect();
+        let mut frames = 0;
+        for sender in senders {
+            let Ok(stats) = sender.get_stats().await else {
+                continue;
+            };
+            for stats in stats {
+                if let RtcStats::OutboundRtp(outbound) = stats {
+                    frames += match outbound.stream.kind.as_str() {
+                        "video" => outbound.outbound.frames_encoded as u64,
+                        _ => outbound.sent.packets_sent,
+                    };
+                }
+            }
+        }
+        frames
+    }
+
+    async fn key_rotation_task(
+        self,
+        local_identity: ParticipantIdentity,
+        options: KeyRotationOptions,
+        mut close_rx: oneshot::Receiver<()>,
+    ) {
+        let poll_interval = options.poll_interval().unwrap();
+        let mut last_rotation = Instant::now();
+        let mut last_frames = match options.frame_interval {
+            Some(_) => self.local_frames_sent().await,
+            None => 0,
+        };
+
+        loop {
+            tokio::select! {
+                _ = &mut close_rx => break,
+                _ = livekit_runtime::sleep(poll_interval) => {},
+            }
+
+            let mut rotate = options.interval.is_some_and(|i| last_rotation.elapsed() >= i);
+            let mut frames = 0;
+            if let Some(frame_interval) = options.frame_interval {
+                frames = self.local_frames_sent().await;
+                if frames < last_frames {
+                    // Counters restart when tracks are unpublished
+                    last_frames = frames;
+                }
+                rotate |= frames - last_frames >= frame_interval;
+            }
+            if !rotate {
+                continue;
+            }
+
+            match self.rotate_key(&local_identity, options.mode) {
+                Ok(key_index) => log::debug!("rotated e2ee key, key_index: {}", key_index),
+                Err(e) => log::warn!("failed to rotate e2ee key: {}", e),
+            }
+            last_rotation = Instant::now();
+            last_frames = frames;
+        }
+    }
+
+    /// Switches the frame cryptors of the local participant to the given key index.
+    fn set_sender_key_index(
+        inner: &mut ManagerInner,
+        local_identity: &ParticipantIdentity,
+        key_index: i32,
+    ) {
+        inner.sender_key_index = key_index;
+        inner.pending_sender_key_index = None;
+        for ((identity, _), cryptor) in inner.frame_cryptors.iter() {
+            if identity == local_identity {
+                cryptor.set_key_index(key_index);
+            }
+        }
+    }
+
+    /// Switches local tracks to a new key index after [`SENDER_KEY_SWITCH_DELAY`], unless
+    /// another rotation happens in the meantime.
+    fn switch_sender_key_index_later(
+        &self,
+        inner: &mut ManagerInner,
+        local_identity: ParticipantIdentity,
+        key_index: i32,
+    ) {
+        inner.pending_sender_key_index = Some(key_index);
+        let manager = self.clone();
+        livekit_runtime::spawn(async move {
+            livekit_runtime::sleep(SENDER_KEY_SWITCH_DELAY).await;
+            let mut inner = manager.inner.lock();
+            if inner.pending_sender_key_index == Some(key_index) {
+                Self::set_sender_key_index(&mut inner, &local_identity, key_index);
+            }
+        });
+    }
+
     fn setup_rtp_sender(
         &self,
         participant_identity: &ParticipantIdentity,
@@ -215,6 +781,7 @@ impl E2eeManager {
             options.key_provider.handle.clone(),
             sender,
         );
+        frame_cryptor.set_key_index(inner.sender_key_index);
         frame_cryptor.set_enabled(inner.enabled);
         frame_cryptor
     }
@@ -223,7 +790,7 @@ impl E2eeManager {
         &self,
         participant_identity: &ParticipantIdentity,
         receiver: RtpReceiver,
-    ) -> FrameCryptor {
+    ) -> Result<FrameCryptor, RtcError> {
         let inner = self.inner.lock();
         let options = inner.options.as_ref().unwrap();
 
@@ -233,16 +800,19 @@ impl E2eeManager {
             EncryptionAlgorithm::AesGcm,
             options.key_provider.handle.clone(),
             receiver,
-        );
+        )?;
         frame_cryptor.set_enabled(inner.enabled);
-        frame_cryptor
+        Ok(frame_cryptor)
     }
 
     fn remove_frame_cryptor(&self, participant_identity: ParticipantIdentity, track_sid: TrackSid) {
         log::debug!("removing frame cryptor for {}", participant_identity);
 
+        let key = (participant_identity, track_sid);
+        self.diagnostics.lock().remove(&key);
         let mut inner = self.inner.lock();
-        inner.frame_cryptors.remove(&(participant_identity, track_sid));
+        inner.frame_cryptors.remove(&key);
+        inner.frame_transformers.remove(&key);
     }
 
     /// Decrypt data received from a data channel
@@ -283,3 +853,13 @@ impl E2eeManager {
         data_packet_cryptor.encrypt(participant_identity, key_index, data)
     }
 }
+
+/// Installed in place of a missing custom transformer, so that frames advertised as
+/// encrypted are never sent or decoded as plaintext.
+struct DropFrames;
+
+impl FrameTransformer for DropFrames {
+    fn transform(&self, _: &str, _: &FrameMetadata, _: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+}
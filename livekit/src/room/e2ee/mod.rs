@@ -12,11 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use libwebrtc::native::frame_transformer::FrameTransformer;
 
 use self::key_provider::KeyProvider;
 
+pub mod diagnostics;
+pub mod key_exchange;
 pub mod key_provider;
+pub mod key_rotation;
 pub mod manager;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,10 +39,16 @@ pub enum EncryptionType {
 pub struct E2eeOptions {
     pub encryption_type: EncryptionType,
     pub key_provider: KeyProvider,
+    /// Transforms the encoded frames of tracks using [`EncryptionType::Custom`], instead of the
+    /// built-in frame cryptor
+    pub frame_transformer: Option<Arc<dyn FrameTransformer>>,
 }
 
 impl Debug for E2eeOptions {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("E2eeOptions").field("encryption_type", &self.encryption_type).finish()
+        f.debug_struct("E2eeOptions")
+            .field("encryption_type", &self.encryption_type)
+            .field("frame_transformer", &self.frame_transformer.is_some())
+            .finish()
     }
 }
@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use bmrng::unbounded::UnboundedRequestReceiver;
 use libwebrtc::{
     native::frame_cryptor::EncryptionState,
     prelude::{
@@ -29,7 +28,7 @@ use livekit_runtime::JoinHandle;
 use parking_lot::RwLock;
 pub use proto::DisconnectReason;
 use proto::{promise::Promise, SignalTarget};
-use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
+use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::sync::{
     broadcast,
@@ -38,6 +37,12 @@ use tokio::sync::{
 };
 pub use utils::take_cell::TakeCell;
 
+use self::e2ee::{
+    key_exchange::{
+        KeyExchangeError, RpcKeyExchangeTransport, KEY_EXCHANGE_RPC_METHOD, PUBLIC_KEY_ATTRIBUTE,
+    },
+    key_rotation::{KeyRotationError, KeyRotationMode, KeyRotationOptions},
+};
 pub use self::{
     data_stream::*,
     e2ee::{manager::E2eeManager, E2eeOptions},
@@ -45,7 +50,7 @@ pub use self::{
 };
 pub use crate::rtc_engine::SimulateScenario;
 use crate::{
-    participant::ConnectionQuality,
+    participant::{ConnectionQuality, RPC_REQUEST_TOPIC, RPC_RESPONSE_TOPIC},
     prelude::*,
     registered_audio_filter_plugins,
     rtc_engine::{
@@ -65,6 +70,10 @@ pub(crate) mod utils;
 
 pub const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Default amount of outgoing data stream bytes that can be pending on the reliable
+/// data channel before writers are made to wait.
+pub const DEFAULT_DATA_STREAM_HIGH_WATERMARK: u64 = 2 * INITIAL_BUFFERED_AMOUNT_LOW_THRESHOLD;
+
 pub type RoomResult<T> = Result<T, RoomError>;
 
 #[derive(Error, Debug)]
@@ -79,6 +88,10 @@ pub enum RoomError {
     TrackAlreadyPublished,
     #[error("already closed")]
     AlreadyClosed,
+    #[error("no encoder available for {}", .0.as_str())]
+    CodecUnavailable(options::VideoCodec),
+    #[error("encoded video sources must be published without simulcast, using {}", .0.as_str())]
+    EncodedSourceMismatch(options::VideoCodec),
     #[error("request error: {reason:?} - {message}")]
     Request { reason: proto::request_response::Reason, message: String },
 }
@@ -213,6 +226,19 @@ pub enum RoomEvent {
         participant: Participant,
         state: EncryptionState,
     },
+    /// The key used to encrypt the local tracks was rotated
+    EncryptionKeyRotated {
+        participant: Participant,
+        mode: KeyRotationMode,
+        /// Key index used to encrypt after the rotation
+        key_index: i32,
+    },
+    /// A track of the participant couldn't be decrypted because the key is missing.
+    /// The key can be requested from the participant and set on the key provider.
+    EncryptionKeyMissing {
+        participant: Participant,
+        key_index: i32,
+    },
     ConnectionStateChanged(ConnectionState),
     Connected {
         /// Initial participants & their tracks prior to joining the room
@@ -368,6 +394,9 @@ pub struct RoomOptions {
     pub rtc_config: RtcConfiguration,
     pub join_retries: u32,
     pub sdk_options: RoomSdkOptions,
+    /// Once this many bytes are pending on the reliable data channel, data stream writes
+    /// wait until the pending amount drops to the channel's buffered amount low threshold.
+    pub data_stream_high_watermark: u64,
 }
 
 impl Default for RoomOptions {
@@ -388,6 +417,7 @@ impl Default for RoomOptions {
             },
             join_retries: 3,
             sdk_options: RoomSdkOptions::default(),
+            data_stream_high_watermark: DEFAULT_DATA_STREAM_HIGH_WATERMARK,
         }
     }
 }
@@ -627,6 +657,20 @@ impl Room {
         });
         inner.local_participant.set_session(Arc::downgrade(&inner));
 
+        // Oversized RPC payloads are sent over text streams on reserved topics
+        for topic in [RPC_REQUEST_TOPIC, RPC_RESPONSE_TOPIC] {
+            let local_participant = inner.local_participant.clone();
+            inner.incoming_stream_manager.set_reserved_text_stream_handler(
+                topic,
+                Arc::new(move |reader, sender_identity| {
+                    let local_participant = local_participant.clone();
+                    Box::pin(async move {
+                        local_participant.handle_rpc_payload_stream(reader, sender_identity).await
+                    })
+                }),
+            );
+        }
+
         e2ee_manager.on_state_changed({
             let dispatcher = dispatcher.clone();
             let inner = inner.clone();
@@ -651,6 +695,36 @@ impl Room {
             }
         });
 
+        e2ee_manager.on_key_rotated({
+            let dispatcher = dispatcher.clone();
+            let inner = inner.clone();
+            move |_, rotation| {
+                dispatcher.dispatch(&RoomEvent::EncryptionKeyRotated {
+                    participant: Participant::Local(inner.local_participant.clone()),
+                    mode: rotation.mode,
+                    key_index: rotation.key_index,
+                });
+            }
+        });
+
+        e2ee_manager.on_key_missing({
+            let dispatcher = dispatcher.clone();
+            let inner = inner.clone();
+            move |participant_identity, key_index| {
+                // Ignore if the participant is disconnected
+                let Some(participant) =
+                    inner.remote_participants.read().get(&participant_identity).cloned()
+                else {
+                    return;
+                };
+
+                dispatcher.dispatch(&RoomEvent::EncryptionKeyMissing {
+                    participant: Participant::Remote(participant),
+                    key_index,
+                });
+            }
+        });
+
         for pi in join_response.other_participants {
             let participant = {
                 let pi = pi.clone();
@@ -691,6 +765,7 @@ impl Room {
         let outgoing_stream_handle = livekit_runtime::spawn(outgoing_data_stream_task(
             packet_rx,
             rtc_engine.clone(),
+            options.data_stream_high_watermark,
             close_rx.resubscribe(),
         ));
 
@@ -756,6 +831,95 @@ impl Room {
         &self.inner.e2ee_manager
     }
 
+    /// Enables the automatic exchange of E2EE sender keys with the other participants.
+    ///
+    /// The public key of the local participant is published in its attributes, and sender keys
+    /// are delivered over RPC. All participants must enable key exchange to decrypt each other.
+    ///
+    pub fn enable_e2ee_key_exchange(&self) -> Result<(), KeyExchangeError> {
+        let local_participant = self.inner.local_participant.clone();
+        let e2ee_manager = self.inner.e2ee_manager.clone();
+        local_participant.register_rpc_method(KEY_EXCHANGE_RPC_METHOD.to_string(), move |data| {
+            let result = e2ee_manager
+                .handle_key_exchange_message(&data.caller_identity, &data.payload)
+                .map(|_| String::new())
+                .map_err(|e| {
+                    RpcError::built_in(RpcErrorCode::ApplicationError, Some(e.to_string()))
+                });
+            Box::pin(async move { result })
+        });
+
+        let transport = RpcKeyExchangeTransport { local_participant: local_participant.clone() };
+        self.inner
+            .e2ee_manager
+            .enable_key_exchange(local_participant.identity(), Arc::new(transport))?;
+
+        for participant in self.inner.remote_participants.read().values() {
+            self.inner.handle_remote_public_key(participant.identity(), &participant.attributes());
+        }
+        Ok(())
+    }
+
+    /// Rotates the key of the local participant on a schedule, or stops rotating if `None`.
+    ///
+    /// See [`E2eeManager::set_key_rotation`].
+    ///
+    pub fn set_e2ee_key_rotation(
+        &self,
+        options: Option<KeyRotationOptions>,
+    ) -> Result<(), KeyRotationError> {
+        let identity = self.inner.local_participant.identity();
+        self.inner.e2ee_manager.set_key_rotation(identity, options)
+    }
+
+    /// Rotates the key of the local participant once, returning the key index now in use.
+    pub fn rotate_e2ee_key(&self, mode: KeyRotationMode) -> Result<i32, KeyRotationError> {
+        let identity = self.inner.local_participant.identity();
+        self.inner.e2ee_manager.rotate_key(&identity, mode)
+    }
+
+    /// Registers a handler for incoming byte streams on the given topic.
+    ///
+    /// Streams on a topic with a registered handler are passed to the handler instead
+    /// of being emitted as [`RoomEvent::ByteStreamOpened`]. Only one handler can be
+    /// registered per topic.
+    ///
+    pub fn register_byte_stream_handler(
+        &self,
+        topic: String,
+        handler: impl Fn(ByteStreamReader, ParticipantIdentity) -> Pin<Box<dyn Future<Output = ()> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> StreamResult<()> {
+        self.inner.incoming_stream_manager.register_byte_stream_handler(topic, Arc::new(handler))
+    }
+
+    /// Registers a handler for incoming text streams on the given topic.
+    ///
+    /// Streams on a topic with a registered handler are passed to the handler instead
+    /// of being emitted as [`RoomEvent::TextStreamOpened`]. Only one handler can be
+    /// registered per topic.
+    ///
+    pub fn register_text_stream_handler(
+        &self,
+        topic: String,
+        handler: impl Fn(TextStreamReader, ParticipantIdentity) -> Pin<Box<dyn Future<Output = ()> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> StreamResult<()> {
+        self.inner.incoming_stream_manager.register_text_stream_handler(topic, Arc::new(handler))
+    }
+
+    pub fn unregister_byte_stream_handler(&self, topic: &str) {
+        self.inner.incoming_stream_manager.unregister_byte_stream_handler(topic);
+    }
+
+    pub fn unregister_text_stream_handler(&self, topic: &str) {
+        self.inner.incoming_stream_manager.unregister_text_stream_handler(topic);
+    }
+
     pub fn data_channel_options(&self, kind: DataPacketKind) -> DataChannelOptions {
         match kind {
             DataPacketKind::Lossy => self.inner.info.read().lossy_dc_options.clone(),
@@ -1031,6 +1195,10 @@ impl RoomSession {
 
                 self.dispatcher
                     .dispatch(&RoomEvent::ParticipantConnected(remote_participant.clone()));
+                self.handle_remote_public_key(
+                    remote_participant.identity(),
+                    &remote_participant.attributes(),
+                );
 
                 remote_participant.update_info(pi.clone()); // Add tracks
             }
@@ -1735,7 +1903,11 @@ impl RoomSession {
 
         participant.on_attributes_changed({
             let dispatcher = self.dispatcher.clone();
+            let inner = Arc::downgrade(self);
             move |participant, changed_attributes| {
+                if let Some(inner) = inner.upgrade() {
+                    inner.handle_remote_public_key(participant.identity(), &changed_attributes);
+                }
                 let event =
                     RoomEvent::ParticipantAttributesChanged { participant, changed_attributes };
                 dispatcher.dispatch(&event);
@@ -1767,6 +1939,10 @@ impl RoomSession {
     /// A participant has disconnected
     /// Cleanup the participant and emit an event
     fn handle_participant_disconnect(self: Arc<Self>, remote_participant: RemoteParticipant) {
+        self.local_participant.cancel_rpc_invocations_from(&remote_participant.identity());
+        self.local_participant.forget_rpc_peer(&remote_participant.identity());
+        self.e2ee_manager.handle_participant_left(&remote_participant.identity());
+
         for (sid, _) in remote_participant.track_publications() {
             remote_participant.unpublish_track(&sid);
         }
@@ -1776,6 +1952,23 @@ impl RoomSession {
         self.dispatcher.dispatch(&RoomEvent::ParticipantDisconnected(remote_participant));
     }
 
+    /// Forwards the E2EE public key found in `attributes` to the key exchange, if enabled.
+    fn handle_remote_public_key(
+        &self,
+        identity: ParticipantIdentity,
+        attributes: &HashMap<String, String>,
+    ) {
+        if !self.e2ee_manager.key_exchange_enabled() {
+            return;
+        }
+        let Some(public_key) = attributes.get(PUBLIC_KEY_ATTRIBUTE) else {
+            return;
+        };
+        if let Err(e) = self.e2ee_manager.handle_remote_public_key(identity.clone(), public_key) {
+            log::warn!("failed to exchange e2ee key with {}: {}", identity, e);
+        }
+    }
+
     fn get_participant_by_sid(&self, sid: &ParticipantSid) -> Option<RemoteParticipant> {
         self.remote_participants.read().values().find(|x| &x.sid() == sid).cloned()
     }
@@ -1836,16 +2029,23 @@ async fn incoming_data_stream_task(
     }
 }
 
-/// Receives packets from the outgoing stream manager and send them.
+/// Receives packets from the outgoing stream manager and enqueues them for sending.
+///
+/// Packets are only accepted while the reliable data channel has capacity, which
+/// provides backpressure to stream writers.
 async fn outgoing_data_stream_task(
-    mut packet_rx: UnboundedRequestReceiver<proto::DataPacket, Result<(), EngineError>>,
+    mut packet_rx: PacketReceiver,
     engine: Arc<RtcEngine>,
+    high_watermark: u64,
     mut close_rx: broadcast::Receiver<()>,
 ) {
     loop {
         tokio::select! {
             Ok((packet, responder)) = packet_rx.recv() => {
-                let result = engine.publish_data(packet, DataPacketKind::Reliable, false).await;
+                let result = match engine.wait_reliable_buffered_amount_low(high_watermark).await {
+                    Ok(()) => engine.enqueue_data(packet, DataPacketKind::Reliable, false).await,
+                    Err(err) => Err(err),
+                };
                 let _ = responder.respond(result);
             },
             _ = close_rx.recv() => {
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Debug,
     future::Future,
     path::Path,
@@ -28,13 +28,19 @@ use super::{
 };
 use crate::{
     data_stream::{
-        ByteStreamInfo, ByteStreamWriter, StreamByteOptions, StreamResult, StreamTextOptions,
-        TextStreamInfo, TextStreamWriter,
+        ByteStreamInfo, ByteStreamWriter, StreamByteOptions, StreamReader, StreamResult,
+        StreamTextOptions, TextStreamInfo, TextStreamReader, TextStreamWriter,
     },
     e2ee::EncryptionType,
     options::{self, compute_video_encodings, video_layers_from_encodings, TrackPublishOptions},
     prelude::*,
-    room::participant::rpc::{RpcError, RpcErrorCode, RpcInvocationData, MAX_PAYLOAD_BYTES},
+    room::participant::rpc::{
+        PerformTypedRpcData, RpcCancellationToken, RpcError, RpcErrorCode, RpcFuture,
+        RpcInterceptor, RpcInvocationData, RpcMethodInfo, RpcNext, RpcTargets, MAX_PAYLOAD_BYTES,
+        RPC_CANCEL_METHOD, RPC_DISCOVERY_METHOD, RPC_REQUEST_ID_ATTR, RPC_REQUEST_TOPIC,
+        RPC_RESPONSE_TOPIC, RPC_VERSION_INLINE, RPC_VERSION_STREAM_REQUEST,
+        RPC_VERSION_STREAM_RESPONSE,
+    },
     rtc_engine::{EngineError, RtcEngine},
     ChatMessage, DataPacket, RoomSession, RpcAck, RpcRequest, RpcResponse, SipDTMF, Transcription,
 };
@@ -46,15 +52,15 @@ use livekit_runtime::timeout;
 use parking_lot::{Mutex, RwLock};
 use proto::request_response::Reason;
 use semver::Version;
+use serde::{de::DeserializeOwned, Serialize};
 use tokio::sync::oneshot;
 
-type RpcHandler = Arc<
-    dyn Fn(RpcInvocationData) -> Pin<Box<dyn Future<Output = Result<String, RpcError>> + Send>>
-        + Send
-        + Sync,
->;
+type RpcHandler = Arc<dyn Fn(RpcInvocationData) -> RpcFuture + Send + Sync>;
 
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a request payload received over a data stream is kept while waiting for the
+/// request that references it.
+const STREAMED_PAYLOAD_TIMEOUT: Duration = Duration::from_secs(10);
 
 type LocalTrackPublishedHandler = Box<dyn Fn(LocalParticipant, LocalTrackPublication) + Send>;
 type LocalTrackUnpublishedHandler = Box<dyn Fn(LocalParticipant, LocalTrackPublication) + Send>;
@@ -67,8 +73,26 @@ struct LocalEvents {
 
 struct RpcState {
     pending_acks: HashMap<String, oneshot::Sender<()>>,
-    pending_responses: HashMap<String, oneshot::Sender<Result<String, RpcError>>>,
+    /// Responses awaited from the destination participant of each request, keyed by request ID.
+    pending_responses:
+        HashMap<String, (ParticipantIdentity, oneshot::Sender<Result<String, RpcError>>)>,
     handlers: HashMap<String, RpcHandler>,
+    method_info: HashMap<String, RpcMethodInfo>,
+    discovery_enabled: bool,
+    /// Identities of participants known to only support inline payloads.
+    inline_only_peers: HashSet<String>,
+    /// Request payloads sent over a data stream, keyed by caller identity and request ID.
+    streamed_payloads: HashMap<(ParticipantIdentity, String), StreamedPayload>,
+    interceptors: Vec<Arc<dyn RpcInterceptor>>,
+    /// Incoming invocations that can still be cancelled by their caller, keyed by request ID.
+    active_invocations: HashMap<String, (ParticipantIdentity, RpcCancellationToken)>,
+}
+
+/// A request payload sent over a data stream may be read before or after the request
+/// that references it.
+enum StreamedPayload {
+    Waiting(oneshot::Sender<String>),
+    Received(String),
 }
 
 impl RpcState {
@@ -77,6 +101,12 @@ impl RpcState {
             pending_acks: HashMap::new(),
             pending_responses: HashMap::new(),
             handlers: HashMap::new(),
+            method_info: HashMap::new(),
+            discovery_enabled: false,
+            inline_only_peers: HashSet::new(),
+            streamed_payloads: HashMap::new(),
+            interceptors: Vec::new(),
+            active_invocations: HashMap::new(),
         }
     }
 }
@@ -254,6 +284,17 @@ impl LocalParticipant {
         track: LocalTrack,
         options: TrackPublishOptions,
     ) -> RoomResult<LocalTrackPublication> {
+        if self.local.encryption_type == EncryptionType::Custom {
+            let has_transformer = self
+                .session()
+                .is_some_and(|session| session.e2ee_manager.frame_transformer().is_some());
+            if !has_transformer {
+                Err(RoomError::Internal(
+                    "custom encryption requires E2eeOptions::frame_transformer".into(),
+                ))?
+            }
+        }
+
         let disable_red = self.local.encryption_type != EncryptionType::None || !options.red;
 
         let mut req = proto::AddTrackRequest {
@@ -276,6 +317,24 @@ impl LocalParticipant {
         let mut encodings = Vec::default();
         match &track {
             LocalTrack::Video(video_track) => {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let libwebrtc::video_source::RtcVideoSource::Encoded(source) =
+                    video_track.rtc_source()
+                {
+                    use libwebrtc::video_source::native::EncodedVideoCodec;
+
+                    // Encoded frames are sent as is: a single layer, in the codec of the source
+                    let codec = match source.codec() {
+                        EncodedVideoCodec::H264 => options::VideoCodec::H264,
+                        EncodedVideoCodec::H265 => options::VideoCodec::H265,
+                    };
+                    if options.simulcast || options.video_codec != codec {
+                        Err(RoomError::EncodedSourceMismatch(codec))?
+                    }
+                } else if !has_video_encoder(options.video_codec) {
+                    Err(RoomError::CodecUnavailable(options.video_codec))?
+                }
+
                 // Get the video dimension
                 // TODO(theomonnom): Use MediaStreamTrack::getSettings() on web
                 let resolution = video_track.rtc_source().video_resolution();
@@ -778,16 +837,138 @@ impl LocalParticipant {
         self.inner.info.read().permission.clone()
     }
 
+    /// Performs an RPC call on a remote participant.
+    ///
+    /// The call passes through the interceptors added with [`Self::add_rpc_interceptor`]
+    /// before being sent.
+    ///
+    /// Payloads larger than [`MAX_PAYLOAD_BYTES`] are sent over a data stream. Participants
+    /// that don't support this reject such requests with [`RpcErrorCode::RequestPayloadTooLarge`].
+    ///
     pub async fn perform_rpc(&self, data: PerformRpcData) -> Result<String, RpcError> {
+        let local_participant = self.clone();
+        let send = RpcNext::new(move |data| {
+            let local_participant = local_participant.clone();
+            Box::pin(async move { local_participant.send_rpc_request(data).await })
+        });
+
+        let interceptors = self.local.rpc_state.lock().interceptors.clone();
+        let chain = interceptors.into_iter().rev().fold(send, |next, interceptor| {
+            RpcNext::new(move |data| interceptor.intercept_outgoing(data, next.clone()))
+        });
+        chain.run(data).await
+    }
+
+    /// Performs the same RPC call on several participants concurrently.
+    ///
+    /// Returns the result of each call, keyed by the identity of the participant it was sent to.
+    ///
+    pub async fn perform_rpc_many(
+        &self,
+        identities: Vec<ParticipantIdentity>,
+        method: String,
+        payload: String,
+        response_timeout: Duration,
+    ) -> HashMap<ParticipantIdentity, Result<String, RpcError>> {
+        let calls: Vec<_> = identities
+            .into_iter()
+            .map(|identity| {
+                let local_participant = self.clone();
+                let data = PerformRpcData {
+                    destination_identity: identity.to_string(),
+                    method: method.clone(),
+                    payload: payload.clone(),
+                    response_timeout,
+                    ..Default::default()
+                };
+                (
+                    identity,
+                    tokio::task::spawn(async move { local_participant.perform_rpc(data).await }),
+                )
+            })
+            .collect();
+
+        let mut results = HashMap::with_capacity(calls.len());
+        for (identity, call) in calls {
+            let result = call.await.unwrap_or_else(|e| {
+                Err(RpcError::built_in(RpcErrorCode::SendFailed, Some(e.to_string())))
+            });
+            results.insert(identity, result);
+        }
+        results
+    }
+
+    /// Performs the same RPC call on every remote participant selected by `targets`.
+    ///
+    /// See [`Self::perform_rpc_many`].
+    ///
+    pub async fn perform_rpc_matching(
+        &self,
+        targets: RpcTargets,
+        method: String,
+        payload: String,
+        response_timeout: Duration,
+    ) -> HashMap<ParticipantIdentity, Result<String, RpcError>> {
+        let identities = match self.session() {
+            Some(session) => session
+                .remote_participants
+                .read()
+                .values()
+                .filter(|participant| {
+                    targets.matches(&participant.attributes(), participant.kind())
+                })
+                .map(|participant| participant.identity())
+                .collect(),
+            None => Vec::new(),
+        };
+        self.perform_rpc_many(identities, method, payload, response_timeout).await
+    }
+
+    async fn send_rpc_request(&self, data: PerformRpcData) -> Result<String, RpcError> {
+        if data.payload.len() > MAX_PAYLOAD_BYTES {
+            return self.perform_rpc_with_version(data, RPC_VERSION_STREAM_REQUEST).await.map_err(
+                |e| match e.code == RpcErrorCode::UnsupportedVersion as u32 {
+                    true => RpcError::built_in(RpcErrorCode::RequestPayloadTooLarge, None),
+                    false => e,
+                },
+            );
+        }
+
+        let destination_identity = data.destination_identity.clone();
+        if !self.local.rpc_state.lock().inline_only_peers.contains(&destination_identity) {
+            match self.perform_rpc_with_version(data.clone(), RPC_VERSION_STREAM_RESPONSE).await {
+                Err(e) if e.code == RpcErrorCode::UnsupportedVersion as u32 => {
+                    // The handler wasn't invoked, retry with the version every participant supports
+                    self.local.rpc_state.lock().inline_only_peers.insert(destination_identity);
+                }
+                result => return result,
+            }
+        }
+        self.perform_rpc_with_version(data, RPC_VERSION_INLINE).await
+    }
+
+    async fn perform_rpc_with_version(
+        &self,
+        data: PerformRpcData,
+        version: u32,
+    ) -> Result<String, RpcError> {
         // Maximum amount of time it should ever take for an RPC request to reach the destination, and the ACK to come back
         // This is set to 7 seconds to account for various relay timeouts and retries in LiveKit Cloud that occur in rare cases
 
         let max_round_trip_latency = Duration::from_millis(7000);
         let min_effective_timeout = Duration::from_millis(1000);
 
-        if data.payload.len() > MAX_PAYLOAD_BYTES {
-            return Err(RpcError::built_in(RpcErrorCode::RequestPayloadTooLarge, None));
+        let cancellation_token = data.cancellation_token.clone();
+        if cancellation_token.as_ref().is_some_and(|token| token.is_cancelled()) {
+            return Err(RpcError::built_in(RpcErrorCode::Cancelled, None));
         }
+        let cancelled = async {
+            match &cancellation_token {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(cancelled);
 
         if let Some(server_info) =
             self.inner.rtc_engine.session().signal_client().join_response().server_info
@@ -814,7 +995,9 @@ impl LocalParticipant {
         {
             let mut rpc_state = self.local.rpc_state.lock();
             rpc_state.pending_acks.insert(id.clone(), ack_tx);
-            rpc_state.pending_responses.insert(id.clone(), response_tx);
+            rpc_state
+                .pending_responses
+                .insert(id.clone(), (data.destination_identity.clone().into(), response_tx));
         }
 
         if let Err(e) = self
@@ -822,9 +1005,12 @@ impl LocalParticipant {
                 destination_identity: data.destination_identity.clone(),
                 id: id.clone(),
                 method: data.method.clone(),
-                payload: data.payload.clone(),
+                payload: match version {
+                    RPC_VERSION_STREAM_REQUEST => String::new(),
+                    _ => data.payload.clone(),
+                },
                 response_timeout: effective_timeout,
-                version: 1,
+                version,
             })
             .await
         {
@@ -836,8 +1022,30 @@ impl LocalParticipant {
             return Err(RpcError::built_in(RpcErrorCode::SendFailed, Some(e.to_string())));
         }
 
+        if version == RPC_VERSION_STREAM_REQUEST {
+            let options = StreamTextOptions {
+                topic: RPC_REQUEST_TOPIC.to_string(),
+                attributes: HashMap::from([(RPC_REQUEST_ID_ATTR.to_string(), id.clone())]),
+                destination_identities: vec![data.destination_identity.clone().into()],
+                ..Default::default()
+            };
+            if let Err(e) = self.send_text(&data.payload, options).await {
+                let mut rpc_state = self.local.rpc_state.lock();
+                rpc_state.pending_acks.remove(&id);
+                rpc_state.pending_responses.remove(&id);
+                log::error!("Failed to send RPC request payload: {}", e);
+                return Err(RpcError::built_in(RpcErrorCode::SendFailed, Some(e.to_string())));
+            }
+        }
+
         // Wait for ack timeout
-        match tokio::time::timeout(max_round_trip_latency, ack_rx).await {
+        let ack = tokio::select! {
+            ack = tokio::time::timeout(max_round_trip_latency, ack_rx) => ack,
+            _ = &mut cancelled => {
+                return Err(self.cancel_rpc(id.clone(), data.destination_identity.clone()));
+            }
+        };
+        match ack {
             Err(_) => {
                 let mut rpc_state = self.local.rpc_state.lock();
                 rpc_state.pending_acks.remove(&id);
@@ -850,7 +1058,13 @@ impl LocalParticipant {
         }
 
         // Wait for response timout
-        let response = match tokio::time::timeout(data.response_timeout, response_rx).await {
+        let response = tokio::select! {
+            response = tokio::time::timeout(data.response_timeout, response_rx) => response,
+            _ = &mut cancelled => {
+                return Err(self.cancel_rpc(id.clone(), data.destination_identity.clone()));
+            }
+        };
+        let response = match response {
             Err(_) => {
                 self.local.rpc_state.lock().pending_responses.remove(&id);
                 return Err(RpcError::built_in(RpcErrorCode::ResponseTimeout, None));
@@ -874,6 +1088,95 @@ impl LocalParticipant {
         }
     }
 
+    /// Stops waiting for a pending call and notifies the destination participant.
+    fn cancel_rpc(&self, request_id: String, destination_identity: String) -> RpcError {
+        // Best effort, the ack and response to the notice are ignored
+        let notice_id = create_random_uuid();
+        let notice_timeout = Duration::from_millis(1000);
+        let (response_tx, response_rx) = oneshot::channel();
+        {
+            let mut rpc_state = self.local.rpc_state.lock();
+            rpc_state.pending_acks.remove(&request_id);
+            rpc_state.pending_responses.remove(&request_id);
+            rpc_state.pending_acks.insert(notice_id.clone(), oneshot::channel().0);
+            rpc_state
+                .pending_responses
+                .insert(notice_id.clone(), (destination_identity.clone().into(), response_tx));
+        }
+
+        let local_participant = self.clone();
+        livekit_runtime::spawn(async move {
+            if let Err(e) = local_participant
+                .publish_rpc_request(RpcRequest {
+                    destination_identity,
+                    id: notice_id.clone(),
+                    method: RPC_CANCEL_METHOD.to_string(),
+                    payload: request_id,
+                    response_timeout: notice_timeout,
+                    version: RPC_VERSION_INLINE,
+                })
+                .await
+            {
+                log::warn!("Failed to publish RPC cancellation: {}", e);
+            } else {
+                // Leave time for the ack and the response, same as a regular call
+                let max_round_trip_latency = Duration::from_millis(7000);
+                let _ = tokio::time::timeout(max_round_trip_latency + notice_timeout, response_rx)
+                    .await;
+            }
+
+            // The destination may never answer, don't keep waiting for it
+            let mut rpc_state = local_participant.local.rpc_state.lock();
+            rpc_state.pending_acks.remove(&notice_id);
+            rpc_state.pending_responses.remove(&notice_id);
+        });
+
+        RpcError::built_in(RpcErrorCode::Cancelled, None)
+    }
+
+    /// Performs an RPC call with a request and response that are encoded as JSON.
+    ///
+    /// A response that cannot be deserialized into `Resp` results in an error with the
+    /// [`RpcErrorCode::InvalidResponsePayload`] code.
+    ///
+    pub async fn perform_typed_rpc<Req: Serialize, Resp: DeserializeOwned>(
+        &self,
+        data: PerformTypedRpcData<Req>,
+    ) -> Result<Resp, RpcError> {
+        let payload = serde_json::to_string(&data.request).map_err(|e| {
+            RpcError::built_in(RpcErrorCode::InvalidRequestPayload, Some(e.to_string()))
+        })?;
+        let response = self
+            .perform_rpc(PerformRpcData {
+                destination_identity: data.destination_identity,
+                method: data.method,
+                payload,
+                response_timeout: data.response_timeout,
+                cancellation_token: data.cancellation_token,
+            })
+            .await?;
+        serde_json::from_str(&response).map_err(|e| {
+            RpcError::built_in(RpcErrorCode::InvalidResponsePayload, Some(e.to_string()))
+        })
+    }
+
+    /// Lists the RPC methods registered by a remote participant.
+    ///
+    /// The remote participant must have enabled discovery, otherwise the call fails with
+    /// [`RpcErrorCode::UnsupportedMethod`].
+    ///
+    pub async fn list_rpc_methods(
+        &self,
+        destination_identity: String,
+    ) -> Result<Vec<RpcMethodInfo>, RpcError> {
+        self.perform_typed_rpc(PerformTypedRpcData::new(
+            destination_identity,
+            RPC_DISCOVERY_METHOD.to_string(),
+            (),
+        ))
+        .await
+    }
+
     pub fn register_rpc_method(
         &self,
         method: String,
@@ -882,7 +1185,57 @@ impl LocalParticipant {
             + Sync
             + 'static,
     ) {
-        self.local.rpc_state.lock().handlers.insert(method, Arc::new(handler));
+        let info = RpcMethodInfo { name: method.clone(), typed: false };
+        self.register_rpc_handler(method, info, Arc::new(handler));
+    }
+
+    /// Registers an RPC method whose request and response are encoded as JSON.
+    ///
+    /// Requests that cannot be deserialized into `Req` are rejected with the
+    /// [`RpcErrorCode::InvalidRequestPayload`] code without invoking the handler.
+    ///
+    pub fn register_typed_rpc_method<Req, Resp>(
+        &self,
+        method: String,
+        handler: impl Fn(
+                RpcInvocationData,
+                Req,
+            ) -> Pin<Box<dyn Future<Output = Result<Resp, RpcError>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    ) where
+        Req: DeserializeOwned + Send + 'static,
+        Resp: Serialize + Send + 'static,
+    {
+        let info = RpcMethodInfo { name: method.clone(), typed: true };
+        let handler = Arc::new(handler);
+        let handler: RpcHandler = Arc::new(move |data: RpcInvocationData| {
+            let handler = handler.clone();
+            Box::pin(async move {
+                let request = serde_json::from_str::<Req>(&data.payload).map_err(|e| {
+                    RpcError::built_in(RpcErrorCode::InvalidRequestPayload, Some(e.to_string()))
+                })?;
+                let response = handler(data, request).await?;
+                serde_json::to_string(&response).map_err(|e| {
+                    RpcError::built_in(RpcErrorCode::ApplicationError, Some(e.to_string()))
+                })
+            })
+        });
+        self.register_rpc_handler(method, info, handler);
+    }
+
+    fn register_rpc_handler(&self, method: String, info: RpcMethodInfo, handler: RpcHandler) {
+        if method == RPC_DISCOVERY_METHOD {
+            log::warn!("cannot register reserved RPC method: {}", method);
+            return;
+        }
+
+        {
+            let mut rpc_state = self.local.rpc_state.lock();
+            rpc_state.method_info.insert(method.clone(), info);
+            rpc_state.handlers.insert(method, handler);
+        }
 
         // Pre-connect the publisher PC so ACKs can be sent immediately when requests arrive.
         // Without this, the first RPC request would trigger publisher negotiation, causing
@@ -891,7 +1244,37 @@ impl LocalParticipant {
     }
 
     pub fn unregister_rpc_method(&self, method: String) {
-        self.local.rpc_state.lock().handlers.remove(&method);
+        let mut rpc_state = self.local.rpc_state.lock();
+        rpc_state.handlers.remove(&method);
+        rpc_state.method_info.remove(&method);
+    }
+
+    /// Adds an interceptor to the end of the chain that every incoming invocation and
+    /// outgoing call passes through.
+    pub fn add_rpc_interceptor(&self, interceptor: impl RpcInterceptor + 'static) {
+        self.local.rpc_state.lock().interceptors.push(Arc::new(interceptor));
+    }
+
+    /// Removes all interceptors added with [`Self::add_rpc_interceptor`].
+    pub fn clear_rpc_interceptors(&self) {
+        self.local.rpc_state.lock().interceptors.clear();
+    }
+
+    /// Returns the RPC methods registered on this participant, sorted by name.
+    pub fn rpc_methods(&self) -> Vec<RpcMethodInfo> {
+        let mut methods: Vec<_> =
+            self.local.rpc_state.lock().method_info.values().cloned().collect();
+        methods.sort_by(|a, b| a.name.cmp(&b.name));
+        methods
+    }
+
+    /// Enables or disables answering the reserved [`RPC_DISCOVERY_METHOD`], which lets remote
+    /// participants list the RPC methods registered on this participant.
+    ///
+    /// Discovery is disabled by default.
+    ///
+    pub fn set_rpc_discovery_enabled(&self, enabled: bool) {
+        self.local.rpc_state.lock().discovery_enabled = enabled;
     }
 
     pub(crate) fn handle_incoming_rpc_ack(&self, request_id: String) {
@@ -910,7 +1293,7 @@ impl LocalParticipant {
         error: Option<proto::RpcError>,
     ) {
         let mut rpc_state = self.local.rpc_state.lock();
-        if let Some(tx) = rpc_state.pending_responses.remove(&request_id) {
+        if let Some((_, tx)) = rpc_state.pending_responses.remove(&request_id) {
             let _ = tx.send(match error {
                 Some(e) => Err(RpcError::from_proto(e)),
                 None => Ok(payload.unwrap_or_default()),
@@ -929,6 +1312,14 @@ impl LocalParticipant {
         response_timeout: Duration,
         version: u32,
     ) {
+        // Registered before the ACK, a cancel may arrive as soon as the caller receives it
+        let cancellation_token = RpcCancellationToken::new();
+        self.local
+            .rpc_state
+            .lock()
+            .active_invocations
+            .insert(request_id.clone(), (caller_identity.clone(), cancellation_token.clone()));
+
         if let Err(e) = self
             .publish_rpc_ack(RpcAck {
                 destination_identity: caller_identity.to_string(),
@@ -942,24 +1333,60 @@ impl LocalParticipant {
         let caller_identity_2 = caller_identity.clone();
         let request_id_2 = request_id.clone();
 
-        let response = if version != 1 {
+        let payload = match version {
+            RPC_VERSION_STREAM_REQUEST => {
+                self.receive_streamed_payload(&caller_identity, &request_id, response_timeout).await
+            }
+            _ => Ok(payload),
+        };
+
+        let response = if !(RPC_VERSION_INLINE..=RPC_VERSION_STREAM_REQUEST).contains(&version) {
             Err(RpcError::built_in(RpcErrorCode::UnsupportedVersion, None))
+        } else if method == RPC_CANCEL_METHOD {
+            if let Ok(cancelled_request_id) = &payload {
+                self.handle_incoming_rpc_cancel(&caller_identity, cancelled_request_id);
+            }
+            Ok(String::new())
         } else {
-            let handler = self.local.rpc_state.lock().handlers.get(&method).cloned();
-
-            match handler {
-                Some(handler) => {
-                    match tokio::task::spawn(async move {
-                        handler(RpcInvocationData {
-                            request_id: request_id.clone(),
-                            caller_identity: caller_identity.clone(),
-                            payload: payload.clone(),
-                            response_timeout,
-                        })
-                        .await
-                    })
-                    .await
-                    {
+            match payload {
+                Err(e) => Err(e),
+                Ok(payload) => {
+                    let caller_attributes = self
+                        .session()
+                        .and_then(|session| session.get_participant_by_identity(&caller_identity))
+                        .map(|participant| participant.attributes())
+                        .unwrap_or_default();
+                    let invocation = RpcInvocationData {
+                        request_id: request_id.clone(),
+                        method,
+                        caller_identity: caller_identity.clone(),
+                        caller_attributes,
+                        payload,
+                        response_timeout,
+                        cancellation_token: cancellation_token.clone(),
+                    };
+
+                    let local_participant = self.clone();
+                    let dispatch =
+                        RpcNext::new(move |data| local_participant.dispatch_rpc_invocation(data));
+                    let interceptors = self.local.rpc_state.lock().interceptors.clone();
+                    let chain =
+                        interceptors.into_iter().rev().fold(dispatch, |next, interceptor| {
+                            RpcNext::new(move |data| {
+                                interceptor.intercept_incoming(data, next.clone())
+                            })
+                        });
+
+                    let mut handler_task = tokio::task::spawn(chain.run(invocation));
+                    let result = tokio::select! {
+                        result = &mut handler_task => result,
+                        _ = tokio::time::sleep(response_timeout) => {
+                            // The caller has stopped waiting, let the handler know
+                            cancellation_token.cancel();
+                            handler_task.await
+                        }
+                    };
+                    match result {
                         Ok(result) => result,
                         Err(e) => {
                             log::error!("RPC method handler returned an error: {:?}", e);
@@ -967,14 +1394,37 @@ impl LocalParticipant {
                         }
                     }
                 }
-                None => Err(RpcError::built_in(RpcErrorCode::UnsupportedMethod, None)),
             }
         };
 
+        if self.local.rpc_state.lock().active_invocations.remove(&request_id_2).is_none() {
+            // Cancelled by the caller, who no longer expects a response
+            return;
+        }
+
         let (payload, error) = match response {
             Ok(response_payload) if response_payload.len() <= MAX_PAYLOAD_BYTES => {
                 (Some(response_payload), None)
             }
+            Ok(response_payload) if version >= RPC_VERSION_STREAM_RESPONSE => {
+                // The stream completes the request on the caller side
+                let options = StreamTextOptions {
+                    topic: RPC_RESPONSE_TOPIC.to_string(),
+                    attributes: HashMap::from([(
+                        RPC_REQUEST_ID_ATTR.to_string(),
+                        request_id_2.clone(),
+                    )]),
+                    destination_identities: vec![caller_identity_2.clone()],
+                    ..Default::default()
+                };
+                match self.send_text(&response_payload, options).await {
+                    Ok(_) => return,
+                    Err(e) => (
+                        None,
+                        Some(RpcError::built_in(RpcErrorCode::SendFailed, Some(e.to_string()))),
+                    ),
+                }
+            }
             Ok(_) => (None, Some(RpcError::built_in(RpcErrorCode::ResponsePayloadTooLarge, None))),
             Err(e) => (None, Some(e.into())),
         };
@@ -992,6 +1442,155 @@ impl LocalParticipant {
         }
     }
 
+    /// Looks up the handler of an incoming invocation that passed the interceptor chain.
+    fn dispatch_rpc_invocation(&self, data: RpcInvocationData) -> RpcFuture {
+        if data.method == RPC_DISCOVERY_METHOD {
+            let response = match self.local.rpc_state.lock().discovery_enabled {
+                true => serde_json::to_string(&self.rpc_methods()).map_err(|e| {
+                    RpcError::built_in(RpcErrorCode::ApplicationError, Some(e.to_string()))
+                }),
+                false => Err(RpcError::built_in(RpcErrorCode::UnsupportedMethod, None)),
+            };
+            return Box::pin(async move { response });
+        }
+
+        let handler = self.local.rpc_state.lock().handlers.get(&data.method).cloned();
+        match handler {
+            Some(handler) => handler(data),
+            None => {
+                Box::pin(async { Err(RpcError::built_in(RpcErrorCode::UnsupportedMethod, None)) })
+            }
+        }
+    }
+
+    fn handle_incoming_rpc_cancel(&self, caller_identity: &ParticipantIdentity, request_id: &str) {
+        let mut rpc_state = self.local.rpc_state.lock();
+        let Some((identity, _)) = rpc_state.active_invocations.get(request_id) else {
+            return;
+        };
+        if identity != caller_identity {
+            log::warn!("Ignoring RPC cancellation from a participant other than the caller");
+            return;
+        }
+        if let Some((_, cancellation_token)) = rpc_state.active_invocations.remove(request_id) {
+            cancellation_token.cancel();
+        }
+    }
+
+    /// Cancels the incoming invocations of a participant that has disconnected.
+    pub(crate) fn cancel_rpc_invocations_from(&self, caller_identity: &ParticipantIdentity) {
+        self.local.rpc_state.lock().active_invocations.retain(
+            |_, (identity, cancellation_token)| {
+                if identity != caller_identity {
+                    return true;
+                }
+                cancellation_token.cancel();
+                false
+            },
+        );
+    }
+
+    /// Forgets the RPC version of a participant that has disconnected, it may rejoin with
+    /// another SDK.
+    pub(crate) fn forget_rpc_peer(&self, identity: &ParticipantIdentity) {
+        self.local.rpc_state.lock().inline_only_peers.remove(identity.as_str());
+    }
+
+    /// Waits for a request payload sent over a data stream.
+    async fn receive_streamed_payload(
+        &self,
+        caller_identity: &ParticipantIdentity,
+        request_id: &str,
+        timeout: Duration,
+    ) -> Result<String, RpcError> {
+        let key = (caller_identity.clone(), request_id.to_string());
+        let payload_rx = {
+            let mut rpc_state = self.local.rpc_state.lock();
+            match rpc_state.streamed_payloads.remove(&key) {
+                Some(StreamedPayload::Received(payload)) => return Ok(payload),
+                _ => {
+                    let (payload_tx, payload_rx) = oneshot::channel();
+                    rpc_state
+                        .streamed_payloads
+                        .insert(key.clone(), StreamedPayload::Waiting(payload_tx));
+                    payload_rx
+                }
+            }
+        };
+
+        match tokio::time::timeout(timeout, payload_rx).await {
+            Ok(Ok(payload)) => Ok(payload),
+            _ => {
+                self.local.rpc_state.lock().streamed_payloads.remove(&key);
+                Err(RpcError::built_in(
+                    RpcErrorCode::SendFailed,
+                    Some("request payload stream was not received".to_string()),
+                ))
+            }
+        }
+    }
+
+    /// Handles a text stream carrying an oversized RPC request or response payload.
+    pub(crate) async fn handle_rpc_payload_stream(
+        &self,
+        reader: TextStreamReader,
+        sender_identity: ParticipantIdentity,
+    ) {
+        let topic = reader.info().topic.clone();
+        let Some(request_id) = reader.info().attributes.get(RPC_REQUEST_ID_ATTR).cloned() else {
+            log::warn!("RPC payload stream without request ID");
+            return;
+        };
+        if topic == RPC_RESPONSE_TOPIC {
+            let rpc_state = self.local.rpc_state.lock();
+            let Some((destination_identity, _)) = rpc_state.pending_responses.get(&request_id)
+            else {
+                log::error!("Response received for unexpected RPC request: {}", request_id);
+                return;
+            };
+            if *destination_identity != sender_identity {
+                log::warn!("Ignoring RPC response from a participant other than the destination");
+                return;
+            }
+        }
+        let payload = match reader.read_all().await {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::error!("Failed to read RPC payload stream: {:?}", e);
+                return;
+            }
+        };
+
+        if topic == RPC_RESPONSE_TOPIC {
+            self.handle_incoming_rpc_response(request_id, Some(payload), None);
+            return;
+        }
+
+        // Keyed by sender, so that a payload can only be used by a request from the same caller
+        let key = (sender_identity, request_id);
+        let mut rpc_state = self.local.rpc_state.lock();
+        match rpc_state.streamed_payloads.remove(&key) {
+            Some(StreamedPayload::Waiting(payload_tx)) => {
+                let _ = payload_tx.send(payload);
+            }
+            _ => {
+                rpc_state.streamed_payloads.insert(key.clone(), StreamedPayload::Received(payload));
+
+                // Evict the payload if the request never arrives
+                let local_participant = self.clone();
+                livekit_runtime::spawn(async move {
+                    livekit_runtime::sleep(STREAMED_PAYLOAD_TIMEOUT).await;
+                    let mut rpc_state = local_participant.local.rpc_state.lock();
+                    if let Some(StreamedPayload::Received(_)) =
+                        rpc_state.streamed_payloads.get(&key)
+                    {
+                        rpc_state.streamed_payloads.remove(&key);
+                    }
+                });
+            }
+        }
+    }
+
     /// Send text to participants in the room.
     ///
     /// This method sends a complete text string to participants in the room as a text stream.
@@ -1032,6 +1631,26 @@ impl LocalParticipant {
         self.session().unwrap().outgoing_stream_manager.send_file(path, options).await
     }
 
+    /// Send the contents of an asynchronous reader to participants in the room.
+    ///
+    /// This method reads from any [`AsyncRead`](tokio::io::AsyncRead) source until EOF,
+    /// sending its contents to participants in the room as a byte stream, and returns
+    /// information about the stream used.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source to read bytes from.
+    /// * `options` - Configuration options for the byte stream, including topic and
+    ///   destination participants. Set `total_length` if the size is known in advance.
+    ///
+    pub async fn send_reader(
+        &self,
+        reader: impl tokio::io::AsyncRead + Unpin,
+        options: StreamByteOptions,
+    ) -> StreamResult<ByteStreamInfo> {
+        self.session().unwrap().outgoing_stream_manager.send_reader(reader, options).await
+    }
+
     /// Send an in-memory blob of bytes to participants in the room.
     ///
     /// This method sends a provided byte slice as a byte stream.
@@ -1088,3 +1707,23 @@ impl LocalParticipant {
         // Local participants don't receive data messages, so this is a no-op
     }
 }
+
+/// Whether the peer connection factory has an encoder for `codec`
+#[cfg(not(target_arch = "wasm32"))]
+fn has_video_encoder(codec: options::VideoCodec) -> bool {
+    use libwebrtc::{
+        native::codec_support::VideoCodecType,
+        peer_connection_factory::native::PeerConnectionFactoryExt,
+    };
+
+    use crate::rtc_engine::lk_runtime::LkRuntime;
+
+    let codec = match codec {
+        options::VideoCodec::VP8 => VideoCodecType::VP8,
+        options::VideoCodec::VP9 => VideoCodecType::VP9,
+        options::VideoCodec::AV1 => VideoCodecType::AV1,
+        options::VideoCodec::H264 => VideoCodecType::H264,
+        options::VideoCodec::H265 => VideoCodecType::H265,
+    };
+    LkRuntime::instance().pc_factory().has_video_encoder(codec)
+}
@@ -28,6 +28,7 @@ use crate::room::utils;
 pub use local_participant::*;
 pub use remote_participant::*;
 pub use rpc::*;
+pub(crate) use rpc::{RPC_REQUEST_TOPIC, RPC_RESPONSE_TOPIC};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ConnectionQuality {
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::room::participant::ParticipantIdentity;
+use crate::room::participant::{ParticipantIdentity, ParticipantKind};
 use livekit_protocol::RpcError as RpcError_Proto;
-use std::{error::Error, fmt::Display, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap, error::Error, fmt::Display, future::Future, pin::Pin, sync::Arc,
+    time::Duration,
+};
+use tokio::sync::watch;
 
 /// Parameters for performing an RPC call
 #[derive(Debug, Clone)]
@@ -23,6 +28,8 @@ pub struct PerformRpcData {
     pub method: String,
     pub payload: String,
     pub response_timeout: Duration,
+    /// Cancels the call when triggered, notifying the destination participant.
+    pub cancellation_token: Option<RpcCancellationToken>,
 }
 
 impl Default for PerformRpcData {
@@ -32,6 +39,56 @@ impl Default for PerformRpcData {
             method: Default::default(),
             payload: Default::default(),
             response_timeout: Duration::from_secs(15),
+            cancellation_token: None,
+        }
+    }
+}
+
+/// Selects the remote participants targeted by
+/// [`LocalParticipant::perform_rpc_matching`](super::LocalParticipant::perform_rpc_matching).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcTargets {
+    /// Participants whose attribute `key` is set to `value`.
+    Attribute { key: String, value: String },
+    /// Participants of the given kind.
+    Kind(ParticipantKind),
+}
+
+impl RpcTargets {
+    pub(crate) fn matches(
+        &self,
+        attributes: &HashMap<String, String>,
+        kind: ParticipantKind,
+    ) -> bool {
+        match self {
+            Self::Attribute { key, value } => attributes.get(key) == Some(value),
+            Self::Kind(target_kind) => *target_kind == kind,
+        }
+    }
+}
+
+/// Parameters for performing an RPC call with a typed request
+///
+/// The request is serialized to JSON before being sent, and the response is deserialized
+/// from JSON on return.
+#[derive(Debug, Clone)]
+pub struct PerformTypedRpcData<Req> {
+    pub destination_identity: String,
+    pub method: String,
+    pub request: Req,
+    pub response_timeout: Duration,
+    /// Cancels the call when triggered, notifying the destination participant.
+    pub cancellation_token: Option<RpcCancellationToken>,
+}
+
+impl<Req> PerformTypedRpcData<Req> {
+    pub fn new(destination_identity: String, method: String, request: Req) -> Self {
+        Self {
+            destination_identity,
+            method,
+            request,
+            response_timeout: PerformRpcData::default().response_timeout,
+            cancellation_token: None,
         }
     }
 }
@@ -40,15 +97,106 @@ impl Default for PerformRpcData {
 ///
 /// Attributes:
 ///     request_id (String): The unique request ID. Will match at both sides of the call, useful for debugging or logging.
+///     method (String): The name of the invoked method.
 ///     caller_identity (ParticipantIdentity): The unique participant identity of the caller.
+///     caller_attributes (HashMap<String, String>): The caller's attributes at the time of the invocation.
 ///     payload (String): The payload of the request. User-definable format, typically JSON.
 ///     response_timeout (Duration): The maximum time the caller will wait for a response.
+///     cancellation_token (RpcCancellationToken): Triggered when the caller cancels the call,
+///         disconnects or stops waiting for a response.
 #[derive(Debug, Clone)]
 pub struct RpcInvocationData {
     pub request_id: String,
+    pub method: String,
     pub caller_identity: ParticipantIdentity,
+    pub caller_attributes: HashMap<String, String>,
     pub payload: String,
     pub response_timeout: Duration,
+    pub cancellation_token: RpcCancellationToken,
+}
+
+/// Future resolving to the response payload of an RPC call.
+pub type RpcFuture = Pin<Box<dyn Future<Output = Result<String, RpcError>> + Send>>;
+
+/// The remainder of an interceptor chain, ending with the method handler for incoming
+/// invocations or with sending the request for outgoing calls.
+pub struct RpcNext<T> {
+    next: Arc<dyn Fn(T) -> RpcFuture + Send + Sync>,
+}
+
+impl<T> RpcNext<T> {
+    pub(crate) fn new(next: impl Fn(T) -> RpcFuture + Send + Sync + 'static) -> Self {
+        Self { next: Arc::new(next) }
+    }
+
+    /// Passes the (possibly modified) call on to the rest of the chain.
+    pub fn run(self, data: T) -> RpcFuture {
+        (self.next)(data)
+    }
+}
+
+impl<T> Clone for RpcNext<T> {
+    fn clone(&self) -> Self {
+        Self { next: self.next.clone() }
+    }
+}
+
+/// Intercepts RPC calls made and received by the local participant.
+///
+/// Interceptors run in the order they were added, before the method handler is looked up
+/// for incoming invocations and before the request is sent for outgoing calls. An interceptor
+/// can reject a call by returning an [`RpcError`] without running `next`, rewrite the data
+/// passed to `next` or the response it returns, and measure how long `next` takes.
+///
+/// Both methods pass the call through unchanged by default.
+pub trait RpcInterceptor: Send + Sync {
+    fn intercept_incoming(
+        &self,
+        data: RpcInvocationData,
+        next: RpcNext<RpcInvocationData>,
+    ) -> RpcFuture {
+        next.run(data)
+    }
+
+    fn intercept_outgoing(&self, data: PerformRpcData, next: RpcNext<PerformRpcData>) -> RpcFuture {
+        next.run(data)
+    }
+}
+
+/// Signal used to cancel an outgoing RPC call, or to observe the cancellation of
+/// an incoming invocation.
+///
+/// Clones share the same cancellation state.
+#[derive(Debug, Clone)]
+pub struct RpcCancellationToken {
+    cancelled_tx: Arc<watch::Sender<bool>>,
+}
+
+impl RpcCancellationToken {
+    pub fn new() -> Self {
+        Self { cancelled_tx: Arc::new(watch::channel(false).0) }
+    }
+
+    /// Triggers the cancellation. Subsequent calls have no effect.
+    pub fn cancel(&self) {
+        self.cancelled_tx.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled_tx.borrow()
+    }
+
+    /// Completes once the cancellation has been triggered.
+    pub async fn cancelled(&self) {
+        let mut cancelled_rx = self.cancelled_tx.subscribe();
+        let _ = cancelled_rx.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+impl Default for RpcCancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Specialized error handling for RPC methods.
@@ -109,12 +257,15 @@ pub enum RpcErrorCode {
     RecipientDisconnected = 1503,
     ResponsePayloadTooLarge = 1504,
     SendFailed = 1505,
+    InvalidResponsePayload = 1506,
+    Cancelled = 1507,
 
     UnsupportedMethod = 1400,
     RecipientNotFound = 1401,
     RequestPayloadTooLarge = 1402,
     UnsupportedServer = 1403,
     UnsupportedVersion = 1404,
+    InvalidRequestPayload = 1405,
 }
 
 impl RpcErrorCode {
@@ -126,12 +277,15 @@ impl RpcErrorCode {
             Self::RecipientDisconnected => "Recipient disconnected",
             Self::ResponsePayloadTooLarge => "Response payload too large",
             Self::SendFailed => "Failed to send",
+            Self::InvalidResponsePayload => "Response payload could not be deserialized",
+            Self::Cancelled => "Request cancelled",
 
             Self::UnsupportedMethod => "Method not supported at destination",
             Self::RecipientNotFound => "Recipient not found",
             Self::RequestPayloadTooLarge => "Request payload too large",
             Self::UnsupportedServer => "RPC not supported by server",
             Self::UnsupportedVersion => "Unsupported RPC version",
+            Self::InvalidRequestPayload => "Request payload could not be deserialized",
         }
     }
 }
@@ -143,9 +297,52 @@ impl RpcError {
     }
 }
 
+/// Reserved method used to discover the RPC methods registered by a participant.
+///
+/// Only answered when discovery has been enabled with
+/// [`LocalParticipant::set_rpc_discovery_enabled`](super::LocalParticipant::set_rpc_discovery_enabled).
+pub const RPC_DISCOVERY_METHOD: &str = "lk.rpc.list_methods";
+
+/// Reserved method used to notify the destination participant that a call was cancelled.
+///
+/// The payload is the ID of the cancelled request.
+pub(crate) const RPC_CANCEL_METHOD: &str = "lk.rpc.cancel";
+
+/// Description of an RPC method, as returned by the discovery method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcMethodInfo {
+    pub name: String,
+    /// Whether the method was registered with a typed handler, expecting a JSON request and
+    /// answering with a JSON response.
+    #[serde(default)]
+    pub typed: bool,
+}
+
 /// Maximum payload size in bytes
+///
+/// Larger payloads are sent over a data stream when the remote participant supports it.
 pub const MAX_PAYLOAD_BYTES: usize = 15360; // 15 KB
 
+/// RPC version where payloads are always sent inline, understood by all participants.
+pub(crate) const RPC_VERSION_INLINE: u32 = 1;
+
+/// RPC version where the caller accepts a response larger than [`MAX_PAYLOAD_BYTES`]
+/// over a data stream. The request payload is sent inline.
+pub(crate) const RPC_VERSION_STREAM_RESPONSE: u32 = 2;
+
+/// Like [`RPC_VERSION_STREAM_RESPONSE`], with the request payload sent over a data stream
+/// rather than inline.
+pub(crate) const RPC_VERSION_STREAM_REQUEST: u32 = 3;
+
+/// Topic of the text streams carrying oversized request payloads.
+pub(crate) const RPC_REQUEST_TOPIC: &str = "lk.rpc_request";
+
+/// Topic of the text streams carrying oversized response payloads.
+pub(crate) const RPC_RESPONSE_TOPIC: &str = "lk.rpc_response";
+
+/// Stream attribute linking a payload stream to its RPC request.
+pub(crate) const RPC_REQUEST_ID_ATTR: &str = "lk.rpc_request_id";
+
 /// Calculate the byte length of a string
 pub(crate) fn byte_length(s: &str) -> usize {
     s.as_bytes().len()
@@ -49,6 +49,11 @@ impl LocalVideoTrack {
         }
     }
 
+    /// Creates a track from a video source.
+    ///
+    /// Tracks of an [`RtcVideoSource::Encoded`] source must be published with
+    /// `simulcast: false` and the `video_codec` of the source.
+    ///
     pub fn create_video_track(name: &str, source: RtcVideoSource) -> LocalVideoTrack {
         let rtc_track = match source.clone() {
             #[cfg(not(target_arch = "wasm32"))]
@@ -58,6 +63,14 @@ impl LocalVideoTrack {
                     .pc_factory()
                     .create_video_track(&libwebrtc::native::create_random_uuid(), native_source)
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            RtcVideoSource::Encoded(encoded_source) => {
+                use libwebrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
+                LkRuntime::instance().pc_factory().create_encoded_video_track(
+                    &libwebrtc::native::create_random_uuid(),
+                    encoded_source,
+                )
+            }
             _ => panic!("unsupported video source"),
         };
 
@@ -90,6 +90,11 @@ impl RemoteVideoTrack {
         unreachable!();
     }
 
+    /// Receiver of the track, e.g. to record the encoded frames with a `NativeEncodedVideoStream`
+    pub fn receiver(&self) -> Option<RtpReceiver> {
+        self.transceiver().map(|transceiver| transceiver.receiver())
+    }
+
     pub fn is_remote(&self) -> bool {
         true
     }
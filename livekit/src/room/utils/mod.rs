@@ -18,6 +18,7 @@ use livekit_protocol as proto;
 
 use crate::participant::ParticipantKindDetail;
 
+pub(crate) mod pending_bytes;
 pub mod take_cell;
 pub(crate) mod ttl_map;
 pub(crate) mod tx_queue;
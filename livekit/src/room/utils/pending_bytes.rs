@@ -0,0 +1,111 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use tokio::sync::watch;
+
+/// Number of bytes accepted for sending that have not been sent yet, used to make
+/// writers wait when too many bytes are pending.
+#[derive(Debug)]
+pub struct PendingBytes {
+    amount: watch::Sender<u64>,
+}
+
+impl PendingBytes {
+    pub fn new() -> Self {
+        Self { amount: watch::channel(0).0 }
+    }
+
+    pub fn get(&self) -> u64 {
+        *self.amount.borrow()
+    }
+
+    /// Counts bytes that were accepted for sending.
+    pub fn add(&self, bytes: u64) {
+        if bytes > 0 {
+            self.amount.send_modify(|amount| *amount += bytes);
+        }
+    }
+
+    /// Removes bytes that were sent, or dropped.
+    pub fn sub(&self, bytes: u64) {
+        if bytes > 0 {
+            self.amount.send_modify(|amount| *amount = amount.saturating_sub(bytes));
+        }
+    }
+
+    /// Forgets every pending byte, releasing all the waiting writers.
+    pub fn reset(&self) {
+        self.amount.send_replace(0);
+    }
+
+    /// If more than `high_watermark` bytes are pending, waits until at most `low_watermark`
+    /// bytes are.
+    pub async fn wait_low(&self, high_watermark: u64, low_watermark: u64) {
+        let mut amount_rx = self.amount.subscribe();
+        if *amount_rx.borrow_and_update() <= high_watermark {
+            return;
+        }
+        // The sender lives as long as self, the wait can't fail
+        let _ = amount_rx.wait_for(|amount| *amount <= low_watermark).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::PendingBytes;
+
+    #[test]
+    fn test_add_sub() {
+        let pending = PendingBytes::new();
+        pending.add(10);
+        pending.add(5);
+        assert_eq!(pending.get(), 15);
+        pending.sub(12);
+        assert_eq!(pending.get(), 3);
+        pending.sub(10);
+        assert_eq!(pending.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_writer_blocks_at_high_watermark() {
+        let pending = PendingBytes::new();
+
+        // Below the high watermark, writers don't wait
+        pending.add(100);
+        pending.wait_low(100, 20).await;
+
+        pending.add(1);
+        let mut wait = Box::pin(pending.wait_low(100, 20));
+        assert!(tokio::time::timeout(Duration::from_millis(10), &mut wait).await.is_err());
+
+        // Dropping under the high watermark isn't enough, the low watermark must be reached
+        pending.sub(50);
+        assert!(tokio::time::timeout(Duration::from_millis(10), &mut wait).await.is_err());
+
+        pending.sub(31);
+        tokio::time::timeout(Duration::from_millis(10), wait).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reset_releases_writers() {
+        let pending = PendingBytes::new();
+        pending.add(200);
+        let mut wait = Box::pin(pending.wait_low(100, 20));
+        assert!(tokio::time::timeout(Duration::from_millis(10), &mut wait).await.is_err());
+        pending.reset();
+        tokio::time::timeout(Duration::from_millis(10), wait).await.unwrap();
+    }
+}
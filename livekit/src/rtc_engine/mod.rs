@@ -263,6 +263,30 @@ impl RtcEngine {
         session.publish_data(data, kind, is_raw_packet).await
     }
 
+    /// Like [`Self::publish_data`], but only waits for the packet to be enqueued.
+    pub async fn enqueue_data(
+        &self,
+        data: proto::DataPacket,
+        kind: DataPacketKind,
+        is_raw_packet: bool,
+    ) -> EngineResult<oneshot::Receiver<EngineResult<()>>> {
+        let (session, _r_lock) = {
+            let (handle, _r_lock) = self.inner.wait_reconnection().await?;
+            (handle.session.clone(), _r_lock)
+        };
+        session.enqueue_data(data, kind, is_raw_packet).await
+    }
+
+    pub async fn wait_reliable_buffered_amount_low(&self, high_watermark: u64) -> EngineResult<()> {
+        // Don't hold the reconnection lock while waiting, the data channel may be stalled
+        let session = {
+            let (handle, _r_lock) = self.inner.wait_reconnection().await?;
+            handle.session.clone()
+        };
+        session.wait_reliable_buffered_amount_low(high_watermark).await;
+        Ok(())
+    }
+
     pub async fn simulate_scenario(&self, scenario: SimulateScenario) -> EngineResult<()> {
         let (session, _r_lock) = {
             let (handle, _r_lock) = self.inner.wait_reconnection().await?;
@@ -40,6 +40,7 @@ use super::{rtc_events, EngineError, EngineOptions, EngineResult, SimulateScenar
 use crate::{
     id::ParticipantIdentity,
     utils::{
+        pending_bytes::PendingBytes,
         ttl_map::TtlMap,
         tx_queue::{TxQueue, TxQueueItem},
     },
@@ -223,6 +224,10 @@ struct PublishPacketRequest {
     /// Unencoded data packet.
     packet: proto::DataPacket,
 
+    /// Encoded size counted as pending when the request was enqueued, before the
+    /// sequence number was assigned.
+    pending_size: u64,
+
     /// Notifies the caller once the request has been fulfilled.
     completion_tx: oneshot::Sender<Result<(), EngineError>>,
 }
@@ -345,6 +350,9 @@ struct SessionInner {
     lossy_dc_buffered_amount_low_threshold: AtomicU64,
     reliable_dc: DataChannel,
     reliable_dc_buffered_amount_low_threshold: AtomicU64,
+    /// Bytes accepted for the reliable data channel that have not been sent yet
+    /// (queued in the data channel task or buffered by the data channel).
+    reliable_dc_pending: PendingBytes,
 
     /// Next sequence number for reliable packets.
     next_packet_sequence: AtomicU32,
@@ -484,6 +492,7 @@ impl RtcSession {
             reliable_dc_buffered_amount_low_threshold: AtomicU64::new(
                 INITIAL_BUFFERED_AMOUNT_LOW_THRESHOLD,
             ),
+            reliable_dc_pending: PendingBytes::new(),
             next_packet_sequence: 1.into(),
             packet_rx_state: Mutex::new(TtlMap::new(RELIABLE_RECEIVED_STATE_TTL)),
             participant_info,
@@ -565,6 +574,26 @@ impl RtcSession {
         self.inner.publish_data(data, kind, is_raw_packet).await
     }
 
+    /// Enqueues a data packet without waiting for it to be handed to the data channel.
+    ///
+    /// The returned receiver resolves once the packet has been sent (or failed to send).
+    ///
+    pub async fn enqueue_data(
+        &self,
+        data: proto::DataPacket,
+        kind: DataPacketKind,
+        is_raw_packet: bool,
+    ) -> Result<oneshot::Receiver<Result<(), EngineError>>, EngineError> {
+        self.inner.enqueue_data(data, kind, is_raw_packet).await
+    }
+
+    /// If more than `high_watermark` bytes are pending on the reliable data channel, waits
+    /// until the pending amount drops to the channel's buffered amount low threshold.
+    pub async fn wait_reliable_buffered_amount_low(&self, high_watermark: u64) {
+        let threshold = self.data_channel_buffered_amount_low_threshold(DataPacketKind::Reliable);
+        self.inner.reliable_dc_pending.wait_low(high_watermark, threshold).await;
+    }
+
     pub async fn restart(&self) -> EngineResult<proto::ReconnectResponse> {
         self.inner.restart().await
     }
@@ -764,15 +793,25 @@ impl SessionInner {
                             if event.kind == DataPacketKind::Reliable {
                                 request.packet.sequence = self.next_packet_sequence.fetch_add(1, Ordering::Relaxed);
                             }
+                            let encoded_packet: EncodedPacket = request.packet.into();
+                            let encoded_size = encoded_packet.data.len() as u64;
+                            if event.kind == DataPacketKind::Reliable {
+                                // The sequence number may have changed the encoded size
+                                self.reliable_dc_pending.add(encoded_size.saturating_sub(request.pending_size));
+                                self.reliable_dc_pending.sub(request.pending_size.saturating_sub(encoded_size));
+                            }
                             let ev = DataChannelEvent {
                                 kind: event.kind,
                                 detail: DataChannelEventDetail::PublishData(PublishDataRequest {
-                                    encoded_packet: request.packet.into(),
+                                    encoded_packet,
                                     completion_tx: request.completion_tx.into()
                                 })
                             };
                             if let Err(err) = self.dc_emitter.send(ev) {
-                                log::error!("Failed to enqueue send data request: {}", err)
+                                log::error!("Failed to enqueue send data request: {}", err);
+                                if event.kind == DataPacketKind::Reliable {
+                                    self.reliable_dc_pending.sub(encoded_size);
+                                }
                             }
                         }
                         DataChannelEventDetail::PublishData(request) => {
@@ -809,6 +848,7 @@ impl SessionInner {
                                     } else {
                                         reliable_buffered_amount -= sent;
                                     }
+                                    self.reliable_dc_pending.sub(sent);
                                     let threshold = self.reliable_dc_buffered_amount_low_threshold.load(Ordering::Relaxed);
                                     self._send_until_threshold(DataPacketKind::Reliable, threshold, &mut reliable_buffered_amount, &mut reliable_queue, &mut retry_queue);
                                     retry_queue.trim(sent as usize);
@@ -828,6 +868,10 @@ impl SessionInner {
             }
         }
 
+        // Stop accepting requests before releasing writers waiting for the reliable data
+        // channel to drain, so that nothing gets counted after the reset
+        drop(dc_events);
+        self.reliable_dc_pending.reset();
         log::debug!("closing data_channel_task");
     }
 
@@ -851,6 +895,10 @@ impl SessionInner {
                 .map_err(|err| {
                     EngineError::Internal(format!("failed to send data packet: {:?}", err).into())
                 });
+            if kind == DataPacketKind::Reliable && result.is_err() {
+                // Never handed to the data channel, no buffered amount change will follow
+                self.reliable_dc_pending.sub(request.encoded_packet.data.len() as u64);
+            }
             if let Some(completion_tx) = request.completion_tx {
                 _ = completion_tx.send(result);
             }
@@ -880,6 +928,8 @@ impl SessionInner {
             if encoded_packet.sequence <= last_sequence {
                 continue;
             };
+            let encoded_size = encoded_packet.data.len() as u64;
+            self.reliable_dc_pending.add(encoded_size);
             let ev = DataChannelEvent {
                 kind: DataPacketKind::Reliable,
                 detail: DataChannelEventDetail::PublishData(PublishDataRequest {
@@ -889,6 +939,7 @@ impl SessionInner {
             };
             if let Err(err) = self.dc_emitter.send(ev) {
                 log::error!("Failed to enqueue data for retry: {}", err);
+                self.reliable_dc_pending.sub(encoded_size);
             }
         }
     }
@@ -1495,10 +1546,22 @@ impl SessionInner {
 
     async fn publish_data(
         self: &Arc<Self>,
-        mut packet: proto::DataPacket,
+        packet: proto::DataPacket,
         kind: DataPacketKind,
         is_raw_packet: bool,
     ) -> Result<(), EngineError> {
+        let completion_rx = self.enqueue_data(packet, kind, is_raw_packet).await?;
+        completion_rx.await.map_err(|e| {
+            EngineError::Internal(format!("failed to receive data from dc_task: {:?}", e).into())
+        })?
+    }
+
+    async fn enqueue_data(
+        self: &Arc<Self>,
+        mut packet: proto::DataPacket,
+        kind: DataPacketKind,
+        is_raw_packet: bool,
+    ) -> Result<oneshot::Receiver<Result<(), EngineError>>, EngineError> {
         self.ensure_publisher_connected(kind).await?;
 
         // Populate local participant info fields
@@ -1558,22 +1621,30 @@ impl SessionInner {
             }
         }
 
+        // Counted right away so that writers waiting for the reliable data channel to drain
+        // see the bytes still queued for the data channel task
+        let pending_size = match kind {
+            DataPacketKind::Reliable => packet.encoded_len() as u64,
+            DataPacketKind::Lossy => 0,
+        };
+        self.reliable_dc_pending.add(pending_size);
+
         let (completion_tx, completion_rx) = oneshot::channel();
         let ev = DataChannelEvent {
             kind,
             detail: DataChannelEventDetail::PublishPacket(PublishPacketRequest {
                 packet,
+                pending_size,
                 completion_tx,
             }),
         };
         if let Err(err) = self.dc_emitter.send(ev) {
+            self.reliable_dc_pending.sub(pending_size);
             return Err(EngineError::Internal(
                 format!("Failed to enqueue publish packet request: {:?}", err).into(),
             ));
         };
-        completion_rx.await.map_err(|e| {
-            EngineError::Internal(format!("failed to receive data from dc_task: {:?}", e).into())
-        })?
+        Ok(completion_rx)
     }
 
     /// This reconnection if more seemless compared to the full reconnection implemented in
@@ -39,15 +39,21 @@ async fn test_data_channel_encryption() -> Result<()> {
         KeyProvider::with_shared_key(KeyProviderOptions::default(), "password".as_bytes().to_vec());
 
     let mut options1 = RoomOptions::default();
-    options1.encryption =
-        Some(E2eeOptions { key_provider: key_provider1, encryption_type: EncryptionType::Gcm });
+    options1.encryption = Some(E2eeOptions {
+        key_provider: key_provider1,
+        encryption_type: EncryptionType::Gcm,
+        frame_transformer: None,
+    });
 
     let key_provider2 =
         KeyProvider::with_shared_key(KeyProviderOptions::default(), "password".as_bytes().to_vec());
 
     let mut options2 = RoomOptions::default();
-    options2.encryption =
-        Some(E2eeOptions { key_provider: key_provider2, encryption_type: EncryptionType::Gcm });
+    options2.encryption = Some(E2eeOptions {
+        key_provider: key_provider2,
+        encryption_type: EncryptionType::Gcm,
+        frame_transformer: None,
+    });
 
     let mut rooms = test_rooms_with_options([options1, options2]).await?;
 
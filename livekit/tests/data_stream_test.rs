@@ -19,7 +19,7 @@ use {
     chrono::{TimeDelta, Utc},
     livekit::{RoomEvent, StreamByteOptions, StreamReader, StreamTextOptions},
     std::time::Duration,
-    tokio::{time::timeout, try_join},
+    tokio::{io::AsyncReadExt, time::timeout, try_join},
 };
 
 mod common;
@@ -114,3 +114,73 @@ async fn test_send_text() -> Result<()> {
     timeout(Duration::from_secs(5), async { try_join!(send_text, receive_text) }).await??;
     Ok(())
 }
+
+#[cfg(feature = "__lk-e2e-test")]
+#[tokio::test]
+async fn test_send_reader() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (sending_room, _) = rooms.pop().unwrap();
+    let (_, mut receiving_event_rx) = rooms.pop().unwrap();
+
+    // Spans multiple chunks to exercise partial reads.
+    let bytes_to_send: Vec<u8> = (0..40_000).map(|i| (i % 251) as u8).collect();
+    let expected = bytes_to_send.clone();
+
+    let send_reader = async move {
+        let options = StreamByteOptions { topic: "some-topic".into(), ..Default::default() };
+        let stream_info =
+            sending_room.local_participant().send_reader(bytes_to_send.as_slice(), options).await?;
+        assert!(stream_info.total_length.is_none());
+        Ok(())
+    };
+    let receive_reader = async move {
+        while let Some(event) = receiving_event_rx.recv().await {
+            let RoomEvent::ByteStreamOpened { reader, .. } = event else {
+                continue;
+            };
+            let Some(mut reader) = reader.take() else {
+                return Err(anyhow!("Failed to take reader"));
+            };
+            let mut received = Vec::new();
+            reader.read_to_end(&mut received).await?;
+            assert_eq!(received, expected);
+            break;
+        }
+        Ok(())
+    };
+
+    timeout(Duration::from_secs(5), async { try_join!(send_reader, receive_reader) }).await??;
+    Ok(())
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[tokio::test]
+async fn test_text_stream_handler() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (sending_room, _) = rooms.pop().unwrap();
+    let (receiving_room, _) = rooms.pop().unwrap();
+    let sender_identity = sending_room.local_participant().identity();
+
+    const TEXT_TO_SEND: &str = "some-text";
+
+    let (text_tx, mut text_rx) = tokio::sync::mpsc::unbounded_channel();
+    receiving_room.register_text_stream_handler("some-topic".into(), move |reader, identity| {
+        let text_tx = text_tx.clone();
+        Box::pin(async move {
+            let _ = text_tx.send((reader.read_all().await, identity));
+        })
+    })?;
+    assert!(receiving_room
+        .register_text_stream_handler("some-topic".into(), |_, _| Box::pin(async {}))
+        .is_err());
+
+    let options = StreamTextOptions { topic: "some-topic".into(), ..Default::default() };
+    sending_room.local_participant().send_text(TEXT_TO_SEND, options).await?;
+
+    let (text, identity) = timeout(Duration::from_secs(5), text_rx.recv())
+        .await?
+        .ok_or_else(|| anyhow!("Handler was not invoked"))?;
+    assert_eq!(text?, TEXT_TO_SEND);
+    assert_eq!(identity, sender_identity);
+    Ok(())
+}
@@ -0,0 +1,315 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "__lk-e2e-test")]
+use {
+    anyhow::{anyhow, Ok, Result},
+    common::{test_rooms, test_rooms_with_options},
+    futures_util::StreamExt,
+    libwebrtc::{
+        native::annexb::split_access_units,
+        stats::RtcStats,
+        video_frame::{I420Buffer, VideoFrame, VideoRotation},
+        video_source::{
+            native::{EncodedVideoCodec, EncodedVideoFrame, EncodedVideoSource, NativeVideoSource},
+            RtcVideoSource, VideoResolution,
+        },
+        video_stream::native::{NativeEncodedVideoStream, NativeVideoStream, VideoCodecType},
+    },
+    livekit::{
+        e2ee::{
+            key_provider::{KeyProvider, KeyProviderOptions},
+            EncryptionType,
+        },
+        options::TrackPublishOptions,
+        options::VideoCodec,
+        prelude::*,
+        E2eeOptions,
+    },
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
+    },
+    tokio::time::timeout,
+};
+
+mod common;
+
+/// 3 GOPs of 10 frames, 32x32, with a gray level of 64, 128 and 192 for each GOP.
+#[cfg(feature = "__lk-e2e-test")]
+const H264_FIXTURE: &[u8] = include_bytes!("fixtures/gray_32x32.h264");
+
+/// Publishes the fixture in a loop, restarting from a keyframe when one is requested.
+#[cfg(feature = "__lk-e2e-test")]
+async fn publish_fixture(room: &Room) -> Result<tokio::task::JoinHandle<()>> {
+    let source =
+        EncodedVideoSource::new(VideoResolution { width: 32, height: 32 }, EncodedVideoCodec::H264);
+    let keyframe_requested = Arc::new(AtomicBool::new(false));
+    source.on_keyframe_request(Some(Box::new({
+        let keyframe_requested = keyframe_requested.clone();
+        move || keyframe_requested.store(true, Ordering::Release)
+    })));
+
+    let track =
+        LocalVideoTrack::create_video_track("encoded", RtcVideoSource::Encoded(source.clone()));
+    let options = TrackPublishOptions {
+        video_codec: VideoCodec::H264,
+        simulcast: false,
+        ..Default::default()
+    };
+    room.local_participant().publish_track(LocalTrack::Video(track), options).await?;
+
+    let access_units = split_access_units(EncodedVideoCodec::H264, H264_FIXTURE);
+    assert_eq!(access_units.len(), 30);
+    assert_eq!(access_units.iter().filter(|au| au.is_keyframe).count(), 3);
+
+    Ok(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(33));
+        let mut index = 0;
+        loop {
+            interval.tick().await;
+            if keyframe_requested.swap(false, Ordering::AcqRel) {
+                // Restart from the next keyframe
+                while !access_units[index % access_units.len()].is_keyframe {
+                    index += 1;
+                }
+            }
+
+            let access_unit = access_units[index % access_units.len()];
+            source.capture_frame(&EncodedVideoFrame {
+                data: access_unit.data,
+                is_keyframe: access_unit.is_keyframe,
+                timestamp_us: 0,
+                width: 32,
+                height: 32,
+            });
+            index += 1;
+        }
+    }))
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+async fn subscribed_video_track(
+    events: &mut tokio::sync::mpsc::UnboundedReceiver<RoomEvent>,
+) -> Result<RemoteVideoTrack> {
+    loop {
+        let Some(event) = events.recv().await else { Err(anyhow!("Never received track"))? };
+        if let RoomEvent::TrackSubscribed { track, .. } = event {
+            let RemoteTrack::Video(track) = track else { Err(anyhow!("Expected video track"))? };
+            return Ok(track);
+        }
+    }
+}
+
+/// Publishes a pre-encoded H.264 file and checks the subscriber decodes it.
+///
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+async fn test_encoded_video() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (pub_room, _) = rooms.pop().unwrap();
+    let (_, mut sub_room_events) = rooms.pop().unwrap();
+
+    // Simulcast isn't supported with encoded frames
+    let source =
+        EncodedVideoSource::new(VideoResolution { width: 32, height: 32 }, EncodedVideoCodec::H264);
+    let track = LocalVideoTrack::create_video_track("invalid", RtcVideoSource::Encoded(source));
+    let invalid_options =
+        TrackPublishOptions { video_codec: VideoCodec::H264, ..Default::default() };
+    assert!(matches!(
+        pub_room.local_participant().publish_track(LocalTrack::Video(track), invalid_options).await,
+        Err(RoomError::EncodedSourceMismatch(VideoCodec::H264))
+    ));
+
+    let publish = publish_fixture(&pub_room).await?;
+
+    let receive = async move {
+        let track = subscribed_video_track(&mut sub_room_events).await?;
+        let mut stream = NativeVideoStream::new(track.rtc_track());
+        let mut frames = 0;
+        while let Some(frame) = stream.next().await {
+            let buffer = frame.buffer.to_i420();
+            assert_eq!((buffer.width(), buffer.height()), (32, 32));
+
+            let (y, _, _) = buffer.data();
+            let luma = y[0];
+            assert!(
+                [64, 128, 192].iter().any(|level: &u8| level.abs_diff(luma) <= 2),
+                "unexpected luma {}",
+                luma
+            );
+
+            frames += 1;
+            if frames >= 30 {
+                break;
+            }
+        }
+        Ok(())
+    };
+    let result = timeout(Duration::from_secs(15), receive).await;
+    publish.abort();
+    result??;
+    Ok(())
+}
+
+/// Records the encoded frames of a remote track without decoding them.
+///
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+async fn test_encoded_video_stream() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (pub_room, _) = rooms.pop().unwrap();
+    let (_, mut sub_room_events) = rooms.pop().unwrap();
+
+    let publish = publish_fixture(&pub_room).await?;
+
+    let receive = async move {
+        let track = subscribed_video_track(&mut sub_room_events).await?;
+        let receiver = track.receiver().ok_or(anyhow!("Expected a receiver"))?;
+
+        let mut stream = NativeEncodedVideoStream::new(receiver.clone())?;
+        assert!(NativeEncodedVideoStream::new(receiver.clone()).is_err());
+        stream.request_keyframe();
+
+        // Wait for the requested keyframe, then check the following frames
+        let mut last_timestamp = None;
+        let mut frames = 0;
+        while let Some(frame) = stream.next().await {
+            if last_timestamp.is_none() && !frame.is_keyframe {
+                continue;
+            }
+
+            assert_eq!(frame.codec, VideoCodecType::H264);
+            assert!(frame.data.starts_with(&[0, 0, 0, 1]) || frame.data.starts_with(&[0, 0, 1]));
+            if frame.is_keyframe {
+                assert_eq!((frame.width, frame.height), (32, 32));
+            }
+            if let Some(last_timestamp) = last_timestamp {
+                assert!(frame.rtp_timestamp.wrapping_sub(last_timestamp) < u32::MAX / 2);
+            }
+            last_timestamp = Some(frame.rtp_timestamp);
+
+            frames += 1;
+            if frames >= 30 {
+                break;
+            }
+        }
+        assert_eq!(frames, 30);
+
+        // The sink stays attached to the receiver, a new stream observes it once the first closes
+        stream.close();
+        drop(stream);
+        let mut stream = NativeEncodedVideoStream::new(receiver)?;
+        assert!(stream.next().await.is_some());
+        Ok(())
+    };
+    let result = timeout(Duration::from_secs(15), receive).await;
+    publish.abort();
+    result??;
+    Ok(())
+}
+
+/// The encoded frames of an encrypted track can't be observed: the stream would replace the
+/// frame cryptor of the receiver.
+///
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+async fn test_encoded_video_stream_with_e2ee() -> Result<()> {
+    let options = || {
+        let mut options = RoomOptions::default();
+        options.encryption = Some(E2eeOptions {
+            key_provider: KeyProvider::with_shared_key(
+                KeyProviderOptions::default(),
+                "password".as_bytes().to_vec(),
+            ),
+            encryption_type: EncryptionType::Gcm,
+            frame_transformer: None,
+        });
+        options
+    };
+    let mut rooms = test_rooms_with_options([options(), options()]).await?;
+    let (pub_room, _) = rooms.pop().unwrap();
+    let (sub_room, mut sub_room_events) = rooms.pop().unwrap();
+    pub_room.e2ee_manager().set_enabled(true);
+    sub_room.e2ee_manager().set_enabled(true);
+
+    let publish = publish_fixture(&pub_room).await?;
+
+    let receive = async move {
+        let track = subscribed_video_track(&mut sub_room_events).await?;
+        let receiver = track.receiver().ok_or(anyhow!("Expected a receiver"))?;
+        assert!(NativeEncodedVideoStream::new(receiver).is_err());
+        Ok(())
+    };
+    let result = timeout(Duration::from_secs(15), receive).await;
+    publish.abort();
+    result??;
+    Ok(())
+}
+
+/// H.264 tracks of other sources are still encoded, and reported, by the real encoder.
+///
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+async fn test_native_video_keeps_encoder() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (pub_room, _) = rooms.pop().unwrap();
+    let (_, mut sub_room_events) = rooms.pop().unwrap();
+
+    let source = NativeVideoSource::new(VideoResolution { width: 64, height: 64 });
+    let track =
+        LocalVideoTrack::create_video_track("native", RtcVideoSource::Native(source.clone()));
+    let options = TrackPublishOptions {
+        video_codec: VideoCodec::H264,
+        simulcast: false,
+        ..Default::default()
+    };
+    pub_room.local_participant().publish_track(LocalTrack::Video(track.clone()), options).await?;
+
+    let capture = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(33));
+        let mut frame = VideoFrame {
+            rotation: VideoRotation::VideoRotation0,
+            buffer: I420Buffer::new(64, 64),
+            timestamp_us: 0,
+        };
+        loop {
+            interval.tick().await;
+            source.capture_frame(&frame);
+            frame.timestamp_us += 33_000;
+        }
+    });
+
+    let check = async move {
+        subscribed_video_track(&mut sub_room_events).await?;
+        loop {
+            let encoder = track.get_stats().await?.into_iter().find_map(|stats| match stats {
+                RtcStats::OutboundRtp(stats) => Some(stats.outbound.encoder_implementation),
+                _ => None,
+            });
+            if let Some(encoder) = encoder.filter(|encoder| !encoder.is_empty()) {
+                assert!(!encoder.contains("Passthrough"), "unexpected encoder {}", encoder);
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+    let result = timeout(Duration::from_secs(15), check).await;
+    capture.abort();
+    result??;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "__lk-e2e-test")]
+use {
+    anyhow::{anyhow, Ok, Result},
+    common::{
+        audio::{ChannelIterExt, FreqAnalyzer, SineParameters, SineTrack},
+        test_rooms_with_options,
+    },
+    futures_util::StreamExt,
+    libwebrtc::{
+        audio_source::native::NativeAudioSource,
+        audio_stream::native::NativeAudioStream,
+        native::frame_transformer::{FrameDirection, FrameMetadata, FrameTransformer},
+        prelude::{AudioSourceOptions, RtcAudioSource},
+    },
+    livekit::{
+        e2ee::{
+            key_provider::{KeyProvider, KeyProviderOptions},
+            EncryptionType,
+        },
+        options::TrackPublishOptions,
+        prelude::*,
+        E2eeOptions, RoomOptions,
+    },
+    std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    },
+    tokio::time::timeout,
+};
+
+mod common;
+
+/// XORs every byte of the payload, counting the frames of each direction.
+#[cfg(feature = "__lk-e2e-test")]
+#[derive(Default)]
+struct XorTransformer {
+    sent: AtomicUsize,
+    received: AtomicUsize,
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+impl FrameTransformer for XorTransformer {
+    fn transform(&self, _: &str, metadata: &FrameMetadata, payload: &[u8]) -> Option<Vec<u8>> {
+        match metadata.direction {
+            FrameDirection::Sender => self.sent.fetch_add(1, Ordering::Relaxed),
+            FrameDirection::Receiver => self.received.fetch_add(1, Ordering::Relaxed),
+            FrameDirection::Unknown => return None,
+        };
+        Some(payload.iter().map(|byte| byte ^ 0x5a).collect())
+    }
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+fn custom_options(frame_transformer: Option<Arc<dyn FrameTransformer>>) -> RoomOptions {
+    let mut options = RoomOptions::default();
+    options.encryption = Some(E2eeOptions {
+        key_provider: KeyProvider::new(KeyProviderOptions::default()),
+        encryption_type: EncryptionType::Custom,
+        frame_transformer,
+    });
+    options
+}
+
+/// The sine wave is only heard by the subscriber if the transformer undoes on the receiver
+/// what it did on the sender.
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+async fn test_custom_frame_transformer_loopback() -> Result<()> {
+    const SINE_FREQ: f64 = 60.0;
+    const FRAMES_TO_ANALYZE: usize = 100;
+
+    let pub_transformer = Arc::new(XorTransformer::default());
+    let sub_transformer = Arc::new(XorTransformer::default());
+    let mut rooms = test_rooms_with_options([
+        custom_options(Some(pub_transformer.clone())),
+        custom_options(Some(sub_transformer.clone())),
+    ])
+    .await?;
+    let (pub_room, _) = rooms.remove(0);
+    let (sub_room, mut sub_room_events) = rooms.remove(0);
+    pub_room.e2ee_manager().set_enabled(true);
+    sub_room.e2ee_manager().set_enabled(true);
+
+    let sine_params =
+        SineParameters { freq: SINE_FREQ, amplitude: 1.0, sample_rate: 48_000, num_channels: 1 };
+    let mut sine_track = SineTrack::new(Arc::new(pub_room), sine_params);
+    sine_track.publish().await?;
+
+    let analyze_frames = async move {
+        let track: RemoteTrack = loop {
+            let Some(event) = sub_room_events.recv().await else {
+                Err(anyhow!("Never received track"))?
+            };
+            let RoomEvent::TrackSubscribed { track, .. } = event else {
+                continue;
+            };
+            break track;
+        };
+        let RemoteTrack::Audio(track) = track else { Err(anyhow!("Expected audio track"))? };
+        let mut stream = NativeAudioStream::new(track.rtc_track(), 48_000, 1);
+
+        let mut analyzer = FreqAnalyzer::new();
+        for _ in 0..FRAMES_TO_ANALYZE {
+            let frame = stream.next().await.ok_or(anyhow!("Audio stream ended"))?;
+            analyzer.analyze(frame.channel_iter(0));
+        }
+        let detected_freq = analyzer.estimated_freq(48_000);
+        assert!(
+            (detected_freq - SINE_FREQ).abs() < 20.0,
+            "Detected sine frequency not within range: {}Hz",
+            detected_freq
+        );
+        Ok(())
+    };
+    timeout(Duration::from_secs(15), analyze_frames).await??;
+
+    assert!(pub_transformer.sent.load(Ordering::Relaxed) > 0);
+    assert!(sub_transformer.received.load(Ordering::Relaxed) > 0);
+    Ok(())
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+async fn test_custom_encryption_without_transformer() -> Result<()> {
+    let mut rooms = test_rooms_with_options([custom_options(None)]).await?;
+    let (room, _) = rooms.pop().unwrap();
+
+    let source = NativeAudioSource::new(AudioSourceOptions::default(), 48_000, 1, 1000);
+    let track = LocalAudioTrack::create_audio_track("audio", RtcAudioSource::Native(source));
+    let result = room
+        .local_participant()
+        .publish_track(LocalTrack::Audio(track), TrackPublishOptions::default())
+        .await;
+    assert!(result.is_err(), "custom encrypted track published without a transformer");
+    Ok(())
+}
@@ -16,8 +16,14 @@
 use {
     anyhow::{Context, Result},
     common::test_rooms,
-    livekit::prelude::PerformRpcData,
+    livekit::prelude::{
+        ParticipantIdentity, ParticipantKind, PerformRpcData, PerformTypedRpcData,
+        RpcCancellationToken, RpcError, RpcErrorCode, RpcFuture, RpcInterceptor, RpcInvocationData,
+        RpcNext, RpcTargets,
+    },
+    serde::{Deserialize, Serialize},
     std::time::Duration,
+    tokio::{sync::oneshot, time::timeout},
 };
 
 mod common;
@@ -94,3 +100,286 @@ pub async fn test_rpc_unknown_destination() -> Result<()> {
     assert!(result.is_err(), "Expected error");
     Ok(())
 }
+
+#[cfg(feature = "__lk-e2e-test")]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct AddRequest {
+    a: i32,
+    b: i32,
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+pub async fn test_typed_rpc_invocation() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (caller_room, _) = rooms.pop().unwrap();
+    let (callee_room, _) = rooms.pop().unwrap();
+    let callee_identity = callee_room.local_participant().identity();
+
+    const METHOD_NAME: &str = "add";
+
+    callee_room
+        .local_participant()
+        .register_typed_rpc_method(METHOD_NAME.to_string(), |_, request: AddRequest| {
+            Box::pin(async move { Ok(request.a + request.b) })
+        });
+
+    let mut perform_data = PerformTypedRpcData::new(
+        callee_identity.to_string(),
+        METHOD_NAME.to_string(),
+        AddRequest { a: 2, b: 3 },
+    );
+    perform_data.response_timeout = Duration::from_millis(500);
+    let sum: i32 = caller_room
+        .local_participant()
+        .perform_typed_rpc(perform_data)
+        .await
+        .context("Invocation failed")?;
+    assert_eq!(sum, 5, "Unexpected return value");
+
+    // Malformed request payloads are rejected before reaching the handler
+    let perform_data = PerformRpcData {
+        method: METHOD_NAME.to_string(),
+        destination_identity: callee_identity.to_string(),
+        payload: "not-json".to_string(),
+        response_timeout: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let error = caller_room.local_participant().perform_rpc(perform_data).await.unwrap_err();
+    assert_eq!(error.code, RpcErrorCode::InvalidRequestPayload as u32);
+    Ok(())
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+pub async fn test_rpc_discovery() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (caller_room, _) = rooms.pop().unwrap();
+    let (callee_room, _) = rooms.pop().unwrap();
+    let callee_identity = callee_room.local_participant().identity();
+
+    callee_room
+        .local_participant()
+        .register_typed_rpc_method("add".to_string(), |_, request: AddRequest| {
+            Box::pin(async move { Ok(request.a + request.b) })
+        });
+    callee_room
+        .local_participant()
+        .register_rpc_method("echo".to_string(), |data| Box::pin(async move { Ok(data.payload) }));
+
+    // Discovery is disabled by default
+    let result =
+        caller_room.local_participant().list_rpc_methods(callee_identity.to_string()).await;
+    assert!(result.is_err(), "Expected error");
+
+    callee_room.local_participant().set_rpc_discovery_enabled(true);
+    let methods = caller_room
+        .local_participant()
+        .list_rpc_methods(callee_identity.to_string())
+        .await
+        .context("Discovery failed")?;
+    let names: Vec<_> = methods.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["add", "echo"]);
+    assert!(methods[0].typed);
+    assert!(!methods[1].typed);
+    Ok(())
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+pub async fn test_rpc_large_payload() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (caller_room, _) = rooms.pop().unwrap();
+    let (callee_room, _) = rooms.pop().unwrap();
+    let callee_identity = callee_room.local_participant().identity();
+
+    const METHOD_NAME: &str = "test-method";
+    let payload = "a".repeat(4 * livekit::participant::MAX_PAYLOAD_BYTES);
+
+    callee_room.local_participant().register_rpc_method(METHOD_NAME.to_string(), |data| {
+        // Echo caller payload twice, exceeding the limit in both directions
+        Box::pin(async move { Ok(data.payload.repeat(2)) })
+    });
+
+    let perform_data = PerformRpcData {
+        method: METHOD_NAME.to_string(),
+        destination_identity: callee_identity.to_string(),
+        payload: payload.clone(),
+        response_timeout: Duration::from_secs(5),
+        ..Default::default()
+    };
+    let return_payload = caller_room
+        .local_participant()
+        .perform_rpc(perform_data)
+        .await
+        .context("Invocation failed")?;
+    assert_eq!(return_payload, payload.repeat(2), "Unexpected return value");
+    Ok(())
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+pub async fn test_rpc_cancellation() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (caller_room, _) = rooms.pop().unwrap();
+    let (callee_room, _) = rooms.pop().unwrap();
+    let callee_identity = callee_room.local_participant().identity();
+
+    const METHOD_NAME: &str = "long-running-method";
+
+    let (cancelled_tx, cancelled_rx) = oneshot::channel();
+    let cancelled_tx = std::sync::Mutex::new(Some(cancelled_tx));
+    callee_room.local_participant().register_rpc_method(METHOD_NAME.to_string(), move |data| {
+        let cancelled_tx = cancelled_tx.lock().unwrap().take();
+        Box::pin(async move {
+            // Only completes once the caller cancels the call
+            data.cancellation_token.cancelled().await;
+            if let Some(cancelled_tx) = cancelled_tx {
+                let _ = cancelled_tx.send(());
+            }
+            Ok(String::new())
+        })
+    });
+
+    let cancellation_token = RpcCancellationToken::new();
+    let perform_data = PerformRpcData {
+        method: METHOD_NAME.to_string(),
+        destination_identity: callee_identity.to_string(),
+        response_timeout: Duration::from_secs(10),
+        cancellation_token: Some(cancellation_token.clone()),
+        ..Default::default()
+    };
+    let caller = caller_room.local_participant();
+    let call = tokio::spawn(async move { caller.perform_rpc(perform_data).await });
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    cancellation_token.cancel();
+
+    let error = call.await?.unwrap_err();
+    assert_eq!(error.code, RpcErrorCode::Cancelled as u32);
+    timeout(Duration::from_secs(5), cancelled_rx)
+        .await
+        .context("Handler was not cancelled")?
+        .context("Handler dropped")?;
+    Ok(())
+}
+
+/// Rejects incoming calls to a method and upper-cases outgoing payloads.
+#[cfg(feature = "__lk-e2e-test")]
+struct TestInterceptor {
+    blocked_method: &'static str,
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+impl RpcInterceptor for TestInterceptor {
+    fn intercept_incoming(
+        &self,
+        data: RpcInvocationData,
+        next: RpcNext<RpcInvocationData>,
+    ) -> RpcFuture {
+        if data.method == self.blocked_method {
+            return Box::pin(async { Err(RpcError::new(2000, "Forbidden".to_string(), None)) });
+        }
+        next.run(data)
+    }
+
+    fn intercept_outgoing(
+        &self,
+        mut data: PerformRpcData,
+        next: RpcNext<PerformRpcData>,
+    ) -> RpcFuture {
+        data.payload = data.payload.to_uppercase();
+        next.run(data)
+    }
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+pub async fn test_rpc_interceptors() -> Result<()> {
+    let mut rooms = test_rooms(2).await?;
+    let (caller_room, _) = rooms.pop().unwrap();
+    let (callee_room, _) = rooms.pop().unwrap();
+    let callee_identity = callee_room.local_participant().identity();
+
+    for method in ["allowed-method", "blocked-method"] {
+        callee_room.local_participant().register_rpc_method(method.to_string(), |data| {
+            Box::pin(async move { Ok(data.payload) })
+        });
+    }
+    callee_room
+        .local_participant()
+        .add_rpc_interceptor(TestInterceptor { blocked_method: "blocked-method" });
+    caller_room.local_participant().add_rpc_interceptor(TestInterceptor { blocked_method: "" });
+
+    let perform_data = PerformRpcData {
+        method: "allowed-method".to_string(),
+        destination_identity: callee_identity.to_string(),
+        payload: "test-payload".to_string(),
+        response_timeout: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let return_payload = caller_room
+        .local_participant()
+        .perform_rpc(perform_data.clone())
+        .await
+        .context("Invocation failed")?;
+    assert_eq!(return_payload, "TEST-PAYLOAD", "Outgoing payload was not rewritten");
+
+    let perform_data = PerformRpcData { method: "blocked-method".to_string(), ..perform_data };
+    let error = caller_room.local_participant().perform_rpc(perform_data).await.unwrap_err();
+    assert_eq!(error.code, 2000);
+    Ok(())
+}
+
+#[cfg(feature = "__lk-e2e-test")]
+#[test_log::test(tokio::test)]
+pub async fn test_rpc_many() -> Result<()> {
+    let mut rooms = test_rooms(3).await?;
+    let (caller_room, _) = rooms.pop().unwrap();
+
+    const METHOD_NAME: &str = "status";
+
+    let mut callee_identities = Vec::new();
+    for (callee_room, _) in &rooms {
+        let identity = callee_room.local_participant().identity();
+        callee_room.local_participant().register_rpc_method(METHOD_NAME.to_string(), {
+            let identity = identity.clone();
+            move |_| {
+                let identity = identity.clone();
+                Box::pin(async move { Ok(identity.to_string()) })
+            }
+        });
+        callee_identities.push(identity);
+    }
+
+    let unknown_identity = ParticipantIdentity::from("unknown-participant".to_string());
+    let mut identities = callee_identities.clone();
+    identities.push(unknown_identity.clone());
+    let results = caller_room
+        .local_participant()
+        .perform_rpc_many(
+            identities,
+            METHOD_NAME.to_string(),
+            String::new(),
+            Duration::from_millis(1500),
+        )
+        .await;
+    assert_eq!(results.len(), 3);
+    for identity in &callee_identities {
+        assert_eq!(results[identity].as_deref().ok(), Some(identity.as_str()));
+    }
+    assert!(results[&unknown_identity].is_err(), "Expected error");
+
+    let results = caller_room
+        .local_participant()
+        .perform_rpc_matching(
+            RpcTargets::Kind(ParticipantKind::Standard),
+            METHOD_NAME.to_string(),
+            String::new(),
+            Duration::from_millis(1500),
+        )
+        .await;
+    assert_eq!(results.len(), 2);
+    assert!(results.values().all(|result| result.is_ok()));
+    Ok(())
+}
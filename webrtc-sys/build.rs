@@ -35,8 +35,14 @@ fn main() {
         "src/media_stream_track.rs",
         "src/audio_track.rs",
         "src/video_track.rs",
+        "src/encoded_video_source.rs",
+        "src/encoded_video_sink.rs",
+        "src/video_encoder.rs",
+        "src/video_decoder.rs",
+        "src/video_codec_backend.rs",
         "src/data_channel.rs",
         "src/frame_cryptor.rs",
+        "src/frame_transformer.rs",
         "src/jsep.rs",
         "src/candidate.rs",
         "src/rtp_parameters.rs",
@@ -69,6 +75,11 @@ fn main() {
         "src/media_stream_track.cpp",
         "src/audio_track.cpp",
         "src/video_track.cpp",
+        "src/encoded_video_source.cpp",
+        "src/encoded_video_sink.cpp",
+        "src/video_encoder.cpp",
+        "src/video_decoder.cpp",
+        "src/video_codec_backend.cpp",
         "src/data_channel.cpp",
         "src/jsep.cpp",
         "src/candidate.cpp",
@@ -85,6 +96,7 @@ fn main() {
         "src/audio_device.cpp",
         "src/audio_resampler.cpp",
         "src/frame_cryptor.cpp",
+        "src/frame_transformer.cpp",
         "src/global_task_queue.cpp",
         "src/prohibit_libsrtp_initialization.cpp",
         "src/apm.cpp",
@@ -260,10 +272,15 @@ fn main() {
                 }
             }
 
-            // RK3588 MPP hardware video codec (ARM64 only)
-            if arm {
-                println!("cargo:rustc-link-lib=rockchip_mpp");
+            // RK3588 MPP hardware video codec (ARM64 only), librockchip_mpp is dlopened
+            if webrtc_sys_build::use_rockchip_mpp() {
                 builder.flag("-DUSE_ROCKCHIP_MPP_VIDEO_CODEC=1");
+
+                add_lazy_load_so(
+                    &mut builder,
+                    "rockchip_mpp",
+                    ["rockchip_mpp"].map(String::from).to_vec(),
+                );
             }
 
             builder
@@ -77,6 +77,14 @@ pub fn use_debug() -> bool {
     var.is_ok() && var.unwrap() == "true"
 }
 
+/// Whether to use the WebRTC build with the Rockchip MPP video codecs, enabled by the
+/// `rockchip-mpp` feature of webrtc-sys on ARM64 Linux
+pub fn use_rockchip_mpp() -> bool {
+    env::var("CARGO_FEATURE_ROCKCHIP_MPP").is_ok()
+        && target_os() == "linux"
+        && target_arch() == "arm64"
+}
+
 /// The location of the custom build is defined by the user
 pub fn custom_dir() -> Option<path::PathBuf> {
     if let Ok(path) = env::var("LK_CUSTOM_WEBRTC") {
@@ -91,17 +99,18 @@ pub fn custom_dir() -> Option<path::PathBuf> {
 /// This also has the benefit of not re-downloading the binaries for each crate
 pub fn prebuilt_dir() -> path::PathBuf {
     let target_dir = scratch::path(SCRATH_PATH);
+    let tag = if use_rockchip_mpp() { ROCKCHIP_WEBRTC_TAG } else { WEBRTC_TAG };
     path::Path::new(&target_dir).join(format!(
         "livekit/{}-{}/{}",
         webrtc_triple(),
-        WEBRTC_TAG,
+        tag,
         webrtc_triple()
     ))
 }
 
 pub fn download_url() -> String {
     let triple = webrtc_triple();
-    if target_os() == "linux" && target_arch() == "arm64" {
+    if use_rockchip_mpp() {
         return format!(
             "https://github.com/AayushBarhate/webrtc-rockchip-mpp/releases/download/{}/webrtc-{}.zip",
             ROCKCHIP_WEBRTC_TAG,
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <stdint.h>
+
+#include <map>
+#include <memory>
+#include <optional>
+
+#include "api/frame_transformer_interface.h"
+#include "api/scoped_refptr.h"
+#include "livekit/rtp_receiver.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+class EncodedVideoSink;
+class NativeEncodedVideoSink;
+}  // namespace livekit_ffi
+#include "webrtc-sys/src/encoded_video_sink.rs.h"
+
+namespace livekit_ffi {
+
+/// Observes the encoded frames of a video receiver, before they are decoded.
+/// Frames are still forwarded to the decoder.
+class EncodedVideoSink {
+ public:
+  EncodedVideoSink(webrtc::scoped_refptr<NativeEncodedVideoSink> sink,
+                   uint64_t session,
+                   webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver);
+  ~EncodedVideoSink();
+
+  /// Sends a PLI to the remote sender
+  void request_keyframe() const;
+
+  /// Stops forwarding frames to the observer.
+  void close() const;
+
+ private:
+  webrtc::scoped_refptr<NativeEncodedVideoSink> sink_;
+  uint64_t session_;
+  webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver_;
+};
+
+/// Stays attached to the receiver once created, the frames are only observed
+/// while an EncodedVideoSink is open.
+class NativeEncodedVideoSink : public webrtc::FrameTransformerInterface {
+ public:
+  /// Returns the session to close, or 0 if another EncodedVideoSink is
+  /// already observing the frames
+  uint64_t open(rust::Box<EncodedVideoSinkWrapper> observer);
+
+  void Transform(std::unique_ptr<webrtc::TransformableFrameInterface>
+                     transformable_frame) override;
+
+  void RegisterTransformedFrameCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback)
+      override;
+
+  void RegisterTransformedFrameSinkCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+
+  void UnregisterTransformedFrameCallback() override;
+
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+  /// Stops observing the frames if `session` is still the open one
+  void close(uint64_t session);
+
+ private:
+  webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_for(
+      uint32_t ssrc);
+
+  mutable webrtc::Mutex mutex_;
+  std::optional<rust::Box<EncodedVideoSinkWrapper>> observer_
+      RTC_GUARDED_BY(mutex_);
+  uint64_t session_ RTC_GUARDED_BY(mutex_) = 0;
+  webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_
+      RTC_GUARDED_BY(mutex_);
+  std::map<uint32_t, webrtc::scoped_refptr<webrtc::TransformedFrameCallback>>
+      sink_callbacks_ RTC_GUARDED_BY(mutex_);
+};
+
+std::shared_ptr<EncodedVideoSink> new_encoded_video_sink(
+    rust::Box<EncodedVideoSinkWrapper> observer,
+    std::shared_ptr<RtpReceiver> receiver);
+
+}  // namespace livekit_ffi
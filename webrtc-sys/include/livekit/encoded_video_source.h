@@ -0,0 +1,137 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <stdint.h>
+
+#include <atomic>
+#include <memory>
+#include <optional>
+#include <vector>
+
+#include "api/video/video_frame_buffer.h"
+#include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_encoder.h"
+#include "livekit/video_track.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+
+struct EncodedFrameInfo;
+enum class EncodedVideoCodec : ::std::int32_t;
+class KeyFrameRequestObserverWrapper;
+
+/// Forwards keyframe requests from the encoder to Rust.
+class KeyFrameRequestHandler {
+ public:
+  void set_observer(rust::Box<KeyFrameRequestObserverWrapper> observer);
+  void request_keyframe();
+
+ private:
+  webrtc::Mutex mutex_;
+  std::optional<rust::Box<KeyFrameRequestObserverWrapper>> observer_;
+};
+
+/// A frame that is already encoded, passed through the video pipeline as a
+/// native buffer until it reaches the PassthroughVideoEncoder.
+class EncodedFrameBuffer : public webrtc::VideoFrameBuffer {
+ public:
+  EncodedFrameBuffer(EncodedVideoCodec codec,
+                     std::vector<uint8_t> data,
+                     bool is_keyframe,
+                     int width,
+                     int height,
+                     std::shared_ptr<KeyFrameRequestHandler> keyframe_handler);
+
+  Type type() const override { return Type::kNative; }
+  int width() const override { return width_; }
+  int height() const override { return height_; }
+
+  /// Encoded frames can't be decoded here, local sinks receive a black frame.
+  webrtc::scoped_refptr<webrtc::I420BufferInterface> ToI420() override;
+
+  EncodedVideoCodec codec() const { return codec_; }
+  const std::vector<uint8_t>& data() const { return data_; }
+  bool is_keyframe() const { return is_keyframe_; }
+  void request_keyframe() const { keyframe_handler_->request_keyframe(); }
+
+ private:
+  EncodedVideoCodec codec_;
+  std::vector<uint8_t> data_;
+  bool is_keyframe_;
+  int width_;
+  int height_;
+  std::shared_ptr<KeyFrameRequestHandler> keyframe_handler_;
+};
+
+class EncodedVideoTrackSource {
+ public:
+  EncodedVideoTrackSource(const VideoResolution& resolution,
+                          EncodedVideoCodec codec);
+
+  std::shared_ptr<VideoTrackSource> video_source() const { return source_; }
+
+  /// Pushes an Annex-B access unit.
+  bool capture_frame(rust::Slice<const uint8_t> data,
+                     const EncodedFrameInfo& info) const;
+
+  void set_keyframe_request_observer(
+      rust::Box<KeyFrameRequestObserverWrapper> observer) const;
+
+ private:
+  EncodedVideoCodec codec_;
+  std::shared_ptr<VideoTrackSource> source_;
+  std::shared_ptr<KeyFrameRequestHandler> keyframe_handler_;
+};
+
+std::shared_ptr<EncodedVideoTrackSource> new_encoded_video_track_source(
+    const VideoResolution& resolution,
+    EncodedVideoCodec codec);
+
+/// Sends EncodedFrameBuffers as is, other frames are encoded by the fallback
+/// encoder. The encoder info of the fallback is reported until the first
+/// encoded frame, so tracks of other sources keep their quality scaling.
+class PassthroughVideoEncoder : public webrtc::VideoEncoder {
+ public:
+  PassthroughVideoEncoder(const webrtc::SdpVideoFormat& format,
+                          std::unique_ptr<webrtc::VideoEncoder> fallback);
+
+  void SetFecControllerOverride(
+      webrtc::FecControllerOverride* fec_controller_override) override;
+  int InitEncode(const webrtc::VideoCodec* codec_settings,
+                 const webrtc::VideoEncoder::Settings& settings) override;
+  int32_t RegisterEncodeCompleteCallback(
+      webrtc::EncodedImageCallback* callback) override;
+  int32_t Release() override;
+  int32_t Encode(const webrtc::VideoFrame& frame,
+                 const std::vector<webrtc::VideoFrameType>* frame_types)
+      override;
+  void SetRates(const RateControlParameters& parameters) override;
+  EncoderInfo GetEncoderInfo() const override;
+
+  /// Whether encoded frames can be passed through for this format.
+  static bool IsSupported(const webrtc::SdpVideoFormat& format);
+
+ private:
+  webrtc::SdpVideoFormat format_;
+  std::unique_ptr<webrtc::VideoEncoder> fallback_;
+  webrtc::EncodedImageCallback* callback_ = nullptr;
+  std::atomic<bool> passthrough_{false};
+};
+
+}  // namespace livekit_ffi
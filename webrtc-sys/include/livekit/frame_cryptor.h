@@ -18,11 +18,14 @@
 
 #include <stdint.h>
 
+#include <atomic>
 #include <memory>
 #include <string>
 #include <vector>
 
+#include "api/array_view.h"
 #include "api/crypto/frame_crypto_transformer.h"
+#include "api/frame_transformer_interface.h"
 #include "api/scoped_refptr.h"
 #include "livekit/peer_connection.h"
 #include "livekit/peer_connection_factory.h"
@@ -30,6 +33,7 @@
 #include "livekit/rtp_sender.h"
 #include "livekit/webrtc.h"
 #include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
 #include "rust/cxx.h"
 
 namespace livekit_ffi {
@@ -39,6 +43,7 @@ struct EncryptedPacket;
 enum class Algorithm : ::std::int32_t;
 class RtcFrameCryptorObserverWrapper;
 class NativeFrameCryptorObserver;
+class FrameKeyIndexTracker;
 
 /// Shared secret key for frame encryption.
 class KeyProvider {
@@ -111,6 +116,17 @@ class KeyProvider {
     return vec;
   }
 
+  /// Clears the decryption failures counted for a participant, so that its frames are
+  /// decrypted again after exceeding the failure tolerance.
+  void reset_key_status(const ::rust::String participant_id) const {
+    auto id = std::string(participant_id.data(), participant_id.size());
+    auto key_handler =
+        impl_->options().shared_key ? impl_->GetSharedKey(id) : impl_->GetKey(id);
+    if (key_handler) {
+      key_handler->SetHasValidKey();
+    }
+  }
+
   void set_sif_trailer(rust::Vec<::std::uint8_t> trailer) const {
     std::vector<uint8_t> trailer_vec;
     std::copy(trailer.begin(), trailer.end(), std::back_inserter(trailer_vec));
@@ -151,6 +167,10 @@ class FrameCryptor {
   /// Get the key index for the sender or receiver.
   int32_t key_index() const;
 
+  /// Key index of the last frame: carried by the frame for a receiver, or used to encrypt
+  /// it for a sender.
+  int32_t frame_key_index() const;
+
   rust::String participant_id() const { return participant_id_; }
 
   void register_observer(
@@ -163,12 +183,47 @@ class FrameCryptor {
   const rust::String participant_id_;
   mutable webrtc::Mutex mutex_;
   webrtc::scoped_refptr<webrtc::FrameCryptorTransformer> e2ee_transformer_;
+  webrtc::scoped_refptr<FrameKeyIndexTracker> key_index_tracker_;
   webrtc::scoped_refptr<webrtc::KeyProvider> key_provider_;
   webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender_;
   webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver_;
   mutable webrtc::scoped_refptr<NativeFrameCryptorObserver> observer_;
 };
 
+/// Forwards the frames of a receiver to its frame cryptor, recording the key index
+/// the cryptor decrypts them with.
+class FrameKeyIndexTracker : public webrtc::FrameTransformerInterface {
+ public:
+  FrameKeyIndexTracker(
+      webrtc::scoped_refptr<webrtc::FrameCryptorTransformer> transformer,
+      webrtc::scoped_refptr<webrtc::KeyProvider> key_provider);
+
+  void Transform(std::unique_ptr<webrtc::TransformableFrameInterface>
+                     transformable_frame) override;
+
+  void RegisterTransformedFrameCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback)
+      override;
+
+  void RegisterTransformedFrameSinkCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+
+  void UnregisterTransformedFrameCallback() override;
+
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+  /// -1 until a frame was decrypted
+  int32_t last_key_index() const { return last_key_index_.load(); }
+
+ private:
+  bool has_sif_trailer(rtc::ArrayView<const uint8_t> data) const;
+
+  webrtc::scoped_refptr<webrtc::FrameCryptorTransformer> transformer_;
+  webrtc::scoped_refptr<webrtc::KeyProvider> key_provider_;
+  std::atomic<int32_t> last_key_index_{-1};
+};
+
 class NativeFrameCryptorObserver
     : public webrtc::FrameCryptorTransformerObserver {
  public:
@@ -179,9 +234,14 @@ class NativeFrameCryptorObserver
   void OnFrameCryptionStateChanged(const std::string participant_id,
                                    webrtc::FrameCryptionState error) override;
 
+  /// The transformer may still hold the observer after the FrameCryptor is
+  /// destroyed, the state changes are ignored from then on.
+  void detach();
+
  private:
+  webrtc::Mutex mutex_;
   rust::Box<RtcFrameCryptorObserverWrapper> observer_;
-  const FrameCryptor* fc_;
+  const FrameCryptor* fc_ RTC_GUARDED_BY(mutex_);
 };
 
 class DataPacketCryptor {
@@ -0,0 +1,103 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <stdint.h>
+
+#include <map>
+#include <memory>
+
+#include "api/frame_transformer_interface.h"
+#include "api/scoped_refptr.h"
+#include "livekit/rtp_receiver.h"
+#include "livekit/rtp_sender.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+
+class RtcFrameTransformerWrapper;
+class NativeFrameTransformer;
+
+/// Passes the encoded frames of a sender or a receiver to a Rust transformer.
+class FrameTransformer {
+ public:
+  FrameTransformer(rust::Box<RtcFrameTransformerWrapper> transformer,
+                   webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender);
+
+  FrameTransformer(rust::Box<RtcFrameTransformerWrapper> transformer,
+                   webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver);
+
+  /// When disabled, frames are forwarded untouched.
+  void set_enabled(bool enabled) const;
+
+  bool enabled() const;
+
+ private:
+  webrtc::scoped_refptr<NativeFrameTransformer> transformer_;
+  webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender_;
+  webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver_;
+};
+
+class NativeFrameTransformer : public webrtc::FrameTransformerInterface {
+ public:
+  NativeFrameTransformer(rust::Box<RtcFrameTransformerWrapper> transformer,
+                         bool is_audio);
+
+  void Transform(std::unique_ptr<webrtc::TransformableFrameInterface>
+                     transformable_frame) override;
+
+  void RegisterTransformedFrameCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback)
+      override;
+
+  void RegisterTransformedFrameSinkCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+
+  void UnregisterTransformedFrameCallback() override;
+
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+  void set_enabled(bool enabled);
+
+  bool enabled() const;
+
+ private:
+  webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_for(
+      uint32_t ssrc);
+
+  rust::Box<RtcFrameTransformerWrapper> transformer_;
+  const bool is_audio_;
+  mutable webrtc::Mutex mutex_;
+  bool enabled_ RTC_GUARDED_BY(mutex_) = true;
+  webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_
+      RTC_GUARDED_BY(mutex_);
+  std::map<uint32_t, webrtc::scoped_refptr<webrtc::TransformedFrameCallback>>
+      sink_callbacks_ RTC_GUARDED_BY(mutex_);
+};
+
+std::shared_ptr<FrameTransformer> new_frame_transformer_for_rtp_sender(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    std::shared_ptr<RtpSender> sender);
+
+std::shared_ptr<FrameTransformer> new_frame_transformer_for_rtp_receiver(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    std::shared_ptr<RtpReceiver> receiver);
+
+}  // namespace livekit_ffi
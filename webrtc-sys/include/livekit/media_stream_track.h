@@ -16,11 +16,15 @@
 
 #pragma once
 
+#include <functional>
 #include <memory>
 
+#include "api/frame_transformer_interface.h"
 #include "api/media_stream_interface.h"
 #include "livekit/helper.h"
 #include "livekit/webrtc.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
 #include "rust/cxx.h"
 
 namespace livekit_ffi {
@@ -48,9 +52,43 @@ class MediaStreamTrack {
     return track_;
   }
 
+  /// The frame transformer of a receiver can't be removed nor chained, a new
+  /// one silently replaces the previous one. Returns false if the encoded
+  /// video sink of the receiver of this track would be replaced.
+  bool claim_frame_transformer() {
+    webrtc::MutexLock lock(&frame_transformer_mutex_);
+    if (encoded_sink_) {
+      return false;
+    }
+    has_frame_transformer_ = true;
+    return true;
+  }
+
+  /// Returns the transformer observing the encoded frames of the receiver of
+  /// this track. It is created and attached by `attach` on first use, then
+  /// stays attached and is shared by the following encoded video sinks.
+  /// Returns null if another transformer (e.g. for E2EE) is attached.
+  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> encoded_sink(
+      const std::function<
+          webrtc::scoped_refptr<webrtc::FrameTransformerInterface>()>& attach) {
+    webrtc::MutexLock lock(&frame_transformer_mutex_);
+    if (has_frame_transformer_) {
+      return nullptr;
+    }
+    if (!encoded_sink_) {
+      encoded_sink_ = attach();
+    }
+    return encoded_sink_;
+  }
+
  protected:
   std::shared_ptr<RtcRuntime> rtc_runtime_;
   webrtc::scoped_refptr<webrtc::MediaStreamTrackInterface> track_;
+
+  webrtc::Mutex frame_transformer_mutex_;
+  bool has_frame_transformer_ RTC_GUARDED_BY(frame_transformer_mutex_) = false;
+  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> encoded_sink_
+      RTC_GUARDED_BY(frame_transformer_mutex_);
 };
 
 static std::shared_ptr<MediaStreamTrack> _shared_media_stream_track() {
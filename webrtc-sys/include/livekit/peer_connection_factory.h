@@ -16,6 +16,7 @@
 
 #pragma once
 
+#include "api/environment/environment.h"
 #include "api/peer_connection_interface.h"
 #include "api/scoped_refptr.h"
 #include "api/task_queue/task_queue_factory.h"
@@ -27,6 +28,8 @@
 
 namespace livekit_ffi {
 class PeerConnectionFactory;
+class VideoDecoderFactory;
+class VideoEncoderFactory;
 class PeerConnectionObserverWrapper;
 }  // namespace livekit_ffi
 #include "webrtc-sys/src/peer_connection_factory.rs.h"
@@ -60,6 +63,15 @@ class PeerConnectionFactory {
 
   RtpCapabilities rtp_receiver_capabilities(MediaType type) const;
 
+  rust::Vec<VideoCodecImplementation> video_encoder_implementations() const;
+
+  /// Whether an encoder is available for the codec, e.g. "H264"
+  bool has_video_encoder(rust::Str codec_name) const;
+
+  rust::Vec<VideoCodecImplementation> video_decoder_implementations() const;
+
+  rust::Vec<VideoCodecBackendEntry> video_codec_backends() const;
+
   std::shared_ptr<RtcRuntime> rtc_runtime() const { return rtc_runtime_; }
 
  private:
@@ -67,6 +79,10 @@ class PeerConnectionFactory {
   webrtc::scoped_refptr<AudioDevice> audio_device_;
   webrtc::scoped_refptr<webrtc::PeerConnectionFactoryInterface> peer_factory_;
   webrtc::TaskQueueFactory* task_queue_factory_;
+  // Owned by peer_factory_
+  VideoEncoderFactory* video_encoder_factory_;
+  VideoDecoderFactory* video_decoder_factory_;
+  webrtc::Environment env_;
 };
 
 std::shared_ptr<PeerConnectionFactory> create_peer_connection_factory();
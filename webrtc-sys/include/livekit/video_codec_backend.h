@@ -0,0 +1,41 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <vector>
+
+#include "api/video/video_codec_type.h"
+#include "rust/cxx.h"
+#include "webrtc-sys/src/video_codec_backend.rs.h"
+
+namespace livekit_ffi {
+
+void set_video_codec_backend_rules(rust::Vec<VideoCodecBackendRule> rules);
+
+/// Applies the rules to the backends able to handle `codec`, given in their
+/// default order: disabled backends are removed and preferred ones moved first.
+std::vector<VideoCodecBackend> order_video_codec_backends(
+    webrtc::VideoCodecType codec,
+    std::vector<VideoCodecBackend> backends);
+
+#if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
+/// librockchip_mpp is dlopened on first use, this checks beforehand that it
+/// exists so the software codecs are used otherwise.
+bool IsRockchipMppAvailable();
+#endif
+
+}  // namespace livekit_ffi
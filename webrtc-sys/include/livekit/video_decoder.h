@@ -0,0 +1,98 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <stdint.h>
+
+#include <memory>
+#include <vector>
+
+#include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_decoder.h"
+#include "api/video_codecs/video_decoder_factory.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+class DecodedImageSink;
+}  // namespace livekit_ffi
+#include "webrtc-sys/src/video_decoder.rs.h"
+
+namespace livekit_ffi {
+
+/// Forwards the frames produced by a Rust decoder to WebRTC.
+class DecodedImageSink {
+ public:
+  void set_callback(webrtc::DecodedImageCallback* callback);
+
+  bool on_decoded_frame(const VideoFrameBuffer& buffer,
+                        uint32_t rtp_timestamp,
+                        int32_t qp) const;
+
+ private:
+  mutable webrtc::Mutex mutex_;
+  webrtc::DecodedImageCallback* callback_ RTC_GUARDED_BY(mutex_) = nullptr;
+};
+
+class RustVideoDecoder : public webrtc::VideoDecoder {
+ public:
+  explicit RustVideoDecoder(rust::Box<VideoDecoderWrapper> decoder);
+
+  bool Configure(const Settings& settings) override;
+  int32_t Decode(const webrtc::EncodedImage& input_image,
+                 int64_t render_time_ms) override;
+  int32_t RegisterDecodeCompleteCallback(
+      webrtc::DecodedImageCallback* callback) override;
+  int32_t Release() override;
+  DecoderInfo GetDecoderInfo() const override;
+  const char* ImplementationName() const override;
+
+ private:
+  rust::Box<VideoDecoderWrapper> decoder_;
+  std::shared_ptr<DecodedImageSink> sink_;
+  std::string implementation_name_;
+};
+
+class RustVideoDecoderFactory : public webrtc::VideoDecoderFactory {
+ public:
+  explicit RustVideoDecoderFactory(
+      rust::Box<VideoDecoderFactoryWrapper> factory);
+
+  std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
+
+  std::unique_ptr<webrtc::VideoDecoder> Create(
+      const webrtc::Environment& env,
+      const webrtc::SdpVideoFormat& format) override;
+
+ private:
+  rust::Box<VideoDecoderFactoryWrapper> factory_;
+};
+
+/// Factories registered from Rust, the most recently registered first.
+/// They take precedence over the built-in decoders, which are used as fallback.
+std::vector<std::shared_ptr<RustVideoDecoderFactory>>
+registered_video_decoder_factories();
+
+/// Returns the id used to unregister the factory.
+uint64_t register_video_decoder_factory(
+    rust::Box<VideoDecoderFactoryWrapper> factory);
+
+/// Returns false if no factory is registered with this id.
+bool unregister_video_decoder_factory(uint64_t id);
+
+}  // namespace livekit_ffi
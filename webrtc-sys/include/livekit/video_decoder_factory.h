@@ -16,9 +16,12 @@
 
 #pragma once
 
+#include <utility>
+
 #include "api/video_codecs/video_decoder.h"
 #include "api/video_codecs/video_decoder_factory.h"
 #include "absl/strings/match.h"
+#include "livekit/video_codec_backend.h"
 
 namespace livekit_ffi {
 class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
@@ -33,7 +36,21 @@ class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
   std::unique_ptr<webrtc::VideoDecoder> Create(
       const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) override;
 
+  /// Backends supporting `format`, in the order they are tried
+  std::vector<VideoCodecBackend> Backends(
+      const webrtc::SdpVideoFormat& format) const;
+
  private:
-  std::vector<std::unique_ptr<webrtc::VideoDecoderFactory>> factories_;
+  std::unique_ptr<webrtc::VideoDecoder> CreateDecoder(
+      VideoCodecBackend backend,
+      const webrtc::Environment& env,
+      const webrtc::SdpVideoFormat& format);
+
+  std::vector<webrtc::SdpVideoFormat> SupportedFormats(
+      VideoCodecBackend backend) const;
+
+  std::vector<std::pair<VideoCodecBackend,
+                        std::unique_ptr<webrtc::VideoDecoderFactory>>>
+      factories_;
 };
 }  // namespace livekit_ffi
@@ -0,0 +1,101 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <stdint.h>
+
+#include <memory>
+#include <vector>
+
+#include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_encoder.h"
+#include "api/video_codecs/video_encoder_factory.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+class EncodedImageSink;
+}  // namespace livekit_ffi
+#include "webrtc-sys/src/video_encoder.rs.h"
+
+namespace livekit_ffi {
+
+/// Forwards the images produced by a Rust encoder to WebRTC.
+class EncodedImageSink {
+ public:
+  void set_callback(webrtc::EncodedImageCallback* callback);
+
+  bool on_encoded_image(rust::Slice<const uint8_t> data,
+                        const EncodedImageInfo& info) const;
+
+ private:
+  mutable webrtc::Mutex mutex_;
+  webrtc::EncodedImageCallback* callback_ RTC_GUARDED_BY(mutex_) = nullptr;
+};
+
+class RustVideoEncoder : public webrtc::VideoEncoder {
+ public:
+  explicit RustVideoEncoder(rust::Box<VideoEncoderWrapper> encoder);
+
+  int InitEncode(const webrtc::VideoCodec* codec_settings,
+                 const webrtc::VideoEncoder::Settings& settings) override;
+  int32_t RegisterEncodeCompleteCallback(
+      webrtc::EncodedImageCallback* callback) override;
+  int32_t Release() override;
+  int32_t Encode(const webrtc::VideoFrame& frame,
+                 const std::vector<webrtc::VideoFrameType>* frame_types)
+      override;
+  void SetRates(const RateControlParameters& parameters) override;
+  EncoderInfo GetEncoderInfo() const override;
+
+ private:
+  rust::Box<VideoEncoderWrapper> encoder_;
+  std::shared_ptr<EncodedImageSink> sink_;
+};
+
+class RustVideoEncoderFactory : public webrtc::VideoEncoderFactory {
+ public:
+  explicit RustVideoEncoderFactory(
+      rust::Box<VideoEncoderFactoryWrapper> factory);
+
+  std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
+
+  std::unique_ptr<webrtc::VideoEncoder> Create(
+      const webrtc::Environment& env,
+      const webrtc::SdpVideoFormat& format) override;
+
+ private:
+  rust::Box<VideoEncoderFactoryWrapper> factory_;
+};
+
+/// Factories registered from Rust, the most recently registered first.
+/// They take precedence over the built-in encoders.
+std::vector<std::shared_ptr<RustVideoEncoderFactory>>
+registered_video_encoder_factories();
+
+/// Returns the id used to unregister the factory.
+uint64_t register_video_encoder_factory(
+    rust::Box<VideoEncoderFactoryWrapper> factory);
+
+/// Returns false if no factory is registered with this id.
+bool unregister_video_encoder_factory(uint64_t id);
+
+webrtc::SdpVideoFormat to_webrtc_format(const SdpVideoFormat& format);
+SdpVideoFormat to_rust_format(const webrtc::SdpVideoFormat& format);
+
+}  // namespace livekit_ffi
@@ -16,8 +16,12 @@
 
 #pragma once
 
+#include <optional>
+#include <utility>
+
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory.h"
+#include "livekit/video_codec_backend.h"
 
 namespace livekit_ffi {
 class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
@@ -34,8 +38,26 @@ class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
     std::unique_ptr<webrtc::VideoEncoder> Create(
         const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) override;
 
+    /// Creates the encoder without the passthrough of encoded video sources
+    std::unique_ptr<webrtc::VideoEncoder> CreateEncoder(
+        const webrtc::Environment& env, const webrtc::SdpVideoFormat& format);
+
+    /// Backends supporting `format`, in the order they are tried
+    std::vector<VideoCodecBackend> Backends(
+        const webrtc::SdpVideoFormat& format) const;
+
    private:
-    std::vector<std::unique_ptr<webrtc::VideoEncoderFactory>> factories_;
+    std::unique_ptr<webrtc::VideoEncoder> CreateEncoder(
+        VideoCodecBackend backend,
+        const webrtc::Environment& env,
+        const webrtc::SdpVideoFormat& format);
+
+    std::vector<webrtc::SdpVideoFormat> SupportedFormats(
+        VideoCodecBackend backend) const;
+
+    std::vector<std::pair<VideoCodecBackend,
+                          std::unique_ptr<webrtc::VideoEncoderFactory>>>
+        factories_;
   };
 
  public:
@@ -50,6 +72,14 @@ class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
   std::unique_ptr<webrtc::VideoEncoder> Create(
       const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) override;
 
+  /// Info of the encoder that would be used for a single layer of `format`,
+  /// std::nullopt if no encoder is available
+  std::optional<webrtc::VideoEncoder::EncoderInfo> GetEncoderInfo(
+      const webrtc::Environment& env, const webrtc::SdpVideoFormat& format);
+
+  std::vector<VideoCodecBackend> Backends(
+      const webrtc::SdpVideoFormat& format) const;
+
  private:
   std::unique_ptr<InternalFactory> internal_factory_;
 };
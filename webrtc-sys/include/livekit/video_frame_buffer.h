@@ -17,6 +17,7 @@
 #pragma once
 
 #include <memory>
+#include <vector>
 
 #include "api/video/i420_buffer.h"
 #include "api/video/i422_buffer.h"
@@ -24,6 +25,8 @@
 #include "api/video/i010_buffer.h"
 #include "api/video/nv12_buffer.h"
 #include "api/video/video_frame_buffer.h"
+#include "rtc_base/ref_counted_object.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit_ffi {
 class VideoFrameBuffer;
@@ -38,6 +41,10 @@ class I422Buffer;
 class I444Buffer;
 class I010Buffer;
 class NV12Buffer;
+class RgbaBuffer;
+class I420BufferPool;
+class NV12BufferPool;
+class ExternalBufferRelease;
 }  // namespace livekit_ffi
 
 #ifdef __APPLE__
@@ -211,6 +218,161 @@ class NV12Buffer : public BiplanarYuv8Buffer {
   webrtc::NV12BufferInterface* buffer() const;
 };
 
+// Packed 32 bits per pixel RGB image. WebRTC has no such buffer type, so it is
+// reported as kNative and converted with ToI420() by the consumers.
+class PackedRgbaBuffer : public webrtc::VideoFrameBuffer {
+ public:
+  PackedRgbaBuffer(RgbaFormat format, int width, int height, int stride);
+
+  Type type() const override;
+  int width() const override;
+  int height() const override;
+  webrtc::scoped_refptr<webrtc::I420BufferInterface> ToI420() override;
+
+  RgbaFormat format() const;
+  int stride() const;
+  const uint8_t* data() const;
+
+ private:
+  RgbaFormat format_;
+  int width_;
+  int height_;
+  int stride_;
+  std::vector<uint8_t> data_;
+};
+
+class RgbaBuffer : public VideoFrameBuffer {
+ public:
+  explicit RgbaBuffer(webrtc::scoped_refptr<PackedRgbaBuffer> buffer);
+
+  RgbaFormat format() const;
+  unsigned int stride() const;
+  const uint8_t* data() const;
+
+ private:
+  PackedRgbaBuffer* buffer() const;
+};
+
+// Planes owned by the caller, wrapped without copying them. The release
+// callback is called once the last reference to the buffer is gone.
+class ExternalI420Buffer : public webrtc::I420BufferInterface {
+ public:
+  ExternalI420Buffer(int width,
+                     int height,
+                     const uint8_t* data_y,
+                     int stride_y,
+                     const uint8_t* data_u,
+                     int stride_u,
+                     const uint8_t* data_v,
+                     int stride_v,
+                     rust::Box<ExternalBufferRelease> release);
+  ~ExternalI420Buffer() override;
+
+  int width() const override;
+  int height() const override;
+  const uint8_t* DataY() const override;
+  const uint8_t* DataU() const override;
+  const uint8_t* DataV() const override;
+  int StrideY() const override;
+  int StrideU() const override;
+  int StrideV() const override;
+
+ private:
+  int width_;
+  int height_;
+  const uint8_t* data_y_;
+  int stride_y_;
+  const uint8_t* data_u_;
+  int stride_u_;
+  const uint8_t* data_v_;
+  int stride_v_;
+  rust::Box<ExternalBufferRelease> release_;
+};
+
+class ExternalNV12Buffer : public webrtc::NV12BufferInterface {
+ public:
+  ExternalNV12Buffer(int width,
+                     int height,
+                     const uint8_t* data_y,
+                     int stride_y,
+                     const uint8_t* data_uv,
+                     int stride_uv,
+                     rust::Box<ExternalBufferRelease> release);
+  ~ExternalNV12Buffer() override;
+
+  int width() const override;
+  int height() const override;
+  const uint8_t* DataY() const override;
+  const uint8_t* DataUV() const override;
+  int StrideY() const override;
+  int StrideUV() const override;
+  webrtc::scoped_refptr<webrtc::I420BufferInterface> ToI420() override;
+
+ private:
+  int width_;
+  int height_;
+  const uint8_t* data_y_;
+  int stride_y_;
+  const uint8_t* data_uv_;
+  int stride_uv_;
+  rust::Box<ExternalBufferRelease> release_;
+};
+
+// Recycles buffers of a fixed size. A buffer is only reused once the pool holds
+// the last reference to it, i.e. after every Rust handle and every frame in the
+// WebRTC pipeline using it has been released.
+class I420BufferPool {
+ public:
+  I420BufferPool(int width,
+                 int height,
+                 int stride_y,
+                 int stride_u,
+                 int stride_v,
+                 size_t max_buffers);
+
+  // Returns nullptr when max_buffers are already in use
+  std::unique_ptr<I420Buffer> acquire() const;
+  size_t allocated() const;
+  void release_unused() const;
+
+ private:
+  int width_;
+  int height_;
+  int stride_y_;
+  int stride_u_;
+  int stride_v_;
+  size_t max_buffers_;
+  mutable webrtc::Mutex mutex_;
+  mutable std::vector<
+      webrtc::scoped_refptr<webrtc::RefCountedObject<webrtc::I420Buffer>>>
+      buffers_;
+};
+
+class NV12BufferPool {
+ public:
+  NV12BufferPool(int width,
+                 int height,
+                 int stride_y,
+                 int stride_uv,
+                 size_t max_buffers);
+
+  // Returns nullptr when max_buffers are already in use
+  std::unique_ptr<NV12Buffer> acquire() const;
+  size_t allocated() const;
+  void release_unused() const;
+
+ private:
+  int width_;
+  int height_;
+  int stride_y_;
+  int stride_uv_;
+  size_t max_buffers_;
+  mutable webrtc::Mutex mutex_;
+  mutable std::vector<
+      webrtc::scoped_refptr<webrtc::RefCountedObject<webrtc::NV12Buffer>>>
+      buffers_;
+};
+
 std::unique_ptr<I420Buffer> copy_i420_buffer(
     const std::unique_ptr<I420Buffer>& i420);
 std::unique_ptr<I420Buffer> new_i420_buffer(int width, int height, int stride_y, int stride_u, int stride_v);
@@ -218,6 +380,14 @@ std::unique_ptr<I422Buffer> new_i422_buffer(int width, int height, int stride_y,
 std::unique_ptr<I444Buffer> new_i444_buffer(int width, int height, int stride_y, int stride_u, int stride_v);
 std::unique_ptr<I010Buffer> new_i010_buffer(int width, int height, int stride_y, int stride_u, int stride_v);
 std::unique_ptr<NV12Buffer> new_nv12_buffer(int width, int height, int stride_y, int stride_uv);
+std::unique_ptr<I420ABuffer> new_i420a_buffer(int width, int height, int stride_y, int stride_u, int stride_v, int stride_a);
+std::unique_ptr<RgbaBuffer> new_rgba_buffer(RgbaFormat format, int width, int height, int stride);
+std::unique_ptr<I420Buffer> new_external_i420_buffer(int width, int height, const uint8_t* data_y, int stride_y, const uint8_t* data_u, int stride_u, const uint8_t* data_v, int stride_v, rust::Box<ExternalBufferRelease> release);
+std::unique_ptr<NV12Buffer> new_external_nv12_buffer(int width, int height, const uint8_t* data_y, int stride_y, const uint8_t* data_uv, int stride_uv, rust::Box<ExternalBufferRelease> release);
+const uint8_t* map_dmabuf(int fd, size_t size, bool writable);
+void unmap_dmabuf(int fd, const uint8_t* data, size_t size, bool writable);
+std::unique_ptr<I420BufferPool> new_i420_buffer_pool(int width, int height, int stride_y, int stride_u, int stride_v, size_t max_buffers);
+std::unique_ptr<NV12BufferPool> new_nv12_buffer_pool(int width, int height, int stride_y, int stride_uv, size_t max_buffers);
 
 std::unique_ptr<VideoFrameBuffer> new_native_buffer_from_platform_image_buffer(PlatformImageBuffer *buffer);
 PlatformImageBuffer* native_buffer_to_platform_image_buffer(const std::unique_ptr<VideoFrameBuffer> &);
@@ -226,6 +396,10 @@ static const VideoFrameBuffer* yuv_to_vfb(const PlanarYuvBuffer* yuv) {
   return yuv;
 }
 
+static const VideoFrameBuffer* rgba_to_vfb(const RgbaBuffer* rgba) {
+  return rgba;
+}
+
 static const VideoFrameBuffer* biyuv_to_vfb(const BiplanarYuvBuffer* biyuv) {
   return biyuv;
 }
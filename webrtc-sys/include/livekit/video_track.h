@@ -25,6 +25,7 @@
 #include "livekit/video_frame.h"
 #include "livekit/webrtc.h"
 #include "media/base/adapted_video_track_source.h"
+#include "media/base/video_adapter.h"
 #include "rtc_base/synchronization/mutex.h"
 #include "rtc_base/timestamp_aligner.h"
 #include "rust/cxx.h"
@@ -98,12 +99,29 @@ class VideoTrackSource {
     SourceState state() const override;
     bool remote() const override;
     VideoResolution video_resolution() const;
+    void set_adaptation(const VideoAdaptation& adaptation);
     bool on_captured_frame(const webrtc::VideoFrame& frame);
+    // Encoded frames can't be adapted, they are forwarded as is
+    bool on_captured_encoded_frame(const webrtc::VideoFrame& frame);
 
    private:
+    // Same as AdaptFrame(), without the VideoSinkWants of the sinks
+    bool adapt_frame_ignoring_wants(int width,
+                                    int height,
+                                    int64_t time_us,
+                                    int* out_width,
+                                    int* out_height,
+                                    int* crop_width,
+                                    int* crop_height,
+                                    int* crop_x,
+                                    int* crop_y);
+
     mutable webrtc::Mutex mutex_;
     webrtc::TimestampAligner timestamp_aligner_;
     VideoResolution resolution_;
+    VideoAdaptation adaptation_;
+    // Only used when the sink wants are ignored
+    webrtc::VideoAdapter adapter_;
   };
 
  public:
@@ -111,6 +129,8 @@ class VideoTrackSource {
 
   VideoResolution video_resolution() const;
 
+  void set_adaptation(const VideoAdaptation& adaptation) const;
+
   bool on_captured_frame(const std::unique_ptr<VideoFrame>& frame)
       const;  // frames pushed from Rust (+interior mutability)
 
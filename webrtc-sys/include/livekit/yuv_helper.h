@@ -20,7 +20,15 @@
 #include <stdexcept>
 #include <string>
 
+#include "api/video/i420_buffer.h"
 #include "api/video/yuv_helper.h"
+#include "third_party/libyuv/include/libyuv/convert.h"
+#include "third_party/libyuv/include/libyuv/convert_argb.h"
+#include "third_party/libyuv/include/libyuv/convert_from.h"
+#include "third_party/libyuv/include/libyuv/convert_from_argb.h"
+#include "third_party/libyuv/include/libyuv/planar_functions.h"
+#include "third_party/libyuv/include/libyuv/rotate.h"
+#include "third_party/libyuv/include/libyuv/scale.h"
 #include "webrtc-sys/src/yuv_helper.rs.h"
 
 namespace livekit_ffi {
@@ -378,4 +386,513 @@ static void argb_to_nv12(const uint8_t* src_argb,
                                     height));
 }
 
+static void i420_scale(const uint8_t* src_y,
+                       int src_stride_y,
+                       const uint8_t* src_u,
+                       int src_stride_u,
+                       const uint8_t* src_v,
+                       int src_stride_v,
+                       int src_width,
+                       int src_height,
+                       uint8_t* dst_y,
+                       int dst_stride_y,
+                       uint8_t* dst_u,
+                       int dst_stride_u,
+                       uint8_t* dst_v,
+                       int dst_stride_v,
+                       int dst_width,
+                       int dst_height) {
+  THROW_ON_ERROR(libyuv::I420Scale(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, src_width,
+      src_height, dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v,
+      dst_width, dst_height, libyuv::kFilterBox));
+}
+
+static void i422_scale(const uint8_t* src_y,
+                       int src_stride_y,
+                       const uint8_t* src_u,
+                       int src_stride_u,
+                       const uint8_t* src_v,
+                       int src_stride_v,
+                       int src_width,
+                       int src_height,
+                       uint8_t* dst_y,
+                       int dst_stride_y,
+                       uint8_t* dst_u,
+                       int dst_stride_u,
+                       uint8_t* dst_v,
+                       int dst_stride_v,
+                       int dst_width,
+                       int dst_height) {
+  THROW_ON_ERROR(libyuv::I422Scale(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, src_width,
+      src_height, dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v,
+      dst_width, dst_height, libyuv::kFilterBox));
+}
+
+static void i444_scale(const uint8_t* src_y,
+                       int src_stride_y,
+                       const uint8_t* src_u,
+                       int src_stride_u,
+                       const uint8_t* src_v,
+                       int src_stride_v,
+                       int src_width,
+                       int src_height,
+                       uint8_t* dst_y,
+                       int dst_stride_y,
+                       uint8_t* dst_u,
+                       int dst_stride_u,
+                       uint8_t* dst_v,
+                       int dst_stride_v,
+                       int dst_width,
+                       int dst_height) {
+  THROW_ON_ERROR(libyuv::I444Scale(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, src_width,
+      src_height, dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v,
+      dst_width, dst_height, libyuv::kFilterBox));
+}
+
+static void i010_scale(const uint16_t* src_y,
+                       int src_stride_y,
+                       const uint16_t* src_u,
+                       int src_stride_u,
+                       const uint16_t* src_v,
+                       int src_stride_v,
+                       int src_width,
+                       int src_height,
+                       uint16_t* dst_y,
+                       int dst_stride_y,
+                       uint16_t* dst_u,
+                       int dst_stride_u,
+                       uint16_t* dst_v,
+                       int dst_stride_v,
+                       int dst_width,
+                       int dst_height) {
+  THROW_ON_ERROR(libyuv::I420Scale_16(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, src_width,
+      src_height, dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v,
+      dst_width, dst_height, libyuv::kFilterBox));
+}
+
+static void nv12_scale(const uint8_t* src_y,
+                       int src_stride_y,
+                       const uint8_t* src_uv,
+                       int src_stride_uv,
+                       int src_width,
+                       int src_height,
+                       uint8_t* dst_y,
+                       int dst_stride_y,
+                       uint8_t* dst_uv,
+                       int dst_stride_uv,
+                       int dst_width,
+                       int dst_height) {
+  THROW_ON_ERROR(libyuv::NV12Scale(src_y, src_stride_y, src_uv, src_stride_uv,
+                                   src_width, src_height, dst_y, dst_stride_y,
+                                   dst_uv, dst_stride_uv, dst_width,
+                                   dst_height, libyuv::kFilterBox));
+}
+
+// `rotation` is in degrees, like libyuv::RotationMode
+static void i420_rotate(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_u,
+                        int src_stride_u,
+                        const uint8_t* src_v,
+                        int src_stride_v,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_u,
+                        int dst_stride_u,
+                        uint8_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height,
+                        int rotation) {
+  THROW_ON_ERROR(libyuv::I420Rotate(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, dst_y,
+      dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height,
+      static_cast<libyuv::RotationMode>(rotation)));
+}
+
+static void i422_rotate(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_u,
+                        int src_stride_u,
+                        const uint8_t* src_v,
+                        int src_stride_v,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_u,
+                        int dst_stride_u,
+                        uint8_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height,
+                        int rotation) {
+  THROW_ON_ERROR(libyuv::I422Rotate(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, dst_y,
+      dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height,
+      static_cast<libyuv::RotationMode>(rotation)));
+}
+
+static void i444_rotate(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_u,
+                        int src_stride_u,
+                        const uint8_t* src_v,
+                        int src_stride_v,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_u,
+                        int dst_stride_u,
+                        uint8_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height,
+                        int rotation) {
+  THROW_ON_ERROR(libyuv::I444Rotate(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, dst_y,
+      dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height,
+      static_cast<libyuv::RotationMode>(rotation)));
+}
+
+static void i010_rotate(const uint16_t* src_y,
+                        int src_stride_y,
+                        const uint16_t* src_u,
+                        int src_stride_u,
+                        const uint16_t* src_v,
+                        int src_stride_v,
+                        uint16_t* dst_y,
+                        int dst_stride_y,
+                        uint16_t* dst_u,
+                        int dst_stride_u,
+                        uint16_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height,
+                        int rotation) {
+  THROW_ON_ERROR(libyuv::I010Rotate(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, dst_y,
+      dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height,
+      static_cast<libyuv::RotationMode>(rotation)));
+}
+
+static void nv12_rotate(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_uv,
+                        int src_stride_uv,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_uv,
+                        int dst_stride_uv,
+                        int width,
+                        int height,
+                        int rotation) {
+  // libyuv can only rotate NV12 to I420
+  bool transpose = rotation == 90 || rotation == 270;
+  int dst_width = transpose ? height : width;
+  int dst_height = transpose ? width : height;
+  auto i420 = webrtc::I420Buffer::Create(dst_width, dst_height);
+  THROW_ON_ERROR(libyuv::NV12ToI420Rotate(
+      src_y, src_stride_y, src_uv, src_stride_uv, i420->MutableDataY(),
+      i420->StrideY(), i420->MutableDataU(), i420->StrideU(),
+      i420->MutableDataV(), i420->StrideV(), width, height,
+      static_cast<libyuv::RotationMode>(rotation)));
+  THROW_ON_ERROR(libyuv::I420ToNV12(
+      i420->DataY(), i420->StrideY(), i420->DataU(), i420->StrideU(),
+      i420->DataV(), i420->StrideV(), dst_y, dst_stride_y, dst_uv,
+      dst_stride_uv, dst_width, dst_height));
+}
+
+static void i420_mirror(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_u,
+                        int src_stride_u,
+                        const uint8_t* src_v,
+                        int src_stride_v,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_u,
+                        int dst_stride_u,
+                        uint8_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height) {
+  THROW_ON_ERROR(libyuv::I420Mirror(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, dst_y,
+      dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height));
+}
+
+static void i422_mirror(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_u,
+                        int src_stride_u,
+                        const uint8_t* src_v,
+                        int src_stride_v,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_u,
+                        int dst_stride_u,
+                        uint8_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height) {
+  int chroma_width = (width + 1) / 2;
+  libyuv::MirrorPlane(src_y, src_stride_y, dst_y, dst_stride_y, width, height);
+  libyuv::MirrorPlane(src_u, src_stride_u, dst_u, dst_stride_u, chroma_width,
+                      height);
+  libyuv::MirrorPlane(src_v, src_stride_v, dst_v, dst_stride_v, chroma_width,
+                      height);
+}
+
+static void i444_mirror(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_u,
+                        int src_stride_u,
+                        const uint8_t* src_v,
+                        int src_stride_v,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_u,
+                        int dst_stride_u,
+                        uint8_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height) {
+  libyuv::MirrorPlane(src_y, src_stride_y, dst_y, dst_stride_y, width, height);
+  libyuv::MirrorPlane(src_u, src_stride_u, dst_u, dst_stride_u, width, height);
+  libyuv::MirrorPlane(src_v, src_stride_v, dst_v, dst_stride_v, width, height);
+}
+
+static void i010_mirror(const uint16_t* src_y,
+                        int src_stride_y,
+                        const uint16_t* src_u,
+                        int src_stride_u,
+                        const uint16_t* src_v,
+                        int src_stride_v,
+                        uint16_t* dst_y,
+                        int dst_stride_y,
+                        uint16_t* dst_u,
+                        int dst_stride_u,
+                        uint16_t* dst_v,
+                        int dst_stride_v,
+                        int width,
+                        int height) {
+  // There is no 16-bit mirror, flipping vertically (negative height) then
+  // rotating by 180 degrees mirrors horizontally
+  THROW_ON_ERROR(libyuv::I010Rotate(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, dst_y,
+      dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, -height,
+      libyuv::kRotate180));
+}
+
+static void nv12_mirror(const uint8_t* src_y,
+                        int src_stride_y,
+                        const uint8_t* src_uv,
+                        int src_stride_uv,
+                        uint8_t* dst_y,
+                        int dst_stride_y,
+                        uint8_t* dst_uv,
+                        int dst_stride_uv,
+                        int width,
+                        int height) {
+  THROW_ON_ERROR(libyuv::NV12Mirror(src_y, src_stride_y, src_uv, src_stride_uv,
+                                    dst_y, dst_stride_y, dst_uv, dst_stride_uv,
+                                    width, height));
+}
+
+static void rgba_to_i420(const uint8_t* src_rgba,
+                         int src_stride_rgba,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::RGBAToI420(src_rgba, src_stride_rgba, dst_y,
+                                    dst_stride_y, dst_u, dst_stride_u, dst_v,
+                                    dst_stride_v, width, height));
+}
+
+static void bgra_to_i420(const uint8_t* src_bgra,
+                         int src_stride_bgra,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::BGRAToI420(src_bgra, src_stride_bgra, dst_y,
+                                    dst_stride_y, dst_u, dst_stride_u, dst_v,
+                                    dst_stride_v, width, height));
+}
+
+static void i420_to_i422(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI422(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420_to_i444(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI444(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420_to_i010(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint16_t* dst_y,
+                         int dst_stride_y,
+                         uint16_t* dst_u,
+                         int dst_stride_u,
+                         uint16_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI010(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420a_to_argb(const uint8_t* src_y,
+                          int src_stride_y,
+                          const uint8_t* src_u,
+                          int src_stride_u,
+                          const uint8_t* src_v,
+                          int src_stride_v,
+                          const uint8_t* src_a,
+                          int src_stride_a,
+                          uint8_t* dst_argb,
+                          int dst_stride_argb,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::I420AlphaToARGB(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, src_a,
+      src_stride_a, dst_argb, dst_stride_argb, width, height, 0));
+}
+
+static void i420a_to_abgr(const uint8_t* src_y,
+                          int src_stride_y,
+                          const uint8_t* src_u,
+                          int src_stride_u,
+                          const uint8_t* src_v,
+                          int src_stride_v,
+                          const uint8_t* src_a,
+                          int src_stride_a,
+                          uint8_t* dst_abgr,
+                          int dst_stride_abgr,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::I420AlphaToABGR(
+      src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, src_a,
+      src_stride_a, dst_abgr, dst_stride_abgr, width, height, 0));
+}
+
+static void argb_extract_alpha(const uint8_t* src_argb,
+                               int src_stride_argb,
+                               uint8_t* dst_a,
+                               int dst_stride_a,
+                               int width,
+                               int height) {
+  THROW_ON_ERROR(libyuv::ARGBExtractAlpha(src_argb, src_stride_argb, dst_a,
+                                          dst_stride_a, width, height));
+}
+
+static void argb_to_abgr(const uint8_t* src_argb,
+                         int src_stride_argb,
+                         uint8_t* dst_abgr,
+                         int dst_stride_abgr,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ARGBToABGR(src_argb, src_stride_argb, dst_abgr,
+                                    dst_stride_abgr, width, height));
+}
+
+static void argb_to_bgra(const uint8_t* src_argb,
+                         int src_stride_argb,
+                         uint8_t* dst_bgra,
+                         int dst_stride_bgra,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ARGBToBGRA(src_argb, src_stride_argb, dst_bgra,
+                                    dst_stride_bgra, width, height));
+}
+
+static void argb_to_rgba(const uint8_t* src_argb,
+                         int src_stride_argb,
+                         uint8_t* dst_rgba,
+                         int dst_stride_rgba,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ARGBToRGBA(src_argb, src_stride_argb, dst_rgba,
+                                    dst_stride_rgba, width, height));
+}
+
+static void abgr_to_argb(const uint8_t* src_abgr,
+                         int src_stride_abgr,
+                         uint8_t* dst_argb,
+                         int dst_stride_argb,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ABGRToARGB(src_abgr, src_stride_abgr, dst_argb,
+                                    dst_stride_argb, width, height));
+}
+
+static void bgra_to_argb(const uint8_t* src_bgra,
+                         int src_stride_bgra,
+                         uint8_t* dst_argb,
+                         int dst_stride_argb,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::BGRAToARGB(src_bgra, src_stride_bgra, dst_argb,
+                                    dst_stride_argb, width, height));
+}
+
+static void rgba_to_argb(const uint8_t* src_rgba,
+                         int src_stride_rgba,
+                         uint8_t* dst_argb,
+                         int dst_stride_argb,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::RGBAToARGB(src_rgba, src_stride_rgba, dst_argb,
+                                    dst_stride_argb, width, height));
+}
+
 }  // namespace livekit_ffi
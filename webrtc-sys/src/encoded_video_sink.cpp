@@ -0,0 +1,182 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/encoded_video_sink.h"
+
+#include <memory>
+#include <stdexcept>
+
+#include "api/make_ref_counted.h"
+#include "api/media_stream_interface.h"
+#include "livekit/media_stream_track.h"
+#include "webrtc-sys/src/encoded_video_sink.rs.h"
+
+namespace livekit_ffi {
+
+VideoCodecType ToVideoCodecType(webrtc::VideoCodecType codec) {
+  switch (codec) {
+    case webrtc::kVideoCodecVP8:
+      return VideoCodecType::VP8;
+    case webrtc::kVideoCodecVP9:
+      return VideoCodecType::VP9;
+    case webrtc::kVideoCodecAV1:
+      return VideoCodecType::AV1;
+    case webrtc::kVideoCodecH264:
+      return VideoCodecType::H264;
+    case webrtc::kVideoCodecH265:
+      return VideoCodecType::H265;
+    default:
+      return VideoCodecType::Generic;
+  }
+}
+
+EncodedVideoSink::EncodedVideoSink(
+    webrtc::scoped_refptr<NativeEncodedVideoSink> sink,
+    uint64_t session,
+    webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver)
+    : sink_(sink), session_(session), receiver_(receiver) {}
+
+EncodedVideoSink::~EncodedVideoSink() {
+  // The sink can't be detached from the receiver, it keeps forwarding frames
+  // to the decoder until the next EncodedVideoSink opens it
+  close();
+}
+
+void EncodedVideoSink::request_keyframe() const {
+  auto track = receiver_->track();
+  if (track && track->kind() == webrtc::MediaStreamTrackInterface::kVideoKind) {
+    static_cast<webrtc::VideoTrackInterface*>(track.get())
+        ->GetSource()
+        ->GenerateKeyFrame();
+  }
+}
+
+void EncodedVideoSink::close() const {
+  sink_->close(session_);
+}
+
+uint64_t NativeEncodedVideoSink::open(
+    rust::Box<EncodedVideoSinkWrapper> observer) {
+  webrtc::MutexLock lock(&mutex_);
+  if (observer_) {
+    return 0;
+  }
+  observer_ = std::move(observer);
+  return ++session_;
+}
+
+void NativeEncodedVideoSink::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  auto callback = callback_for(frame->GetSsrc());
+  if (!callback) {
+    return;
+  }
+
+  {
+    webrtc::MutexLock lock(&mutex_);
+    if (observer_) {
+      auto video_frame =
+          static_cast<webrtc::TransformableVideoFrameInterface*>(frame.get());
+      auto frame_metadata = video_frame->Metadata();
+
+      EncodedVideoFrameMetadata metadata{};
+      metadata.codec = ToVideoCodecType(frame_metadata.GetCodec());
+      metadata.is_keyframe = video_frame->IsKeyFrame();
+      metadata.rtp_timestamp = frame->GetTimestamp();
+      metadata.ssrc = frame->GetSsrc();
+      metadata.payload_type = frame->GetPayloadType();
+      metadata.width = frame_metadata.GetWidth();
+      metadata.height = frame_metadata.GetHeight();
+      metadata.spatial_index = frame_metadata.GetSpatialIndex();
+      metadata.temporal_index = frame_metadata.GetTemporalIndex();
+
+      auto data = frame->GetData();
+      (*observer_)->on_encoded_frame(
+          metadata, rust::Slice<const uint8_t>(data.data(), data.size()));
+    }
+  }
+
+  callback->OnTransformedFrame(std::move(frame));
+}
+
+void NativeEncodedVideoSink::RegisterTransformedFrameCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+void NativeEncodedVideoSink::RegisterTransformedFrameSinkCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_[ssrc] = callback;
+}
+
+void NativeEncodedVideoSink::UnregisterTransformedFrameCallback() {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = nullptr;
+}
+
+void NativeEncodedVideoSink::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_.erase(ssrc);
+}
+
+void NativeEncodedVideoSink::close(uint64_t session) {
+  webrtc::MutexLock lock(&mutex_);
+  // Closing twice must not stop the EncodedVideoSink opened in between
+  if (session == session_) {
+    observer_ = std::nullopt;
+  }
+}
+
+webrtc::scoped_refptr<webrtc::TransformedFrameCallback>
+NativeEncodedVideoSink::callback_for(uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  auto it = sink_callbacks_.find(ssrc);
+  if (it != sink_callbacks_.end()) {
+    return it->second;
+  }
+  return callback_;
+}
+
+std::shared_ptr<EncodedVideoSink> new_encoded_video_sink(
+    rust::Box<EncodedVideoSinkWrapper> observer,
+    std::shared_ptr<RtpReceiver> receiver) {
+  auto rtc_receiver = receiver->rtc_receiver();
+  auto transformer = receiver->track()->encoded_sink([&] {
+    auto sink = webrtc::make_ref_counted<NativeEncodedVideoSink>();
+    rtc_receiver->SetDepacketizerToDecoderFrameTransformer(sink);
+    return webrtc::scoped_refptr<webrtc::FrameTransformerInterface>(sink);
+  });
+  // Replacing a FrameCryptor would hand the ciphertext to the decoder
+  if (!transformer) {
+    throw std::runtime_error(
+        "the receiver already has a frame transformer, e.g. for E2EE");
+  }
+
+  auto sink = webrtc::scoped_refptr<NativeEncodedVideoSink>(
+      static_cast<NativeEncodedVideoSink*>(transformer.get()));
+  auto session = sink->open(std::move(observer));
+  if (!session) {
+    throw std::runtime_error(
+        "the encoded frames of this receiver are already observed");
+  }
+  return std::make_shared<EncodedVideoSink>(sink, session, rtc_receiver);
+}
+
+}  // namespace livekit_ffi
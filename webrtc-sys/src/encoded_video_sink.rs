@@ -0,0 +1,97 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum VideoCodecType {
+        Generic = 0,
+        VP8,
+        VP9,
+        AV1,
+        H264,
+        H265,
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedVideoFrameMetadata {
+        pub codec: VideoCodecType,
+        pub is_keyframe: bool,
+        pub rtp_timestamp: u32,
+        pub ssrc: u32,
+        pub payload_type: u8,
+        pub width: u16,
+        pub height: u16,
+        /// -1 when the codec has no spatial layers
+        pub spatial_index: i32,
+        /// -1 when the codec has no temporal layers
+        pub temporal_index: i32,
+    }
+
+    extern "C++" {
+        include!("livekit/rtp_receiver.h");
+
+        type RtpReceiver = crate::rtp_receiver::ffi::RtpReceiver;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/encoded_video_sink.h");
+
+        type EncodedVideoSink;
+
+        fn new_encoded_video_sink(
+            observer: Box<EncodedVideoSinkWrapper>,
+            receiver: SharedPtr<RtpReceiver>,
+        ) -> Result<SharedPtr<EncodedVideoSink>>;
+
+        fn request_keyframe(self: &EncodedVideoSink);
+        fn close(self: &EncodedVideoSink);
+    }
+
+    extern "Rust" {
+        type EncodedVideoSinkWrapper;
+
+        fn on_encoded_frame(
+            self: &EncodedVideoSinkWrapper,
+            metadata: &EncodedVideoFrameMetadata,
+            data: &[u8],
+        );
+    }
+} // namespace livekit_ffi
+
+impl_thread_safety!(ffi::EncodedVideoSink, Send + Sync);
+
+pub trait EncodedVideoSink: Send + Sync {
+    fn on_encoded_frame(&self, metadata: &ffi::EncodedVideoFrameMetadata, data: &[u8]);
+}
+
+pub struct EncodedVideoSinkWrapper {
+    observer: Arc<dyn EncodedVideoSink>,
+}
+
+impl EncodedVideoSinkWrapper {
+    pub fn new(observer: Arc<dyn EncodedVideoSink>) -> Self {
+        Self { observer }
+    }
+
+    fn on_encoded_frame(&self, metadata: &ffi::EncodedVideoFrameMetadata, data: &[u8]) {
+        self.observer.on_encoded_frame(metadata, data);
+    }
+}
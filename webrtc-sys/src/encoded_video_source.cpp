@@ -0,0 +1,253 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/encoded_video_source.h"
+
+#include <algorithm>
+
+#include "absl/strings/match.h"
+
+#include "api/make_ref_counted.h"
+#include "api/video/encoded_image.h"
+#include "api/video/i420_buffer.h"
+#include "media/base/media_constants.h"
+#include "modules/video_coding/include/video_codec_interface.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "rtc_base/logging.h"
+#include "webrtc-sys/src/encoded_video_source.rs.h"
+
+namespace livekit_ffi {
+
+void KeyFrameRequestHandler::set_observer(
+    rust::Box<KeyFrameRequestObserverWrapper> observer) {
+  webrtc::MutexLock lock(&mutex_);
+  observer_ = std::move(observer);
+}
+
+void KeyFrameRequestHandler::request_keyframe() {
+  webrtc::MutexLock lock(&mutex_);
+  if (observer_) {
+    (*observer_)->on_keyframe_request();
+  }
+}
+
+EncodedFrameBuffer::EncodedFrameBuffer(
+    EncodedVideoCodec codec,
+    std::vector<uint8_t> data,
+    bool is_keyframe,
+    int width,
+    int height,
+    std::shared_ptr<KeyFrameRequestHandler> keyframe_handler)
+    : codec_(codec),
+      data_(std::move(data)),
+      is_keyframe_(is_keyframe),
+      width_(width),
+      height_(height),
+      keyframe_handler_(std::move(keyframe_handler)) {}
+
+webrtc::scoped_refptr<webrtc::I420BufferInterface>
+EncodedFrameBuffer::ToI420() {
+  auto buffer = webrtc::I420Buffer::Create(width_, height_);
+  webrtc::I420Buffer::SetBlack(buffer.get());
+  return buffer;
+}
+
+EncodedVideoTrackSource::EncodedVideoTrackSource(
+    const VideoResolution& resolution,
+    EncodedVideoCodec codec)
+    : codec_(codec),
+      source_(std::make_shared<VideoTrackSource>(resolution)),
+      keyframe_handler_(std::make_shared<KeyFrameRequestHandler>()) {}
+
+bool EncodedVideoTrackSource::capture_frame(
+    rust::Slice<const uint8_t> data,
+    const EncodedFrameInfo& info) const {
+  VideoResolution resolution = source_->video_resolution();
+  int width = info.width != 0 ? info.width : resolution.width;
+  int height = info.height != 0 ? info.height : resolution.height;
+  if (width == 0 || height == 0) {
+    RTC_LOG(LS_ERROR) << "unknown resolution for encoded frame";
+    return false;
+  }
+
+  auto buffer = webrtc::make_ref_counted<EncodedFrameBuffer>(
+      codec_, std::vector<uint8_t>(data.begin(), data.end()), info.is_keyframe,
+      width, height, keyframe_handler_);
+
+  return source_->get()->on_captured_encoded_frame(
+      webrtc::VideoFrame::Builder()
+          .set_video_frame_buffer(buffer)
+          .set_timestamp_us(info.timestamp_us)
+          .build());
+}
+
+void EncodedVideoTrackSource::set_keyframe_request_observer(
+    rust::Box<KeyFrameRequestObserverWrapper> observer) const {
+  keyframe_handler_->set_observer(std::move(observer));
+}
+
+std::shared_ptr<EncodedVideoTrackSource> new_encoded_video_track_source(
+    const VideoResolution& resolution,
+    EncodedVideoCodec codec) {
+  return std::make_shared<EncodedVideoTrackSource>(resolution, codec);
+}
+
+namespace {
+
+std::optional<EncodedVideoCodec> CodecFromFormat(
+    const webrtc::SdpVideoFormat& format) {
+  if (absl::EqualsIgnoreCase(format.name, cricket::kH264CodecName)) {
+    return EncodedVideoCodec::H264;
+  }
+  if (absl::EqualsIgnoreCase(format.name, cricket::kH265CodecName)) {
+    return EncodedVideoCodec::H265;
+  }
+  return std::nullopt;
+}
+
+}  // namespace
+
+PassthroughVideoEncoder::PassthroughVideoEncoder(
+    const webrtc::SdpVideoFormat& format,
+    std::unique_ptr<webrtc::VideoEncoder> fallback)
+    : format_(format), fallback_(std::move(fallback)) {}
+
+bool PassthroughVideoEncoder::IsSupported(
+    const webrtc::SdpVideoFormat& format) {
+  return CodecFromFormat(format).has_value();
+}
+
+void PassthroughVideoEncoder::SetFecControllerOverride(
+    webrtc::FecControllerOverride* fec_controller_override) {
+  if (fallback_) {
+    fallback_->SetFecControllerOverride(fec_controller_override);
+  }
+}
+
+int PassthroughVideoEncoder::InitEncode(
+    const webrtc::VideoCodec* codec_settings,
+    const webrtc::VideoEncoder::Settings& settings) {
+  return fallback_ ? fallback_->InitEncode(codec_settings, settings)
+                   : WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t PassthroughVideoEncoder::RegisterEncodeCompleteCallback(
+    webrtc::EncodedImageCallback* callback) {
+  callback_ = callback;
+  return fallback_ ? fallback_->RegisterEncodeCompleteCallback(callback)
+                   : WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t PassthroughVideoEncoder::Release() {
+  return fallback_ ? fallback_->Release() : WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t PassthroughVideoEncoder::Encode(
+    const webrtc::VideoFrame& frame,
+    const std::vector<webrtc::VideoFrameType>* frame_types) {
+  auto native = frame.video_frame_buffer();
+  auto* encoded = native->type() == webrtc::VideoFrameBuffer::Type::kNative
+                      ? dynamic_cast<EncodedFrameBuffer*>(native.get())
+                      : nullptr;
+  if (!encoded) {
+    if (!fallback_) {
+      return WEBRTC_VIDEO_CODEC_ERROR;
+    }
+    // Native buffers are accepted for the encoded frames, convert the others
+    // for a fallback encoder that can't take them
+    if (native->type() == webrtc::VideoFrameBuffer::Type::kNative &&
+        !fallback_->GetEncoderInfo().supports_native_handle) {
+      auto i420 = native->ToI420();
+      if (!i420) {
+        return WEBRTC_VIDEO_CODEC_ERROR;
+      }
+      webrtc::VideoFrame converted(frame);
+      converted.set_video_frame_buffer(i420);
+      return fallback_->Encode(converted, frame_types);
+    }
+    return fallback_->Encode(frame, frame_types);
+  }
+
+  passthrough_ = true;
+
+  if (!callback_) {
+    return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+  }
+
+  if (CodecFromFormat(format_) != encoded->codec()) {
+    RTC_LOG(LS_ERROR) << "encoded frame doesn't match the negotiated codec "
+                      << format_.name;
+    return WEBRTC_VIDEO_CODEC_ERROR;
+  }
+
+  bool keyframe_requested =
+      frame_types &&
+      std::find(frame_types->begin(), frame_types->end(),
+                webrtc::VideoFrameType::kVideoFrameKey) != frame_types->end();
+  if (keyframe_requested && !encoded->is_keyframe()) {
+    encoded->request_keyframe();
+  }
+
+  webrtc::EncodedImage image;
+  image.SetEncodedData(webrtc::EncodedImageBuffer::Create(
+      encoded->data().data(), encoded->data().size()));
+  image._encodedWidth = encoded->width();
+  image._encodedHeight = encoded->height();
+  image.SetRtpTimestamp(frame.rtp_timestamp());
+  image.capture_time_ms_ = frame.render_time_ms();
+  image.rotation_ = frame.rotation();
+  image._frameType = encoded->is_keyframe()
+                         ? webrtc::VideoFrameType::kVideoFrameKey
+                         : webrtc::VideoFrameType::kVideoFrameDelta;
+
+  webrtc::CodecSpecificInfo codec_info;
+  if (encoded->codec() == EncodedVideoCodec::H264) {
+    codec_info.codecType = webrtc::kVideoCodecH264;
+    codec_info.codecSpecific.H264.packetization_mode =
+        webrtc::H264PacketizationMode::NonInterleaved;
+  } else {
+    codec_info.codecType = webrtc::kVideoCodecH265;
+  }
+
+  auto result = callback_->OnEncodedImage(image, &codec_info);
+  return result.error == webrtc::EncodedImageCallback::Result::OK
+             ? WEBRTC_VIDEO_CODEC_OK
+             : WEBRTC_VIDEO_CODEC_ERROR;
+}
+
+void PassthroughVideoEncoder::SetRates(
+    const RateControlParameters& parameters) {
+  // The bitrate of encoded frames is controlled by their producer
+  if (fallback_) {
+    fallback_->SetRates(parameters);
+  }
+}
+
+webrtc::VideoEncoder::EncoderInfo PassthroughVideoEncoder::GetEncoderInfo()
+    const {
+  EncoderInfo info = fallback_ ? fallback_->GetEncoderInfo() : EncoderInfo();
+  // Lets the encoded frames reach Encode() as native buffers
+  info.supports_native_handle = true;
+  if (passthrough_) {
+    // Encoded frames must reach the encoder untouched: no scaling nor dropping
+    info.implementation_name = "Passthrough";
+    info.has_trusted_rate_controller = true;
+    info.scaling_settings = VideoEncoder::ScalingSettings::kOff;
+  }
+  return info;
+}
+
+}  // namespace livekit_ffi
@@ -0,0 +1,92 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum EncodedVideoCodec {
+        H264 = 0,
+        H265,
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedFrameInfo {
+        pub is_keyframe: bool,
+        pub timestamp_us: i64,
+        /// 0 to use the resolution of the source, i.e. of the previous frame
+        pub width: u32,
+        pub height: u32,
+    }
+
+    extern "C++" {
+        include!("livekit/video_track.h");
+
+        type VideoTrackSource = crate::video_track::ffi::VideoTrackSource;
+        type VideoResolution = crate::video_track::ffi::VideoResolution;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/encoded_video_source.h");
+
+        type EncodedVideoTrackSource;
+
+        fn new_encoded_video_track_source(
+            resolution: &VideoResolution,
+            codec: EncodedVideoCodec,
+        ) -> SharedPtr<EncodedVideoTrackSource>;
+
+        fn video_source(self: &EncodedVideoTrackSource) -> SharedPtr<VideoTrackSource>;
+        fn capture_frame(
+            self: &EncodedVideoTrackSource,
+            data: &[u8],
+            info: &EncodedFrameInfo,
+        ) -> bool;
+        fn set_keyframe_request_observer(
+            self: &EncodedVideoTrackSource,
+            observer: Box<KeyFrameRequestObserverWrapper>,
+        );
+    }
+
+    extern "Rust" {
+        type KeyFrameRequestObserverWrapper;
+
+        fn on_keyframe_request(self: &KeyFrameRequestObserverWrapper);
+    }
+} // namespace livekit_ffi
+
+impl_thread_safety!(ffi::EncodedVideoTrackSource, Send + Sync);
+
+pub trait KeyFrameRequestObserver: Send + Sync {
+    fn on_keyframe_request(&self);
+}
+
+pub struct KeyFrameRequestObserverWrapper {
+    observer: Arc<dyn KeyFrameRequestObserver>,
+}
+
+impl KeyFrameRequestObserverWrapper {
+    pub fn new(observer: Arc<dyn KeyFrameRequestObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_keyframe_request(&self) {
+        self.observer.on_keyframe_request();
+    }
+}
@@ -16,10 +16,13 @@
 
 #include "livekit/frame_cryptor.h"
 
+#include <algorithm>
 #include <memory>
+#include <stdexcept>
 
 #include "absl/types/optional.h"
 #include "api/make_ref_counted.h"
+#include "livekit/media_stream_track.h"
 #include "livekit/peer_connection.h"
 #include "livekit/peer_connection_factory.h"
 #include "livekit/webrtc.h"
@@ -96,7 +99,9 @@ FrameCryptor::FrameCryptor(
       new webrtc::FrameCryptorTransformer(rtc_runtime->signaling_thread(),
                                           participant_id, mediaType, algorithm,
                                           key_provider_));
-  receiver->SetDepacketizerToDecoderFrameTransformer(e2ee_transformer_);
+  key_index_tracker_ = rtc::make_ref_counted<FrameKeyIndexTracker>(
+      e2ee_transformer_, key_provider_);
+  receiver->SetDepacketizerToDecoderFrameTransformer(key_index_tracker_);
   e2ee_transformer_->SetEnabled(false);
 }
 
@@ -109,6 +114,9 @@ FrameCryptor::~FrameCryptor() {
 void FrameCryptor::register_observer(
     rust::Box<RtcFrameCryptorObserverWrapper> observer) const {
   webrtc::MutexLock lock(&mutex_);
+  if (observer_) {
+    observer_->detach();
+  }
   observer_ = rtc::make_ref_counted<NativeFrameCryptorObserver>(
       std::move(observer), this);
   e2ee_transformer_->RegisterFrameCryptorTransformerObserver(observer_);
@@ -116,6 +124,9 @@ void FrameCryptor::register_observer(
 
 void FrameCryptor::unregister_observer() const {
   webrtc::MutexLock lock(&mutex_);
+  if (observer_) {
+    observer_->detach();
+  }
   observer_ = nullptr;
   e2ee_transformer_->UnRegisterFrameCryptorTransformerObserver();
 }
@@ -130,8 +141,18 @@ NativeFrameCryptorObserver::~NativeFrameCryptorObserver() {}
 void NativeFrameCryptorObserver::OnFrameCryptionStateChanged(
     const std::string participant_id,
     webrtc::FrameCryptionState state) {
+  webrtc::MutexLock lock(&mutex_);
+  if (!fc_) {
+    return;
+  }
   observer_->on_frame_cryption_state_change(
-      participant_id, static_cast<FrameCryptionState>(state));
+      participant_id, static_cast<FrameCryptionState>(state),
+      fc_->frame_key_index());
+}
+
+void NativeFrameCryptorObserver::detach() {
+  webrtc::MutexLock lock(&mutex_);
+  fc_ = nullptr;
 }
 
 void FrameCryptor::set_enabled(bool enabled) const {
@@ -154,6 +175,58 @@ int32_t FrameCryptor::key_index() const {
   return e2ee_transformer_->key_index();
 }
 
+int32_t FrameCryptor::frame_key_index() const {
+  // Called by the observer, so it must not take the lock held to detach it
+  if (key_index_tracker_) {
+    return key_index_tracker_->last_key_index();
+  }
+  return e2ee_transformer_->key_index();
+}
+
+FrameKeyIndexTracker::FrameKeyIndexTracker(
+    webrtc::scoped_refptr<webrtc::FrameCryptorTransformer> transformer,
+    webrtc::scoped_refptr<webrtc::KeyProvider> key_provider)
+    : transformer_(transformer), key_provider_(key_provider) {}
+
+void FrameKeyIndexTracker::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  // The cryptor decrypts every frame while enabled, except the unencrypted ones
+  // ending with the SIF trailer, with the key index in their last byte
+  auto data = frame->GetData();
+  if (transformer_->enabled() && !data.empty() && !has_sif_trailer(data)) {
+    last_key_index_.store(data[data.size() - 1]);
+  }
+  transformer_->Transform(std::move(frame));
+}
+
+bool FrameKeyIndexTracker::has_sif_trailer(
+    rtc::ArrayView<const uint8_t> data) const {
+  const auto& trailer = key_provider_->options().uncrypted_magic_bytes;
+  return !trailer.empty() && data.size() >= trailer.size() &&
+         std::equal(trailer.begin(), trailer.end(),
+                    data.end() - trailer.size());
+}
+
+void FrameKeyIndexTracker::RegisterTransformedFrameCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  transformer_->RegisterTransformedFrameCallback(callback);
+}
+
+void FrameKeyIndexTracker::RegisterTransformedFrameSinkCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  transformer_->RegisterTransformedFrameSinkCallback(callback, ssrc);
+}
+
+void FrameKeyIndexTracker::UnregisterTransformedFrameCallback() {
+  transformer_->UnregisterTransformedFrameCallback();
+}
+
+void FrameKeyIndexTracker::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  transformer_->UnregisterTransformedFrameSinkCallback(ssrc);
+}
+
 DataPacketCryptor::DataPacketCryptor(webrtc::FrameCryptorTransformer::Algorithm algorithm,
                  webrtc::scoped_refptr<webrtc::KeyProvider> key_provider)
     : data_packet_cryptor_(
@@ -242,6 +315,11 @@ std::shared_ptr<FrameCryptor> new_frame_cryptor_for_rtp_receiver(
     Algorithm algorithm,
     std::shared_ptr<KeyProvider> key_provider,
     std::shared_ptr<RtpReceiver> receiver) {
+  // The transformer would replace the sink of a NativeEncodedVideoStream
+  if (!receiver->track()->claim_frame_transformer()) {
+    throw std::runtime_error(
+        "the encoded frames of the receiver are observed by another sink");
+  }
   return std::make_shared<FrameCryptor>(
       peer_factory->rtc_runtime(),
       std::string(participant_id.data(), participant_id.size()),
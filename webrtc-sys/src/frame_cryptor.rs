@@ -68,6 +68,8 @@ pub mod ffi {
 
         pub fn set_sif_trailer(&self, trailer: Vec<u8>);
 
+        pub fn reset_key_status(self: &KeyProvider, participant_id: String);
+
         pub fn set_key(
             self: &KeyProvider,
             participant_id: String,
@@ -114,7 +116,7 @@ pub mod ffi {
             algorithm: Algorithm,
             key_provider: SharedPtr<KeyProvider>,
             receiver: SharedPtr<RtpReceiver>,
-        ) -> SharedPtr<FrameCryptor>;
+        ) -> Result<SharedPtr<FrameCryptor>>;
 
         pub fn set_enabled(self: &FrameCryptor, enabled: bool);
 
@@ -165,6 +167,7 @@ pub mod ffi {
             self: &RtcFrameCryptorObserverWrapper,
             participant_id: String,
             state: FrameCryptionState,
+            key_index: i32,
         );
     }
 } // namespace livekit_ffi
@@ -179,7 +182,12 @@ use ffi::FrameCryptionState;
 pub use ffi::EncryptedPacket;
 
 pub trait RtcFrameCryptorObserver: Send + Sync {
-    fn on_frame_cryption_state_change(&self, participant_id: String, state: FrameCryptionState);
+    fn on_frame_cryption_state_change(
+        &self,
+        participant_id: String,
+        state: FrameCryptionState,
+        key_index: i32,
+    );
 }
 
 pub struct RtcFrameCryptorObserverWrapper {
@@ -195,8 +203,9 @@ impl RtcFrameCryptorObserverWrapper {
         self: &RtcFrameCryptorObserverWrapper,
         participant_id: String,
         state: FrameCryptionState,
+        key_index: i32,
     ) {
-        self.observer.on_frame_cryption_state_change(participant_id, state);
+        self.observer.on_frame_cryption_state_change(participant_id, state, key_index);
     }
 }
 
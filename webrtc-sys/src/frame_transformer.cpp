@@ -0,0 +1,172 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/frame_transformer.h"
+
+#include <memory>
+#include <stdexcept>
+
+#include "api/make_ref_counted.h"
+#include "livekit/media_stream_track.h"
+#include "webrtc-sys/src/frame_transformer.rs.h"
+
+namespace livekit_ffi {
+
+FrameDirection ToFrameDirection(
+    webrtc::TransformableFrameInterface::Direction direction) {
+  switch (direction) {
+    case webrtc::TransformableFrameInterface::Direction::kReceiver:
+      return FrameDirection::Receiver;
+    case webrtc::TransformableFrameInterface::Direction::kSender:
+      return FrameDirection::Sender;
+    default:
+      return FrameDirection::Unknown;
+  }
+}
+
+FrameTransformer::FrameTransformer(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender)
+    : sender_(sender) {
+  bool is_audio = sender->track()->kind() == "audio";
+  transformer_ = rtc::make_ref_counted<NativeFrameTransformer>(
+      std::move(transformer), is_audio);
+  sender->SetEncoderToPacketizerFrameTransformer(transformer_);
+}
+
+FrameTransformer::FrameTransformer(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver)
+    : receiver_(receiver) {
+  bool is_audio = receiver->track()->kind() == "audio";
+  transformer_ = rtc::make_ref_counted<NativeFrameTransformer>(
+      std::move(transformer), is_audio);
+  receiver->SetDepacketizerToDecoderFrameTransformer(transformer_);
+}
+
+void FrameTransformer::set_enabled(bool enabled) const {
+  transformer_->set_enabled(enabled);
+}
+
+bool FrameTransformer::enabled() const {
+  return transformer_->enabled();
+}
+
+NativeFrameTransformer::NativeFrameTransformer(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    bool is_audio)
+    : transformer_(std::move(transformer)), is_audio_(is_audio) {}
+
+void NativeFrameTransformer::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  auto callback = callback_for(frame->GetSsrc());
+  if (!callback) {
+    return;
+  }
+
+  if (!enabled()) {
+    callback->OnTransformedFrame(std::move(frame));
+    return;
+  }
+
+  TransformableFrameMetadata metadata{};
+  metadata.direction = ToFrameDirection(frame->GetDirection());
+  metadata.is_audio = is_audio_;
+  metadata.ssrc = frame->GetSsrc();
+  metadata.payload_type = frame->GetPayloadType();
+  metadata.rtp_timestamp = frame->GetTimestamp();
+  metadata.is_key_frame =
+      !is_audio_ &&
+      static_cast<webrtc::TransformableVideoFrameInterface*>(frame.get())
+          ->IsKeyFrame();
+
+  auto data = frame->GetData();
+  rust::Vec<uint8_t> output;
+  if (!transformer_->transform(
+          metadata, rust::Slice<const uint8_t>(data.data(), data.size()),
+          output)) {
+    return;  // The transformer dropped the frame
+  }
+
+  frame->SetData(
+      rtc::ArrayView<const uint8_t>(output.data(), output.size()));
+  callback->OnTransformedFrame(std::move(frame));
+}
+
+void NativeFrameTransformer::RegisterTransformedFrameCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+void NativeFrameTransformer::RegisterTransformedFrameSinkCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_[ssrc] = callback;
+}
+
+void NativeFrameTransformer::UnregisterTransformedFrameCallback() {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = nullptr;
+}
+
+void NativeFrameTransformer::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_.erase(ssrc);
+}
+
+void NativeFrameTransformer::set_enabled(bool enabled) {
+  webrtc::MutexLock lock(&mutex_);
+  enabled_ = enabled;
+}
+
+bool NativeFrameTransformer::enabled() const {
+  webrtc::MutexLock lock(&mutex_);
+  return enabled_;
+}
+
+webrtc::scoped_refptr<webrtc::TransformedFrameCallback>
+NativeFrameTransformer::callback_for(uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  auto it = sink_callbacks_.find(ssrc);
+  if (it != sink_callbacks_.end()) {
+    return it->second;
+  }
+  return callback_;
+}
+
+std::shared_ptr<FrameTransformer> new_frame_transformer_for_rtp_sender(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    std::shared_ptr<RtpSender> sender) {
+  return std::make_shared<FrameTransformer>(std::move(transformer),
+                                            sender->rtc_sender());
+}
+
+std::shared_ptr<FrameTransformer> new_frame_transformer_for_rtp_receiver(
+    rust::Box<RtcFrameTransformerWrapper> transformer,
+    std::shared_ptr<RtpReceiver> receiver) {
+  // The transformer would replace the sink of a NativeEncodedVideoStream
+  if (!receiver->track()->claim_frame_transformer()) {
+    throw std::runtime_error(
+        "the encoded frames of the receiver are observed by another sink");
+  }
+  return std::make_shared<FrameTransformer>(std::move(transformer),
+                                            receiver->rtc_receiver());
+}
+
+}  // namespace livekit_ffi
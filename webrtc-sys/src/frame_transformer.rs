@@ -0,0 +1,106 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum FrameDirection {
+        Unknown = 0,
+        Receiver,
+        Sender,
+    }
+
+    #[derive(Debug)]
+    pub struct TransformableFrameMetadata {
+        pub direction: FrameDirection,
+        pub is_audio: bool,
+        pub ssrc: u32,
+        pub payload_type: u8,
+        pub rtp_timestamp: u32,
+        pub is_key_frame: bool,
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/frame_transformer.h");
+        include!("livekit/rtp_sender.h");
+        include!("livekit/rtp_receiver.h");
+
+        type RtpSender = crate::rtp_sender::ffi::RtpSender;
+        type RtpReceiver = crate::rtp_receiver::ffi::RtpReceiver;
+
+        pub type FrameTransformer;
+
+        pub fn new_frame_transformer_for_rtp_sender(
+            transformer: Box<RtcFrameTransformerWrapper>,
+            sender: SharedPtr<RtpSender>,
+        ) -> SharedPtr<FrameTransformer>;
+
+        pub fn new_frame_transformer_for_rtp_receiver(
+            transformer: Box<RtcFrameTransformerWrapper>,
+            receiver: SharedPtr<RtpReceiver>,
+        ) -> Result<SharedPtr<FrameTransformer>>;
+
+        pub fn set_enabled(self: &FrameTransformer, enabled: bool);
+
+        pub fn enabled(self: &FrameTransformer) -> bool;
+    }
+
+    extern "Rust" {
+        type RtcFrameTransformerWrapper;
+
+        fn transform(
+            self: &RtcFrameTransformerWrapper,
+            metadata: &TransformableFrameMetadata,
+            payload: &[u8],
+            output: &mut Vec<u8>,
+        ) -> bool;
+    }
+} // namespace livekit_ffi
+
+impl_thread_safety!(ffi::FrameTransformer, Send + Sync);
+
+pub trait RtcFrameTransformer: Send + Sync {
+    /// Writes the transformed payload into `output`, returns false to drop the frame
+    fn transform(
+        &self,
+        metadata: &ffi::TransformableFrameMetadata,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> bool;
+}
+
+pub struct RtcFrameTransformerWrapper {
+    transformer: Arc<dyn RtcFrameTransformer>,
+}
+
+impl RtcFrameTransformerWrapper {
+    pub fn new(transformer: Arc<dyn RtcFrameTransformer>) -> Self {
+        Self { transformer }
+    }
+
+    fn transform(
+        self: &RtcFrameTransformerWrapper,
+        metadata: &ffi::TransformableFrameMetadata,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> bool {
+        self.transformer.transform(metadata, payload, output)
+    }
+}
@@ -50,4 +50,11 @@ for dep in "${vaapi_deps[@]}"
 do
   generate_implib "vaapi" ${dep} "x86_64-linux-gnu"
   generate_implib "vaapi" ${dep} "aarch64-linux-gnu"
-done
\ No newline at end of file
+done
+
+# Rockchip MPP is only available on ARM64 boards
+rockchip_mpp_deps=("librockchip_mpp")
+for dep in "${rockchip_mpp_deps[@]}"
+do
+  generate_implib "rockchip_mpp" ${dep} "aarch64-linux-gnu"
+done
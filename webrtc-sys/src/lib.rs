@@ -22,7 +22,10 @@ pub mod candidate;
 pub mod data_channel;
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 pub mod desktop_capturer;
+pub mod encoded_video_sink;
+pub mod encoded_video_source;
 pub mod frame_cryptor;
+pub mod frame_transformer;
 pub mod helper;
 pub mod jsep;
 pub mod media_stream;
@@ -35,6 +38,9 @@ pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
+pub mod video_codec_backend;
+pub mod video_decoder;
+pub mod video_encoder;
 pub mod video_frame;
 pub mod video_frame_buffer;
 pub mod video_track;
@@ -19,6 +19,7 @@
 #include <memory>
 #include <utility>
 
+#include "absl/strings/match.h"
 #include "api/audio_codecs/builtin_audio_decoder_factory.h"
 #include "api/audio_codecs/builtin_audio_encoder_factory.h"
 #include "api/audio/builtin_audio_processing_builder.h"
@@ -37,6 +38,7 @@
 #include "livekit/rtc_error.h"
 #include "livekit/rtp_parameters.h"
 #include "livekit/video_decoder_factory.h"
+#include "livekit/video_encoder.h"
 #include "livekit/video_encoder_factory.h"
 #include "livekit/webrtc.h"
 #include "rtc_base/thread.h"
@@ -49,7 +51,7 @@ class PeerConnectionObserver;
 
 PeerConnectionFactory::PeerConnectionFactory(
     std::shared_ptr<RtcRuntime> rtc_runtime)
-    : rtc_runtime_(rtc_runtime) {
+    : rtc_runtime_(rtc_runtime), env_(webrtc::CreateEnvironment()) {
   RTC_LOG(LS_VERBOSE) << "PeerConnectionFactory::PeerConnectionFactory()";
 
   webrtc::PeerConnectionFactoryDependencies dependencies;
@@ -68,10 +70,14 @@ PeerConnectionFactory::PeerConnectionFactory(
 
   dependencies.adm = audio_device_;
 
-  dependencies.video_encoder_factory =
-      std::move(std::make_unique<livekit_ffi::VideoEncoderFactory>());
-  dependencies.video_decoder_factory =
-      std::move(std::make_unique<livekit_ffi::VideoDecoderFactory>());
+  auto video_encoder_factory =
+      std::make_unique<livekit_ffi::VideoEncoderFactory>();
+  auto video_decoder_factory =
+      std::make_unique<livekit_ffi::VideoDecoderFactory>();
+  video_encoder_factory_ = video_encoder_factory.get();
+  video_decoder_factory_ = video_decoder_factory.get();
+  dependencies.video_encoder_factory = std::move(video_encoder_factory);
+  dependencies.video_decoder_factory = std::move(video_decoder_factory);
   dependencies.audio_encoder_factory = webrtc::CreateBuiltinAudioEncoderFactory();
   dependencies.audio_decoder_factory = webrtc::CreateBuiltinAudioDecoderFactory();
   dependencies.audio_processing = webrtc::BuiltinAudioProcessingBuilder()
@@ -140,6 +146,94 @@ RtpCapabilities PeerConnectionFactory::rtp_receiver_capabilities(
       static_cast<webrtc::MediaType>(type)));
 }
 
+rust::Vec<VideoCodecImplementation>
+PeerConnectionFactory::video_encoder_implementations() const {
+  rust::Vec<VideoCodecImplementation> implementations;
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : video_encoder_factory_->GetSupportedFormats()) {
+    // The first factory supporting a format is the one used
+    if (format.IsCodecInList(formats)) {
+      continue;
+    }
+    formats.push_back(format);
+
+    auto info = video_encoder_factory_->GetEncoderInfo(env_, format);
+    if (!info) {
+      continue;
+    }
+    auto support =
+        video_encoder_factory_->QueryCodecSupport(format, std::nullopt);
+    implementations.push_back(VideoCodecImplementation{
+        to_rust_format(format), info->implementation_name,
+        info->is_hardware_accelerated, support.is_power_efficient});
+  }
+  return implementations;
+}
+
+bool PeerConnectionFactory::has_video_encoder(rust::Str codec_name) const {
+  // Formats without an available backend are already filtered out, no encoder
+  // needs to be created
+  std::string name(codec_name.data(), codec_name.size());
+  for (const auto& format : video_encoder_factory_->GetSupportedFormats()) {
+    if (absl::EqualsIgnoreCase(format.name, name)) {
+      return true;
+    }
+  }
+  return false;
+}
+
+rust::Vec<VideoCodecImplementation>
+PeerConnectionFactory::video_decoder_implementations() const {
+  rust::Vec<VideoCodecImplementation> implementations;
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : video_decoder_factory_->GetSupportedFormats()) {
+    if (format.IsCodecInList(formats)) {
+      continue;
+    }
+    formats.push_back(format);
+
+    auto decoder = video_decoder_factory_->Create(env_, format);
+    if (!decoder) {
+      continue;
+    }
+    auto info = decoder->GetDecoderInfo();
+    auto support = video_decoder_factory_->QueryCodecSupport(format, false);
+    implementations.push_back(VideoCodecImplementation{
+        to_rust_format(format), info.implementation_name,
+        info.is_hardware_accelerated, support.is_power_efficient});
+  }
+  return implementations;
+}
+
+rust::Vec<VideoCodecBackendEntry> PeerConnectionFactory::video_codec_backends()
+    const {
+  rust::Vec<VideoCodecBackendEntry> entries;
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : video_encoder_factory_->GetSupportedFormats()) {
+    if (format.IsCodecInList(formats)) {
+      continue;
+    }
+    formats.push_back(format);
+    for (auto backend : video_encoder_factory_->Backends(format)) {
+      entries.push_back(
+          VideoCodecBackendEntry{to_rust_format(format), true, backend});
+    }
+  }
+
+  formats.clear();
+  for (const auto& format : video_decoder_factory_->GetSupportedFormats()) {
+    if (format.IsCodecInList(formats)) {
+      continue;
+    }
+    formats.push_back(format);
+    for (auto backend : video_decoder_factory_->Backends(format)) {
+      entries.push_back(
+          VideoCodecBackendEntry{to_rust_format(format), false, backend});
+    }
+  }
+  return entries;
+}
+
 std::shared_ptr<PeerConnectionFactory> create_peer_connection_factory() {
   return std::make_shared<PeerConnectionFactory>(RtcRuntime::create());
 }
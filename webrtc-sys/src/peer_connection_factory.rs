@@ -36,6 +36,15 @@ pub mod ffi {
         estimated_disconnected_time_ms: i64,
     }
 
+    /// Codec implementation used by the factory for a format
+    #[derive(Debug)]
+    pub struct VideoCodecImplementation {
+        pub format: SdpVideoFormat,
+        pub implementation_name: String,
+        pub is_hardware_accelerated: bool,
+        pub is_power_efficient: bool,
+    }
+
     extern "C++" {
         include!("livekit/rtp_parameters.h");
         include!("livekit/rtc_error.h");
@@ -50,6 +59,8 @@ pub mod ffi {
         include!("livekit/webrtc.h");
         include!("livekit/peer_connection.h");
         include!("livekit/audio_track.h");
+        include!("livekit/video_encoder.h");
+        include!("livekit/video_codec_backend.h");
 
         type RtcConfiguration = crate::peer_connection::ffi::RtcConfiguration;
         type PeerConnectionState = crate::peer_connection::ffi::PeerConnectionState;
@@ -79,6 +90,8 @@ pub mod ffi {
         type MediaStreamTrack = crate::media_stream::ffi::MediaStreamTrack;
         type SessionDescription = crate::jsep::ffi::SessionDescription;
         type MediaType = crate::webrtc::ffi::MediaType;
+        type SdpVideoFormat = crate::video_encoder::ffi::SdpVideoFormat;
+        type VideoCodecBackendEntry = crate::video_codec_backend::ffi::VideoCodecBackendEntry;
     }
 
     unsafe extern "C++" {
@@ -116,6 +129,18 @@ pub mod ffi {
             self: &PeerConnectionFactory,
             kind: MediaType,
         ) -> RtpCapabilities;
+
+        fn video_encoder_implementations(
+            self: &PeerConnectionFactory,
+        ) -> Vec<VideoCodecImplementation>;
+
+        fn has_video_encoder(self: &PeerConnectionFactory, codec_name: &str) -> bool;
+
+        fn video_decoder_implementations(
+            self: &PeerConnectionFactory,
+        ) -> Vec<VideoCodecImplementation>;
+
+        fn video_codec_backends(self: &PeerConnectionFactory) -> Vec<VideoCodecBackendEntry>;
     }
 
     extern "Rust" {
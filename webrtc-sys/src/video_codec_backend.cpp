@@ -0,0 +1,119 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_codec_backend.h"
+
+#include <algorithm>
+
+#if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
+#include <dlfcn.h>
+#endif
+
+#include "rtc_base/logging.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread_annotations.h"
+
+namespace livekit_ffi {
+
+namespace {
+
+webrtc::Mutex rules_mutex;
+std::vector<VideoCodecBackendRule>& rules()
+    RTC_EXCLUSIVE_LOCKS_REQUIRED(rules_mutex) {
+  static auto* rules = new std::vector<VideoCodecBackendRule>();
+  return *rules;
+}
+
+VideoCodecType ToVideoCodecType(webrtc::VideoCodecType codec) {
+  switch (codec) {
+    case webrtc::kVideoCodecVP8:
+      return VideoCodecType::VP8;
+    case webrtc::kVideoCodecVP9:
+      return VideoCodecType::VP9;
+    case webrtc::kVideoCodecAV1:
+      return VideoCodecType::AV1;
+    case webrtc::kVideoCodecH264:
+      return VideoCodecType::H264;
+    case webrtc::kVideoCodecH265:
+      return VideoCodecType::H265;
+    default:
+      return VideoCodecType::Generic;
+  }
+}
+
+}  // namespace
+
+void set_video_codec_backend_rules(rust::Vec<VideoCodecBackendRule> new_rules) {
+  webrtc::MutexLock lock(&rules_mutex);
+  rules().assign(new_rules.begin(), new_rules.end());
+}
+
+std::vector<VideoCodecBackend> order_video_codec_backends(
+    webrtc::VideoCodecType codec,
+    std::vector<VideoCodecBackend> backends) {
+  std::vector<VideoCodecBackendRule> codec_rules;
+  {
+    webrtc::MutexLock lock(&rules_mutex);
+    // Rules of the codec take precedence over the generic ones
+    for (VideoCodecType rule_codec :
+         {ToVideoCodecType(codec), VideoCodecType::Generic}) {
+      for (const auto& rule : rules()) {
+        if (rule.codec == rule_codec) {
+          codec_rules.push_back(rule);
+        }
+      }
+      if (rule_codec == VideoCodecType::Generic) {
+        break;
+      }
+    }
+  }
+
+  // The first rule matching a backend decides whether it is preferred or
+  // disabled, the backends without rules keep their default order
+  std::vector<VideoCodecBackend> ordered;
+  for (const auto& rule : codec_rules) {
+    auto it = std::find(backends.begin(), backends.end(), rule.backend);
+    if (it == backends.end()) {
+      continue;
+    }
+    if (!rule.disabled) {
+      ordered.push_back(*it);
+    }
+    backends.erase(it);
+  }
+
+  ordered.insert(ordered.end(), backends.begin(), backends.end());
+  return ordered;
+}
+
+#if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
+bool IsRockchipMppAvailable() {
+  static const bool available = [] {
+    // The implib module dlopens the library again on the first MPP call
+    void* handle = dlopen("librockchip_mpp.so.1", RTLD_LAZY | RTLD_GLOBAL);
+    if (!handle) {
+      RTC_LOG(LS_INFO) << "librockchip_mpp.so.1 is not found, Rockchip MPP "
+                          "video codecs are disabled.";
+      return false;
+    }
+    dlclose(handle);
+    return true;
+  }();
+  return available;
+}
+#endif
+
+}  // namespace livekit_ffi
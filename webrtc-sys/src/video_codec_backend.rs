@@ -0,0 +1,61 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum VideoCodecBackend {
+        /// Factories registered from Rust
+        Rust,
+        /// VideoToolbox or MediaCodec
+        Platform,
+        Nvidia,
+        Vaapi,
+        RockchipMpp,
+        /// libvpx, OpenH264, libaom and dav1d
+        Software,
+    }
+
+    /// Prefers or disables a backend for a codec, rules are applied in order.
+    #[derive(Debug, Clone)]
+    pub struct VideoCodecBackendRule {
+        /// Generic applies to every codec, after the rules of the codec itself
+        pub codec: VideoCodecType,
+        pub backend: VideoCodecBackend,
+        pub disabled: bool,
+    }
+
+    /// Position of a backend in the order used for a format.
+    #[derive(Debug, Clone)]
+    pub struct VideoCodecBackendEntry {
+        pub format: SdpVideoFormat,
+        pub encoder: bool,
+        pub backend: VideoCodecBackend,
+    }
+
+    extern "C++" {
+        include!("livekit/encoded_video_sink.h");
+        include!("livekit/video_encoder.h");
+
+        type VideoCodecType = crate::encoded_video_sink::ffi::VideoCodecType;
+        type SdpVideoFormat = crate::video_encoder::ffi::SdpVideoFormat;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/video_codec_backend.h");
+
+        fn set_video_codec_backend_rules(rules: Vec<VideoCodecBackendRule>);
+    }
+}
@@ -0,0 +1,199 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_decoder.h"
+
+#include <algorithm>
+#include <optional>
+#include <utility>
+
+#include "api/video/encoded_image.h"
+#include "api/video/video_frame.h"
+#include "livekit/video_encoder.h"
+#include "livekit/video_frame_buffer.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "rtc_base/logging.h"
+#include "webrtc-sys/src/video_decoder.rs.h"
+
+namespace livekit_ffi {
+
+namespace {
+
+using RegisteredFactory =
+    std::pair<uint64_t, std::shared_ptr<RustVideoDecoderFactory>>;
+
+webrtc::Mutex registry_mutex;
+uint64_t last_registered_id RTC_GUARDED_BY(registry_mutex) = 0;
+std::vector<RegisteredFactory>& registry()
+    RTC_EXCLUSIVE_LOCKS_REQUIRED(registry_mutex) {
+  static auto* factories = new std::vector<RegisteredFactory>();
+  return *factories;
+}
+
+VideoCodecType ToVideoCodecType(webrtc::VideoCodecType codec) {
+  switch (codec) {
+    case webrtc::kVideoCodecVP8:
+      return VideoCodecType::VP8;
+    case webrtc::kVideoCodecVP9:
+      return VideoCodecType::VP9;
+    case webrtc::kVideoCodecAV1:
+      return VideoCodecType::AV1;
+    case webrtc::kVideoCodecH264:
+      return VideoCodecType::H264;
+    case webrtc::kVideoCodecH265:
+      return VideoCodecType::H265;
+    default:
+      return VideoCodecType::Generic;
+  }
+}
+
+}  // namespace
+
+void DecodedImageSink::set_callback(webrtc::DecodedImageCallback* callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+bool DecodedImageSink::on_decoded_frame(const VideoFrameBuffer& buffer,
+                                        uint32_t rtp_timestamp,
+                                        int32_t qp) const {
+  webrtc::MutexLock lock(&mutex_);
+  if (!callback_) {
+    return false;
+  }
+
+  webrtc::VideoFrame frame = webrtc::VideoFrame::Builder()
+                                 .set_video_frame_buffer(buffer.get())
+                                 .set_rtp_timestamp(rtp_timestamp)
+                                 .build();
+  callback_->Decoded(frame, std::nullopt,
+                     qp >= 0 ? std::optional<uint8_t>(qp) : std::nullopt);
+  return true;
+}
+
+RustVideoDecoder::RustVideoDecoder(rust::Box<VideoDecoderWrapper> decoder)
+    : decoder_(std::move(decoder)),
+      sink_(std::make_shared<DecodedImageSink>()),
+      implementation_name_(
+          std::string(decoder_->decoder_info().implementation_name)) {}
+
+bool RustVideoDecoder::Configure(const Settings& settings) {
+  VideoDecoderSettings rust_settings{};
+  rust_settings.codec = ToVideoCodecType(settings.codec_type());
+  rust_settings.max_render_width = settings.max_render_resolution().Width();
+  rust_settings.max_render_height = settings.max_render_resolution().Height();
+  rust_settings.number_of_cores = settings.number_of_cores();
+  return decoder_->configure(rust_settings, sink_);
+}
+
+int32_t RustVideoDecoder::Decode(const webrtc::EncodedImage& input_image,
+                                 int64_t render_time_ms) {
+  EncodedImageMetadata metadata{};
+  metadata.rtp_timestamp = input_image.RtpTimestamp();
+  metadata.render_time_ms = render_time_ms;
+  metadata.is_keyframe =
+      input_image._frameType == webrtc::VideoFrameType::kVideoFrameKey;
+  metadata.width = input_image._encodedWidth;
+  metadata.height = input_image._encodedHeight;
+  return decoder_->decode(
+      rust::Slice<const uint8_t>(input_image.data(), input_image.size()),
+      metadata);
+}
+
+int32_t RustVideoDecoder::RegisterDecodeCompleteCallback(
+    webrtc::DecodedImageCallback* callback) {
+  sink_->set_callback(callback);
+  return WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t RustVideoDecoder::Release() {
+  sink_->set_callback(nullptr);
+  return decoder_->release();
+}
+
+webrtc::VideoDecoder::DecoderInfo RustVideoDecoder::GetDecoderInfo() const {
+  auto rust_info = decoder_->decoder_info();
+
+  DecoderInfo info;
+  info.implementation_name = std::string(rust_info.implementation_name);
+  info.is_hardware_accelerated = rust_info.is_hardware_accelerated;
+  return info;
+}
+
+const char* RustVideoDecoder::ImplementationName() const {
+  return implementation_name_.c_str();
+}
+
+RustVideoDecoderFactory::RustVideoDecoderFactory(
+    rust::Box<VideoDecoderFactoryWrapper> factory)
+    : factory_(std::move(factory)) {}
+
+std::vector<webrtc::SdpVideoFormat>
+RustVideoDecoderFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : factory_->supported_formats()) {
+    formats.push_back(to_webrtc_format(format));
+  }
+  return formats;
+}
+
+std::unique_ptr<webrtc::VideoDecoder> RustVideoDecoderFactory::Create(
+    const webrtc::Environment& env,
+    const webrtc::SdpVideoFormat& format) {
+  try {
+    return std::make_unique<RustVideoDecoder>(
+        factory_->create_decoder(to_rust_format(format)));
+  } catch (const rust::Error& e) {
+    RTC_LOG(LS_WARNING) << "failed to create decoder for " << format.name
+                        << ": " << e.what();
+    return nullptr;
+  }
+}
+
+std::vector<std::shared_ptr<RustVideoDecoderFactory>>
+registered_video_decoder_factories() {
+  webrtc::MutexLock lock(&registry_mutex);
+  std::vector<std::shared_ptr<RustVideoDecoderFactory>> factories;
+  for (const auto& [id, factory] : registry()) {
+    factories.push_back(factory);
+  }
+  return factories;
+}
+
+uint64_t register_video_decoder_factory(
+    rust::Box<VideoDecoderFactoryWrapper> factory) {
+  webrtc::MutexLock lock(&registry_mutex);
+  uint64_t id = ++last_registered_id;
+  registry().insert(
+      registry().begin(),
+      {id, std::make_shared<RustVideoDecoderFactory>(std::move(factory))});
+  return id;
+}
+
+bool unregister_video_decoder_factory(uint64_t id) {
+  webrtc::MutexLock lock(&registry_mutex);
+  auto& factories = registry();
+  auto it = std::find_if(
+      factories.begin(), factories.end(),
+      [id](const RegisteredFactory& entry) { return entry.first == id; });
+  if (it == factories.end()) {
+    return false;
+  }
+  factories.erase(it);
+  return true;
+}
+
+}  // namespace livekit_ffi
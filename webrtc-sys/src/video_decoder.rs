@@ -0,0 +1,173 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use cxx::SharedPtr;
+
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+
+    #[derive(Debug)]
+    pub struct VideoDecoderSettings {
+        pub codec: VideoCodecType,
+        /// 0 if unknown
+        pub max_render_width: u32,
+        pub max_render_height: u32,
+        pub number_of_cores: i32,
+    }
+
+    #[derive(Debug)]
+    pub struct VideoDecoderInfo {
+        pub implementation_name: String,
+        pub is_hardware_accelerated: bool,
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedImageMetadata {
+        pub rtp_timestamp: u32,
+        pub render_time_ms: i64,
+        pub is_keyframe: bool,
+        /// 0 if unknown, usually only set on keyframes
+        pub width: u32,
+        pub height: u32,
+    }
+
+    extern "C++" {
+        include!("livekit/video_frame_buffer.h");
+        include!("livekit/encoded_video_sink.h");
+        include!("livekit/video_encoder.h");
+
+        type VideoFrameBuffer = crate::video_frame_buffer::ffi::VideoFrameBuffer;
+        type VideoCodecType = crate::encoded_video_sink::ffi::VideoCodecType;
+        type SdpVideoFormat = crate::video_encoder::ffi::SdpVideoFormat;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/video_decoder.h");
+
+        type DecodedImageSink;
+
+        fn on_decoded_frame(
+            self: &DecodedImageSink,
+            buffer: &VideoFrameBuffer,
+            rtp_timestamp: u32,
+            qp: i32,
+        ) -> bool;
+
+        fn register_video_decoder_factory(factory: Box<VideoDecoderFactoryWrapper>) -> u64;
+        fn unregister_video_decoder_factory(id: u64) -> bool;
+    }
+
+    extern "Rust" {
+        type VideoDecoderFactoryWrapper;
+        type VideoDecoderWrapper;
+
+        fn supported_formats(self: &VideoDecoderFactoryWrapper) -> Vec<SdpVideoFormat>;
+        fn create_decoder(
+            self: &VideoDecoderFactoryWrapper,
+            format: &SdpVideoFormat,
+        ) -> Result<Box<VideoDecoderWrapper>>;
+
+        fn configure(
+            self: &mut VideoDecoderWrapper,
+            settings: &VideoDecoderSettings,
+            sink: SharedPtr<DecodedImageSink>,
+        ) -> bool;
+        fn decode(
+            self: &mut VideoDecoderWrapper,
+            data: &[u8],
+            metadata: &EncodedImageMetadata,
+        ) -> i32;
+        fn release(self: &mut VideoDecoderWrapper) -> i32;
+        fn decoder_info(self: &VideoDecoderWrapper) -> VideoDecoderInfo;
+    }
+} // namespace livekit_ffi
+
+impl_thread_safety!(ffi::DecodedImageSink, Send + Sync);
+
+/// Return codes of the decoder methods, as defined in video_error_codes.h
+pub const VIDEO_CODEC_OK: i32 = 0;
+pub const VIDEO_CODEC_ERROR: i32 = -1;
+pub const VIDEO_CODEC_UNINITIALIZED: i32 = -7;
+/// Asks WebRTC to switch to the built-in decoder
+pub const VIDEO_CODEC_FALLBACK_SOFTWARE: i32 = -13;
+
+pub trait VideoDecoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat>;
+    fn create_decoder(&self, format: &ffi::SdpVideoFormat) -> Option<Box<dyn VideoDecoder>>;
+}
+
+pub trait VideoDecoder: Send {
+    fn configure(
+        &mut self,
+        settings: &ffi::VideoDecoderSettings,
+        sink: SharedPtr<ffi::DecodedImageSink>,
+    ) -> bool;
+    fn decode(&mut self, data: &[u8], metadata: &ffi::EncodedImageMetadata) -> i32;
+    fn release(&mut self) -> i32;
+    fn decoder_info(&self) -> ffi::VideoDecoderInfo;
+}
+
+pub struct VideoDecoderFactoryWrapper {
+    factory: Arc<dyn VideoDecoderFactory>,
+}
+
+impl VideoDecoderFactoryWrapper {
+    pub fn new(factory: Arc<dyn VideoDecoderFactory>) -> Self {
+        Self { factory }
+    }
+
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat> {
+        self.factory.supported_formats()
+    }
+
+    fn create_decoder(
+        &self,
+        format: &ffi::SdpVideoFormat,
+    ) -> Result<Box<VideoDecoderWrapper>, &'static str> {
+        self.factory
+            .create_decoder(format)
+            .map(|decoder| Box::new(VideoDecoderWrapper { decoder }))
+            .ok_or("unsupported format")
+    }
+}
+
+pub struct VideoDecoderWrapper {
+    decoder: Box<dyn VideoDecoder>,
+}
+
+impl VideoDecoderWrapper {
+    fn configure(
+        &mut self,
+        settings: &ffi::VideoDecoderSettings,
+        sink: SharedPtr<ffi::DecodedImageSink>,
+    ) -> bool {
+        self.decoder.configure(settings, sink)
+    }
+
+    fn decode(&mut self, data: &[u8], metadata: &ffi::EncodedImageMetadata) -> i32 {
+        self.decoder.decode(data, metadata)
+    }
+
+    fn release(&mut self) -> i32 {
+        self.decoder.release()
+    }
+
+    fn decoder_info(&self) -> ffi::VideoDecoderInfo {
+        self.decoder.decoder_info()
+    }
+}
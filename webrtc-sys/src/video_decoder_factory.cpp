@@ -16,11 +16,15 @@
 
 #include "livekit/video_decoder_factory.h"
 
+#include <algorithm>
+
 #include <modules/video_coding/codecs/av1/av1_svc_config.h>
 #include "api/environment/environment.h"
 #include "api/video_codecs/av1_profile.h"
 #include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_decoder_software_fallback_wrapper.h"
 #include "livekit/objc_video_factory.h"
+#include "livekit/video_decoder.h"
 #include "media/base/media_constants.h"
 #include "modules/video_coding/codecs/h264/include/h264.h"
 #include "modules/video_coding/codecs/vp8/include/vp8.h"
@@ -47,45 +51,121 @@ namespace livekit_ffi {
 
 VideoDecoderFactory::VideoDecoderFactory() {
 #ifdef __APPLE__
-  factories_.push_back(livekit_ffi::CreateObjCVideoDecoderFactory());
+  factories_.emplace_back(VideoCodecBackend::Platform,
+                          livekit_ffi::CreateObjCVideoDecoderFactory());
 #endif
 
 #ifdef WEBRTC_ANDROID
-  factories_.push_back(CreateAndroidVideoDecoderFactory());
+  factories_.emplace_back(VideoCodecBackend::Platform,
+                          CreateAndroidVideoDecoderFactory());
 #endif
 
 #if defined(USE_NVIDIA_VIDEO_CODEC)
   if (webrtc::NvidiaVideoDecoderFactory::IsSupported()) {
-    factories_.push_back(std::make_unique<webrtc::NvidiaVideoDecoderFactory>());
+    factories_.emplace_back(
+        VideoCodecBackend::Nvidia,
+        std::make_unique<webrtc::NvidiaVideoDecoderFactory>());
   }
 #endif
 
 #if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
-  factories_.push_back(std::make_unique<webrtc::RockchipVideoDecoderFactory>());
+  if (IsRockchipMppAvailable()) {
+    factories_.emplace_back(
+        VideoCodecBackend::RockchipMpp,
+        std::make_unique<webrtc::RockchipVideoDecoderFactory>());
+  }
 #endif
 }
 
+std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::SupportedFormats(
+    VideoCodecBackend backend) const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  switch (backend) {
+    case VideoCodecBackend::Rust:
+      for (const auto& factory : registered_video_decoder_factories()) {
+        auto supported_formats = factory->GetSupportedFormats();
+        formats.insert(formats.end(), supported_formats.begin(),
+                       supported_formats.end());
+      }
+      break;
+    case VideoCodecBackend::Software:
+      formats.push_back(webrtc::SdpVideoFormat(cricket::kVp8CodecName));
+      for (const webrtc::SdpVideoFormat& format :
+           webrtc::SupportedVP9DecoderCodecs())
+        formats.push_back(format);
+      for (const webrtc::SdpVideoFormat& h264_format :
+           webrtc::SupportedH264DecoderCodecs())
+        formats.push_back(h264_format);
+
+      formats.push_back(webrtc::SdpVideoFormat(
+          webrtc::SdpVideoFormat::AV1Profile0(),
+          webrtc::LibaomAv1EncoderSupportedScalabilityModes()));
+      break;
+    default:
+      for (const auto& [factory_backend, factory] : factories_) {
+        if (factory_backend == backend) {
+          auto supported_formats = factory->GetSupportedFormats();
+          formats.insert(formats.end(), supported_formats.begin(),
+                         supported_formats.end());
+        }
+      }
+      break;
+  }
+  return formats;
+}
+
+std::vector<VideoCodecBackend> VideoDecoderFactory::Backends(
+    const webrtc::SdpVideoFormat& format) const {
+  // Decoders registered from Rust take precedence, then hardware decoders are
+  // preferred over software ones
+  std::vector<VideoCodecBackend> backends;
+  if (format.IsCodecInList(SupportedFormats(VideoCodecBackend::Rust))) {
+    backends.push_back(VideoCodecBackend::Rust);
+  }
+
+#if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
+  // Rockchip MPP is the default for H.264, WebRTC's duplicate codec filtering
+  // may otherwise hide the formats of the Rockchip factory
+  if (IsRockchipMppAvailable() &&
+      absl::EqualsIgnoreCase(format.name, cricket::kH264CodecName)) {
+    backends.push_back(VideoCodecBackend::RockchipMpp);
+  }
+#endif
+
+  for (const auto& [backend, factory] : factories_) {
+    if (format.IsCodecInList(factory->GetSupportedFormats()) &&
+        std::find(backends.begin(), backends.end(), backend) ==
+            backends.end()) {
+      backends.push_back(backend);
+    }
+  }
+  if (format.IsCodecInList(SupportedFormats(VideoCodecBackend::Software))) {
+    backends.push_back(VideoCodecBackend::Software);
+  }
+  return order_video_codec_backends(
+      webrtc::PayloadStringToCodecType(format.name), std::move(backends));
+}
+
 std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSupportedFormats()
     const {
-  std::vector<webrtc::SdpVideoFormat> formats;
+  std::vector<webrtc::SdpVideoFormat> formats =
+      SupportedFormats(VideoCodecBackend::Rust);
 
-  for (const auto& factory : factories_) {
+  for (const auto& [backend, factory] : factories_) {
     auto supported_formats = factory->GetSupportedFormats();
     formats.insert(formats.end(), supported_formats.begin(),
                    supported_formats.end());
   }
 
-  formats.push_back(webrtc::SdpVideoFormat(cricket::kVp8CodecName));
-  for (const webrtc::SdpVideoFormat& format :
-       webrtc::SupportedVP9DecoderCodecs())
-    formats.push_back(format);
-  for (const webrtc::SdpVideoFormat& h264_format :
-       webrtc::SupportedH264DecoderCodecs())
-    formats.push_back(h264_format);
-
-  formats.push_back(webrtc::SdpVideoFormat(
-      webrtc::SdpVideoFormat::AV1Profile0(),
-      webrtc::LibaomAv1EncoderSupportedScalabilityModes()));
+  auto builtin_formats = SupportedFormats(VideoCodecBackend::Software);
+  formats.insert(formats.end(), builtin_formats.begin(), builtin_formats.end());
+
+  // Formats of disabled backends are not advertised
+  formats.erase(std::remove_if(formats.begin(), formats.end(),
+                               [this](const webrtc::SdpVideoFormat& format) {
+                                 return Backends(format).empty();
+                               }),
+                formats.end());
   return formats;
 }
 
@@ -101,56 +181,78 @@ VideoDecoderFactory::CodecSupport VideoDecoderFactory::QueryCodecSupport(
   }
 
   CodecSupport codec_support;
-  codec_support.is_supported = format.IsCodecInList(GetSupportedFormats());
+  codec_support.is_supported = !Backends(format).empty();
   return codec_support;
 }
 
 std::unique_ptr<webrtc::VideoDecoder> VideoDecoderFactory::Create(
     const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) {
-#if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
-  // Force Rockchip MPP hardware decoder for all H.264.
-  // WebRTC's duplicate codec filtering may hide the Rockchip factory's formats,
-  // so we explicitly route H.264 to it before the generic factory loop.
-  if (absl::EqualsIgnoreCase(format.name, cricket::kH264CodecName)) {
-    RTC_LOG(LS_INFO) << "Routing H.264 to Rockchip MPP decoder";
-    for (const auto& factory : factories_) {
-      auto* rockchip = dynamic_cast<webrtc::RockchipVideoDecoderFactory*>(factory.get());
-      if (rockchip) {
-        auto decoder = rockchip->Create(env, format);
-        if (decoder) {
-          RTC_LOG(LS_INFO) << "Created Rockchip MPP H.264 decoder";
-          return decoder;
-        }
-        RTC_LOG(LS_WARNING) << "Rockchip factory returned null, falling back";
-      }
+  auto backends = Backends(format);
+  for (auto it = backends.begin(); it != backends.end(); ++it) {
+    auto decoder = CreateDecoder(*it, env, format);
+    if (!decoder) {
+      continue;
     }
-  }
-#endif
 
-  for (const auto& factory : factories_) {
-    for (const auto& supported_format : factory->GetSupportedFormats()) {
-      if (supported_format.IsSameCodec(format))
-        return factory->Create(env, format);
+    // The built-in decoder is used when a Rust decoder fails to decode or
+    // returns WEBRTC_VIDEO_CODEC_FALLBACK_SOFTWARE, unless it is disabled
+    if (*it == VideoCodecBackend::Rust &&
+        std::find(it, backends.end(), VideoCodecBackend::Software) !=
+            backends.end()) {
+      if (auto builtin =
+              CreateDecoder(VideoCodecBackend::Software, env, format)) {
+        return webrtc::CreateVideoDecoderSoftwareFallbackWrapper(
+            env, std::move(builtin), std::move(decoder));
+      }
     }
+    return decoder;
   }
 
-  if (absl::EqualsIgnoreCase(format.name, cricket::kVp8CodecName))
-    return webrtc::CreateVp8Decoder(env);
-  if (absl::EqualsIgnoreCase(format.name, cricket::kVp9CodecName))
-    return webrtc::VP9Decoder::Create();
-  if (absl::EqualsIgnoreCase(format.name, cricket::kH264CodecName))
-    return webrtc::H264Decoder::Create();
-
+  RTC_LOG(LS_ERROR) << "No VideoDecoder found for " << format.name;
+  return nullptr;
+}
 
+std::unique_ptr<webrtc::VideoDecoder> VideoDecoderFactory::CreateDecoder(
+    VideoCodecBackend backend,
+    const webrtc::Environment& env,
+    const webrtc::SdpVideoFormat& format) {
+  switch (backend) {
+    case VideoCodecBackend::Rust:
+      for (const auto& factory : registered_video_decoder_factories()) {
+        if (format.IsCodecInList(factory->GetSupportedFormats())) {
+          if (auto decoder = factory->Create(env, format)) {
+            return decoder;
+          }
+        }
+      }
+      return nullptr;
+    case VideoCodecBackend::Software:
+      if (absl::EqualsIgnoreCase(format.name, cricket::kVp8CodecName))
+        return webrtc::CreateVp8Decoder(env);
+      if (absl::EqualsIgnoreCase(format.name, cricket::kVp9CodecName))
+        return webrtc::VP9Decoder::Create();
+      if (absl::EqualsIgnoreCase(format.name, cricket::kH264CodecName))
+        return webrtc::H264Decoder::Create();
 #if defined(RTC_DAV1D_IN_INTERNAL_DECODER_FACTORY)
-  if (absl::EqualsIgnoreCase(format.name, cricket::kAv1CodecName)) {
-    return webrtc::CreateDav1dDecoder();
-  }
+      if (absl::EqualsIgnoreCase(format.name, cricket::kAv1CodecName))
+        return webrtc::CreateDav1dDecoder();
 #endif
-
-
-  RTC_LOG(LS_ERROR) << "No VideoDecoder found for " << format.name;
-  return nullptr;
+      return nullptr;
+    default:
+      for (const auto& [factory_backend, factory] : factories_) {
+        if (factory_backend != backend) {
+          continue;
+        }
+        // The Rockchip factory is used for H.264 even if it doesn't list it
+        if (format.IsCodecInList(factory->GetSupportedFormats()) ||
+            backend == VideoCodecBackend::RockchipMpp) {
+          if (auto decoder = factory->Create(env, format)) {
+            return decoder;
+          }
+        }
+      }
+      return nullptr;
+  }
 }
 
 }  // namespace livekit_ffi
@@ -0,0 +1,301 @@
+/*
+ * Copyright 2025 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_encoder.h"
+
+#include <algorithm>
+#include <utility>
+
+#include "api/video/encoded_image.h"
+#include "livekit/video_frame.h"
+#include "modules/video_coding/include/video_codec_interface.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "rtc_base/logging.h"
+#include "webrtc-sys/src/video_encoder.rs.h"
+
+namespace livekit_ffi {
+
+namespace {
+
+using RegisteredFactory =
+    std::pair<uint64_t, std::shared_ptr<RustVideoEncoderFactory>>;
+
+webrtc::Mutex registry_mutex;
+uint64_t last_registered_id RTC_GUARDED_BY(registry_mutex) = 0;
+std::vector<RegisteredFactory>& registry()
+    RTC_EXCLUSIVE_LOCKS_REQUIRED(registry_mutex) {
+  static auto* factories = new std::vector<RegisteredFactory>();
+  return *factories;
+}
+
+VideoCodecType ToVideoCodecType(webrtc::VideoCodecType codec) {
+  switch (codec) {
+    case webrtc::kVideoCodecVP8:
+      return VideoCodecType::VP8;
+    case webrtc::kVideoCodecVP9:
+      return VideoCodecType::VP9;
+    case webrtc::kVideoCodecAV1:
+      return VideoCodecType::AV1;
+    case webrtc::kVideoCodecH264:
+      return VideoCodecType::H264;
+    case webrtc::kVideoCodecH265:
+      return VideoCodecType::H265;
+    default:
+      return VideoCodecType::Generic;
+  }
+}
+
+webrtc::VideoCodecType ToWebrtcCodecType(VideoCodecType codec) {
+  switch (codec) {
+    case VideoCodecType::VP8:
+      return webrtc::kVideoCodecVP8;
+    case VideoCodecType::VP9:
+      return webrtc::kVideoCodecVP9;
+    case VideoCodecType::AV1:
+      return webrtc::kVideoCodecAV1;
+    case VideoCodecType::H264:
+      return webrtc::kVideoCodecH264;
+    case VideoCodecType::H265:
+      return webrtc::kVideoCodecH265;
+    default:
+      return webrtc::kVideoCodecGeneric;
+  }
+}
+
+// Fills the codec specific info of a single layer stream
+void FillCodecSpecificInfo(const EncodedImageInfo& info,
+                           webrtc::CodecSpecificInfo& codec_info) {
+  codec_info.codecType = ToWebrtcCodecType(info.codec);
+  uint8_t temporal_idx = info.temporal_index >= 0
+                             ? static_cast<uint8_t>(info.temporal_index)
+                             : webrtc::kNoTemporalIdx;
+
+  switch (codec_info.codecType) {
+    case webrtc::kVideoCodecVP8:
+      codec_info.codecSpecific.VP8.nonReference = false;
+      codec_info.codecSpecific.VP8.temporalIdx = temporal_idx;
+      codec_info.codecSpecific.VP8.layerSync = info.layer_sync;
+      codec_info.codecSpecific.VP8.keyIdx = webrtc::kNoKeyIdx;
+      break;
+    case webrtc::kVideoCodecVP9:
+      // Flexible mode, each frame references the previous one
+      codec_info.codecSpecific.VP9.first_frame_in_picture = true;
+      codec_info.codecSpecific.VP9.inter_pic_predicted = !info.is_keyframe;
+      codec_info.codecSpecific.VP9.flexible_mode = true;
+      codec_info.codecSpecific.VP9.ss_data_available = info.is_keyframe;
+      codec_info.codecSpecific.VP9.non_ref_for_inter_layer_pred = true;
+      codec_info.codecSpecific.VP9.temporal_idx = temporal_idx;
+      codec_info.codecSpecific.VP9.temporal_up_switch = info.layer_sync;
+      codec_info.codecSpecific.VP9.inter_layer_predicted = false;
+      codec_info.codecSpecific.VP9.gof_idx = webrtc::kNoGofIdx;
+      codec_info.codecSpecific.VP9.num_spatial_layers = 1;
+      codec_info.codecSpecific.VP9.first_active_layer = 0;
+      codec_info.codecSpecific.VP9.spatial_layer_resolution_present =
+          info.is_keyframe;
+      codec_info.codecSpecific.VP9.width[0] = info.width;
+      codec_info.codecSpecific.VP9.height[0] = info.height;
+      codec_info.codecSpecific.VP9.num_ref_pics = info.is_keyframe ? 0 : 1;
+      codec_info.codecSpecific.VP9.p_diff[0] = 1;
+      break;
+    case webrtc::kVideoCodecH264:
+      codec_info.codecSpecific.H264.packetization_mode =
+          webrtc::H264PacketizationMode::NonInterleaved;
+      codec_info.codecSpecific.H264.temporal_idx = temporal_idx;
+      codec_info.codecSpecific.H264.base_layer_sync = info.layer_sync;
+      codec_info.codecSpecific.H264.idr_frame = info.is_keyframe;
+      break;
+    default:
+      break;
+  }
+  codec_info.end_of_picture = true;
+}
+
+}  // namespace
+
+void EncodedImageSink::set_callback(webrtc::EncodedImageCallback* callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+bool EncodedImageSink::on_encoded_image(rust::Slice<const uint8_t> data,
+                                        const EncodedImageInfo& info) const {
+  webrtc::MutexLock lock(&mutex_);
+  if (!callback_) {
+    return false;
+  }
+
+  webrtc::EncodedImage image;
+  image.SetEncodedData(
+      webrtc::EncodedImageBuffer::Create(data.data(), data.size()));
+  image._encodedWidth = info.width;
+  image._encodedHeight = info.height;
+  image.SetRtpTimestamp(info.rtp_timestamp);
+  image.capture_time_ms_ = info.capture_time_ms;
+  image.qp_ = info.qp;
+  image._frameType = info.is_keyframe ? webrtc::VideoFrameType::kVideoFrameKey
+                                      : webrtc::VideoFrameType::kVideoFrameDelta;
+  if (info.temporal_index >= 0) {
+    image.SetTemporalIndex(info.temporal_index);
+  }
+
+  webrtc::CodecSpecificInfo codec_info;
+  FillCodecSpecificInfo(info, codec_info);
+
+  auto result = callback_->OnEncodedImage(image, &codec_info);
+  return result.error == webrtc::EncodedImageCallback::Result::OK;
+}
+
+RustVideoEncoder::RustVideoEncoder(rust::Box<VideoEncoderWrapper> encoder)
+    : encoder_(std::move(encoder)),
+      sink_(std::make_shared<EncodedImageSink>()) {}
+
+int RustVideoEncoder::InitEncode(
+    const webrtc::VideoCodec* codec_settings,
+    const webrtc::VideoEncoder::Settings& settings) {
+  if (!codec_settings) {
+    return WEBRTC_VIDEO_CODEC_ERR_PARAMETER;
+  }
+
+  VideoEncoderSettings rust_settings{};
+  rust_settings.codec = ToVideoCodecType(codec_settings->codecType);
+  rust_settings.width = codec_settings->width;
+  rust_settings.height = codec_settings->height;
+  rust_settings.max_framerate = codec_settings->maxFramerate;
+  rust_settings.start_bitrate_kbps = codec_settings->startBitrate;
+  rust_settings.min_bitrate_kbps = codec_settings->minBitrate;
+  rust_settings.max_bitrate_kbps = codec_settings->maxBitrate;
+  rust_settings.qp_max = codec_settings->qpMax;
+  rust_settings.number_of_cores = settings.number_of_cores;
+  rust_settings.max_payload_size = settings.max_payload_size;
+  rust_settings.is_screenshare =
+      codec_settings->mode == webrtc::VideoCodecMode::kScreensharing;
+
+  return encoder_->init_encode(rust_settings, sink_);
+}
+
+int32_t RustVideoEncoder::RegisterEncodeCompleteCallback(
+    webrtc::EncodedImageCallback* callback) {
+  sink_->set_callback(callback);
+  return WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t RustVideoEncoder::Release() {
+  sink_->set_callback(nullptr);
+  return encoder_->release();
+}
+
+int32_t RustVideoEncoder::Encode(
+    const webrtc::VideoFrame& frame,
+    const std::vector<webrtc::VideoFrameType>* frame_types) {
+  bool keyframe =
+      frame_types &&
+      std::find(frame_types->begin(), frame_types->end(),
+                webrtc::VideoFrameType::kVideoFrameKey) != frame_types->end();
+  return encoder_->encode(std::make_unique<VideoFrame>(frame), keyframe);
+}
+
+void RustVideoEncoder::SetRates(const RateControlParameters& parameters) {
+  encoder_->set_rates(parameters.bitrate.get_sum_bps(),
+                      parameters.framerate_fps);
+}
+
+webrtc::VideoEncoder::EncoderInfo RustVideoEncoder::GetEncoderInfo() const {
+  auto rust_info = encoder_->encoder_info();
+
+  EncoderInfo info;
+  info.implementation_name = std::string(rust_info.implementation_name);
+  info.is_hardware_accelerated = rust_info.is_hardware_accelerated;
+  info.supports_native_handle = false;
+  return info;
+}
+
+RustVideoEncoderFactory::RustVideoEncoderFactory(
+    rust::Box<VideoEncoderFactoryWrapper> factory)
+    : factory_(std::move(factory)) {}
+
+std::vector<webrtc::SdpVideoFormat>
+RustVideoEncoderFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : factory_->supported_formats()) {
+    formats.push_back(to_webrtc_format(format));
+  }
+  return formats;
+}
+
+std::unique_ptr<webrtc::VideoEncoder> RustVideoEncoderFactory::Create(
+    const webrtc::Environment& env,
+    const webrtc::SdpVideoFormat& format) {
+  try {
+    return std::make_unique<RustVideoEncoder>(
+        factory_->create_encoder(to_rust_format(format)));
+  } catch (const rust::Error& e) {
+    RTC_LOG(LS_WARNING) << "failed to create encoder for " << format.name
+                        << ": " << e.what();
+    return nullptr;
+  }
+}
+
+std::vector<std::shared_ptr<RustVideoEncoderFactory>>
+registered_video_encoder_factories() {
+  webrtc::MutexLock lock(&registry_mutex);
+  std::vector<std::shared_ptr<RustVideoEncoderFactory>> factories;
+  for (const auto& [id, factory] : registry()) {
+    factories.push_back(factory);
+  }
+  return factories;
+}
+
+uint64_t register_video_encoder_factory(
+    rust::Box<VideoEncoderFactoryWrapper> factory) {
+  webrtc::MutexLock lock(&registry_mutex);
+  uint64_t id = ++last_registered_id;
+  registry().insert(
+      registry().begin(),
+      {id, std::make_shared<RustVideoEncoderFactory>(std::move(factory))});
+  return id;
+}
+
+bool unregister_video_encoder_factory(uint64_t id) {
+  webrtc::MutexLock lock(&registry_mutex);
+  auto& factories = registry();
+  auto it = std::find_if(
+      factories.begin(), factories.end(),
+      [id](const RegisteredFactory& entry) { return entry.first == id; });
+  if (it == factories.end()) {
+    return false;
+  }
+  factories.erase(it);
+  return true;
+}
+
+webrtc::SdpVideoFormat to_webrtc_format(const SdpVideoFormat& format) {
+  webrtc::CodecParameterMap parameters;
+  for (const auto& parameter : format.parameters) {
+    parameters[std::string(parameter.key)] = std::string(parameter.value);
+  }
+  return webrtc::SdpVideoFormat(std::string(format.name), parameters);
+}
+
+SdpVideoFormat to_rust_format(const webrtc::SdpVideoFormat& format) {
+  SdpVideoFormat rust_format{};
+  rust_format.name = format.name;
+  for (const auto& [key, value] : format.parameters) {
+    rust_format.parameters.push_back(SdpVideoFormatParameter{key, value});
+  }
+  return rust_format;
+}
+
+}  // namespace livekit_ffi
@@ -0,0 +1,194 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use cxx::{SharedPtr, UniquePtr};
+
+use crate::{impl_thread_safety, video_frame::ffi::VideoFrame};
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+
+    #[derive(Debug, Clone)]
+    pub struct SdpVideoFormatParameter {
+        pub key: String,
+        pub value: String,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct SdpVideoFormat {
+        pub name: String,
+        pub parameters: Vec<SdpVideoFormatParameter>,
+    }
+
+    #[derive(Debug)]
+    pub struct VideoEncoderSettings {
+        pub codec: VideoCodecType,
+        pub width: u16,
+        pub height: u16,
+        pub max_framerate: u32,
+        pub start_bitrate_kbps: u32,
+        pub min_bitrate_kbps: u32,
+        pub max_bitrate_kbps: u32,
+        pub qp_max: u32,
+        pub number_of_cores: i32,
+        pub max_payload_size: usize,
+        pub is_screenshare: bool,
+    }
+
+    #[derive(Debug)]
+    pub struct VideoEncoderInfo {
+        pub implementation_name: String,
+        pub is_hardware_accelerated: bool,
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedImageInfo {
+        pub codec: VideoCodecType,
+        pub is_keyframe: bool,
+        pub rtp_timestamp: u32,
+        pub capture_time_ms: i64,
+        pub width: u32,
+        pub height: u32,
+        /// -1 if unknown
+        pub qp: i32,
+        /// -1 when the stream has no temporal layers
+        pub temporal_index: i32,
+        /// Whether the frame only depends on the base temporal layer
+        pub layer_sync: bool,
+    }
+
+    extern "C++" {
+        include!("livekit/video_frame.h");
+        include!("livekit/encoded_video_sink.h");
+
+        type VideoFrame = crate::video_frame::ffi::VideoFrame;
+        type VideoCodecType = crate::encoded_video_sink::ffi::VideoCodecType;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/video_encoder.h");
+
+        type EncodedImageSink;
+
+        fn on_encoded_image(self: &EncodedImageSink, data: &[u8], info: &EncodedImageInfo) -> bool;
+
+        fn register_video_encoder_factory(factory: Box<VideoEncoderFactoryWrapper>) -> u64;
+        fn unregister_video_encoder_factory(id: u64) -> bool;
+    }
+
+    extern "Rust" {
+        type VideoEncoderFactoryWrapper;
+        type VideoEncoderWrapper;
+
+        fn supported_formats(self: &VideoEncoderFactoryWrapper) -> Vec<SdpVideoFormat>;
+        fn create_encoder(
+            self: &VideoEncoderFactoryWrapper,
+            format: &SdpVideoFormat,
+        ) -> Result<Box<VideoEncoderWrapper>>;
+
+        fn init_encode(
+            self: &mut VideoEncoderWrapper,
+            settings: &VideoEncoderSettings,
+            sink: SharedPtr<EncodedImageSink>,
+        ) -> i32;
+        fn encode(
+            self: &mut VideoEncoderWrapper,
+            frame: UniquePtr<VideoFrame>,
+            keyframe: bool,
+        ) -> i32;
+        fn set_rates(self: &mut VideoEncoderWrapper, bitrate_bps: u32, framerate_fps: f64);
+        fn release(self: &mut VideoEncoderWrapper) -> i32;
+        fn encoder_info(self: &VideoEncoderWrapper) -> VideoEncoderInfo;
+    }
+} // namespace livekit_ffi
+
+impl_thread_safety!(ffi::EncodedImageSink, Send + Sync);
+
+/// Return codes of the encoder methods, as defined in video_error_codes.h
+pub const VIDEO_CODEC_OK: i32 = 0;
+pub const VIDEO_CODEC_ERROR: i32 = -1;
+pub const VIDEO_CODEC_UNINITIALIZED: i32 = -7;
+
+pub trait VideoEncoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat>;
+    fn create_encoder(&self, format: &ffi::SdpVideoFormat) -> Option<Box<dyn VideoEncoder>>;
+}
+
+pub trait VideoEncoder: Send {
+    fn init_encode(
+        &mut self,
+        settings: &ffi::VideoEncoderSettings,
+        sink: SharedPtr<ffi::EncodedImageSink>,
+    ) -> i32;
+    fn encode(&mut self, frame: UniquePtr<VideoFrame>, keyframe: bool) -> i32;
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64);
+    fn release(&mut self) -> i32;
+    fn encoder_info(&self) -> ffi::VideoEncoderInfo;
+}
+
+pub struct VideoEncoderFactoryWrapper {
+    factory: Arc<dyn VideoEncoderFactory>,
+}
+
+impl VideoEncoderFactoryWrapper {
+    pub fn new(factory: Arc<dyn VideoEncoderFactory>) -> Self {
+        Self { factory }
+    }
+
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat> {
+        self.factory.supported_formats()
+    }
+
+    fn create_encoder(
+        &self,
+        format: &ffi::SdpVideoFormat,
+    ) -> Result<Box<VideoEncoderWrapper>, &'static str> {
+        self.factory
+            .create_encoder(format)
+            .map(|encoder| Box::new(VideoEncoderWrapper { encoder }))
+            .ok_or("unsupported format")
+    }
+}
+
+pub struct VideoEncoderWrapper {
+    encoder: Box<dyn VideoEncoder>,
+}
+
+impl VideoEncoderWrapper {
+    fn init_encode(
+        &mut self,
+        settings: &ffi::VideoEncoderSettings,
+        sink: SharedPtr<ffi::EncodedImageSink>,
+    ) -> i32 {
+        self.encoder.init_encode(settings, sink)
+    }
+
+    fn encode(&mut self, frame: UniquePtr<VideoFrame>, keyframe: bool) -> i32 {
+        self.encoder.encode(frame, keyframe)
+    }
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64) {
+        self.encoder.set_rates(bitrate_bps, framerate_fps);
+    }
+
+    fn release(&mut self) -> i32 {
+        self.encoder.release()
+    }
+
+    fn encoder_info(&self) -> ffi::VideoEncoderInfo {
+        self.encoder.encoder_info()
+    }
+}
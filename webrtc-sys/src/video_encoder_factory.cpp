@@ -16,11 +16,16 @@
 
 #include "livekit/video_encoder_factory.h"
 
+#include <algorithm>
+
 #include "api/environment/environment_factory.h"
 #include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_codec.h"
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory_template.h"
+#include "livekit/encoded_video_source.h"
 #include "livekit/objc_video_factory.h"
+#include "livekit/video_encoder.h"
 #include "media/base/media_constants.h"
 #include "media/engine/simulcast_encoder_adapter.h"
 #include "rtc_base/logging.h"
@@ -63,22 +68,28 @@ using Factory = webrtc::VideoEncoderFactoryTemplate<
 
 VideoEncoderFactory::InternalFactory::InternalFactory() {
 #ifdef __APPLE__
-  factories_.push_back(livekit_ffi::CreateObjCVideoEncoderFactory());
+  factories_.emplace_back(VideoCodecBackend::Platform,
+                          livekit_ffi::CreateObjCVideoEncoderFactory());
 #endif
 
 #ifdef WEBRTC_ANDROID
-  factories_.push_back(CreateAndroidVideoEncoderFactory());
+  factories_.emplace_back(VideoCodecBackend::Platform,
+                          CreateAndroidVideoEncoderFactory());
 #endif
 
 #if defined(USE_NVIDIA_VIDEO_CODEC)
   if (webrtc::NvidiaVideoEncoderFactory::IsSupported()) {
-    factories_.push_back(std::make_unique<webrtc::NvidiaVideoEncoderFactory>());
+    factories_.emplace_back(
+        VideoCodecBackend::Nvidia,
+        std::make_unique<webrtc::NvidiaVideoEncoderFactory>());
   } else {
 #endif
 
 #if defined(USE_VAAPI_VIDEO_CODEC)
     if (webrtc::VAAPIVideoEncoderFactory::IsSupported()) {
-      factories_.push_back(std::make_unique<webrtc::VAAPIVideoEncoderFactory>());
+      factories_.emplace_back(
+          VideoCodecBackend::Vaapi,
+          std::make_unique<webrtc::VAAPIVideoEncoderFactory>());
     }
 #endif
 
@@ -87,19 +98,86 @@ VideoEncoderFactory::InternalFactory::InternalFactory() {
 #endif
 
 #if defined(USE_ROCKCHIP_MPP_VIDEO_CODEC)
-  factories_.push_back(std::make_unique<webrtc::RockchipVideoEncoderFactory>());
+  if (IsRockchipMppAvailable()) {
+    factories_.emplace_back(
+        VideoCodecBackend::RockchipMpp,
+        std::make_unique<webrtc::RockchipVideoEncoderFactory>());
+  }
 #endif
 }
 
 std::vector<webrtc::SdpVideoFormat>
-VideoEncoderFactory::InternalFactory::GetSupportedFormats() const {
-  std::vector<webrtc::SdpVideoFormat> formats = Factory().GetSupportedFormats();
+VideoEncoderFactory::InternalFactory::SupportedFormats(
+    VideoCodecBackend backend) const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  switch (backend) {
+    case VideoCodecBackend::Rust:
+      for (const auto& factory : registered_video_encoder_factories()) {
+        auto supported_formats = factory->GetSupportedFormats();
+        formats.insert(formats.end(), supported_formats.begin(),
+                       supported_formats.end());
+      }
+      break;
+    case VideoCodecBackend::Software:
+      formats = Factory().GetSupportedFormats();
+      break;
+    default:
+      for (const auto& [factory_backend, factory] : factories_) {
+        if (factory_backend == backend) {
+          auto supported_formats = factory->GetSupportedFormats();
+          formats.insert(formats.end(), supported_formats.begin(),
+                         supported_formats.end());
+        }
+      }
+      break;
+  }
+  return formats;
+}
 
-  for (const auto& factory : factories_) {
+std::vector<VideoCodecBackend> VideoEncoderFactory::InternalFactory::Backends(
+    const webrtc::SdpVideoFormat& format) const {
+  // Encoders registered from Rust take precedence over the built-in ones,
+  // then hardware encoders are preferred over software ones
+  std::vector<VideoCodecBackend> backends;
+  if (format.IsCodecInList(SupportedFormats(VideoCodecBackend::Rust))) {
+    backends.push_back(VideoCodecBackend::Rust);
+  }
+  for (const auto& [backend, factory] : factories_) {
+    if (format.IsCodecInList(factory->GetSupportedFormats()) &&
+        std::find(backends.begin(), backends.end(), backend) ==
+            backends.end()) {
+      backends.push_back(backend);
+    }
+  }
+  if (webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(),
+                                       format)) {
+    backends.push_back(VideoCodecBackend::Software);
+  }
+  return order_video_codec_backends(
+      webrtc::PayloadStringToCodecType(format.name), std::move(backends));
+}
+
+std::vector<webrtc::SdpVideoFormat>
+VideoEncoderFactory::InternalFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (VideoCodecBackend backend :
+       {VideoCodecBackend::Rust, VideoCodecBackend::Software}) {
+    auto supported_formats = SupportedFormats(backend);
+    formats.insert(formats.end(), supported_formats.begin(),
+                   supported_formats.end());
+  }
+  for (const auto& [backend, factory] : factories_) {
     auto supported_formats = factory->GetSupportedFormats();
     formats.insert(formats.end(), supported_formats.begin(),
                    supported_formats.end());
   }
+
+  // Formats of disabled backends are not advertised
+  formats.erase(std::remove_if(formats.begin(), formats.end(),
+                               [this](const webrtc::SdpVideoFormat& format) {
+                                 return Backends(format).empty();
+                               }),
+                formats.end());
   return formats;
 }
 
@@ -107,35 +185,95 @@ VideoEncoderFactory::CodecSupport
 VideoEncoderFactory::InternalFactory::QueryCodecSupport(
     const webrtc::SdpVideoFormat& format,
     std::optional<std::string> scalability_mode) const {
-  auto original_format =
-      webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(), format);
-  return original_format
-             ? Factory().QueryCodecSupport(*original_format, scalability_mode)
-             : webrtc::VideoEncoderFactory::CodecSupport{.is_supported = false};
+  auto backends = Backends(format);
+  if (backends.empty()) {
+    return webrtc::VideoEncoderFactory::CodecSupport{.is_supported = false};
+  }
+
+  switch (backends.front()) {
+    case VideoCodecBackend::Rust:
+      for (const auto& factory : registered_video_encoder_factories()) {
+        if (format.IsCodecInList(factory->GetSupportedFormats())) {
+          return factory->QueryCodecSupport(format, scalability_mode);
+        }
+      }
+      break;
+    case VideoCodecBackend::Software:
+      return Factory().QueryCodecSupport(
+          *webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(),
+                                            format),
+          scalability_mode);
+    default:
+      for (const auto& [backend, factory] : factories_) {
+        if (backend == backends.front() &&
+            format.IsCodecInList(factory->GetSupportedFormats())) {
+          return factory->QueryCodecSupport(format, scalability_mode);
+        }
+      }
+      break;
+  }
+  return webrtc::VideoEncoderFactory::CodecSupport{.is_supported = false};
 }
 
 std::unique_ptr<webrtc::VideoEncoder>
 VideoEncoderFactory::InternalFactory::Create(
     const webrtc::Environment& env,
     const webrtc::SdpVideoFormat& format) {
-  for (const auto& factory : factories_) {
-    for (const auto& supported_format : factory->GetSupportedFormats()) {
-      if (supported_format.IsSameCodec(format))
-        return factory->Create(env, format);
-    }
+  auto encoder = CreateEncoder(env, format);
+  if (PassthroughVideoEncoder::IsSupported(format)) {
+    // Frames of an EncodedVideoTrackSource skip the encoder
+    return std::make_unique<PassthroughVideoEncoder>(format,
+                                                     std::move(encoder));
   }
+  return encoder;
+}
 
-  auto original_format =
-      webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(), format);
-
-  if (original_format) {
-    return Factory().Create(env, *original_format);
+std::unique_ptr<webrtc::VideoEncoder>
+VideoEncoderFactory::InternalFactory::CreateEncoder(
+    const webrtc::Environment& env,
+    const webrtc::SdpVideoFormat& format) {
+  for (VideoCodecBackend backend : Backends(format)) {
+    if (auto encoder = CreateEncoder(backend, env, format)) {
+      return encoder;
+    }
   }
 
   RTC_LOG(LS_ERROR) << "No VideoEncoder found for " << format.name;
   return nullptr;
 }
 
+std::unique_ptr<webrtc::VideoEncoder>
+VideoEncoderFactory::InternalFactory::CreateEncoder(
+    VideoCodecBackend backend,
+    const webrtc::Environment& env,
+    const webrtc::SdpVideoFormat& format) {
+  switch (backend) {
+    case VideoCodecBackend::Rust:
+      for (const auto& factory : registered_video_encoder_factories()) {
+        if (format.IsCodecInList(factory->GetSupportedFormats())) {
+          if (auto encoder = factory->Create(env, format)) {
+            return encoder;
+          }
+        }
+      }
+      return nullptr;
+    case VideoCodecBackend::Software: {
+      auto original_format = webrtc::FuzzyMatchSdpVideoFormat(
+          Factory().GetSupportedFormats(), format);
+      return original_format ? Factory().Create(env, *original_format)
+                             : nullptr;
+    }
+    default:
+      for (const auto& [factory_backend, factory] : factories_) {
+        if (factory_backend == backend &&
+            format.IsCodecInList(factory->GetSupportedFormats())) {
+          return factory->Create(env, format);
+        }
+      }
+      return nullptr;
+  }
+}
+
 VideoEncoderFactory::VideoEncoderFactory() {
   internal_factory_ = std::make_unique<InternalFactory>();
 }
@@ -163,4 +301,19 @@ std::unique_ptr<webrtc::VideoEncoder> VideoEncoderFactory::Create(
   return encoder;
 }
 
+std::optional<webrtc::VideoEncoder::EncoderInfo>
+VideoEncoderFactory::GetEncoderInfo(const webrtc::Environment& env,
+                                    const webrtc::SdpVideoFormat& format) {
+  auto encoder = internal_factory_->CreateEncoder(env, format);
+  if (!encoder) {
+    return std::nullopt;
+  }
+  return encoder->GetEncoderInfo();
+}
+
+std::vector<VideoCodecBackend> VideoEncoderFactory::Backends(
+    const webrtc::SdpVideoFormat& format) const {
+  return internal_factory_->Backends(format);
+}
+
 }  // namespace livekit_ffi
@@ -16,7 +16,23 @@
 
 #include "livekit/video_frame_buffer.h"
 
+#include <algorithm>
+#include <cerrno>
+#include <cstring>
+#include <stdexcept>
+#include <string>
+
+#if defined(__linux__)
+#include <linux/dma-buf.h>
+#include <sys/ioctl.h>
+#include <sys/mman.h>
+#include <sys/stat.h>
+#include <unistd.h>
+#endif
+
 #include "api/make_ref_counted.h"
+#include "common_video/include/video_frame_buffer.h"
+#include "third_party/libyuv/include/libyuv/convert.h"
 
 namespace livekit_ffi {
 
@@ -294,6 +310,293 @@ std::unique_ptr<NV12Buffer> NV12Buffer::scale(int scaled_width,
           const_cast<webrtc::NV12BufferInterface*>(result->GetNV12())));
 }
 
+PackedRgbaBuffer::PackedRgbaBuffer(RgbaFormat format,
+                                   int width,
+                                   int height,
+                                   int stride)
+    : format_(format),
+      width_(width),
+      height_(height),
+      stride_(stride),
+      data_(static_cast<size_t>(stride) * height) {}
+
+webrtc::VideoFrameBuffer::Type PackedRgbaBuffer::type() const {
+  return Type::kNative;
+}
+
+int PackedRgbaBuffer::width() const {
+  return width_;
+}
+
+int PackedRgbaBuffer::height() const {
+  return height_;
+}
+
+webrtc::scoped_refptr<webrtc::I420BufferInterface> PackedRgbaBuffer::ToI420() {
+  webrtc::scoped_refptr<webrtc::I420Buffer> i420 =
+      webrtc::I420Buffer::Create(width_, height_);
+
+  auto convert = libyuv::ARGBToI420;
+  switch (format_) {
+    case RgbaFormat::Bgra:
+      convert = libyuv::BGRAToI420;
+      break;
+    case RgbaFormat::Abgr:
+      convert = libyuv::ABGRToI420;
+      break;
+    case RgbaFormat::Rgba:
+      convert = libyuv::RGBAToI420;
+      break;
+    default:
+      break;
+  }
+
+  convert(data_.data(), stride_, i420->MutableDataY(), i420->StrideY(),
+          i420->MutableDataU(), i420->StrideU(), i420->MutableDataV(),
+          i420->StrideV(), width_, height_);
+  return i420;
+}
+
+RgbaFormat PackedRgbaBuffer::format() const {
+  return format_;
+}
+
+int PackedRgbaBuffer::stride() const {
+  return stride_;
+}
+
+const uint8_t* PackedRgbaBuffer::data() const {
+  return data_.data();
+}
+
+RgbaBuffer::RgbaBuffer(webrtc::scoped_refptr<PackedRgbaBuffer> buffer)
+    : VideoFrameBuffer(buffer) {}
+
+RgbaFormat RgbaBuffer::format() const {
+  return buffer()->format();
+}
+
+unsigned int RgbaBuffer::stride() const {
+  return buffer()->stride();
+}
+
+const uint8_t* RgbaBuffer::data() const {
+  return buffer()->data();
+}
+
+PackedRgbaBuffer* RgbaBuffer::buffer() const {
+  return static_cast<PackedRgbaBuffer*>(buffer_.get());
+}
+
+ExternalI420Buffer::ExternalI420Buffer(int width,
+                                       int height,
+                                       const uint8_t* data_y,
+                                       int stride_y,
+                                       const uint8_t* data_u,
+                                       int stride_u,
+                                       const uint8_t* data_v,
+                                       int stride_v,
+                                       rust::Box<ExternalBufferRelease> release)
+    : width_(width),
+      height_(height),
+      data_y_(data_y),
+      stride_y_(stride_y),
+      data_u_(data_u),
+      stride_u_(stride_u),
+      data_v_(data_v),
+      stride_v_(stride_v),
+      release_(std::move(release)) {}
+
+ExternalI420Buffer::~ExternalI420Buffer() {
+  release_->release();
+}
+
+int ExternalI420Buffer::width() const {
+  return width_;
+}
+
+int ExternalI420Buffer::height() const {
+  return height_;
+}
+
+const uint8_t* ExternalI420Buffer::DataY() const {
+  return data_y_;
+}
+
+const uint8_t* ExternalI420Buffer::DataU() const {
+  return data_u_;
+}
+
+const uint8_t* ExternalI420Buffer::DataV() const {
+  return data_v_;
+}
+
+int ExternalI420Buffer::StrideY() const {
+  return stride_y_;
+}
+
+int ExternalI420Buffer::StrideU() const {
+  return stride_u_;
+}
+
+int ExternalI420Buffer::StrideV() const {
+  return stride_v_;
+}
+
+ExternalNV12Buffer::ExternalNV12Buffer(int width,
+                                       int height,
+                                       const uint8_t* data_y,
+                                       int stride_y,
+                                       const uint8_t* data_uv,
+                                       int stride_uv,
+                                       rust::Box<ExternalBufferRelease> release)
+    : width_(width),
+      height_(height),
+      data_y_(data_y),
+      stride_y_(stride_y),
+      data_uv_(data_uv),
+      stride_uv_(stride_uv),
+      release_(std::move(release)) {}
+
+ExternalNV12Buffer::~ExternalNV12Buffer() {
+  release_->release();
+}
+
+int ExternalNV12Buffer::width() const {
+  return width_;
+}
+
+int ExternalNV12Buffer::height() const {
+  return height_;
+}
+
+const uint8_t* ExternalNV12Buffer::DataY() const {
+  return data_y_;
+}
+
+const uint8_t* ExternalNV12Buffer::DataUV() const {
+  return data_uv_;
+}
+
+int ExternalNV12Buffer::StrideY() const {
+  return stride_y_;
+}
+
+int ExternalNV12Buffer::StrideUV() const {
+  return stride_uv_;
+}
+
+webrtc::scoped_refptr<webrtc::I420BufferInterface>
+ExternalNV12Buffer::ToI420() {
+  webrtc::scoped_refptr<webrtc::I420Buffer> i420 =
+      webrtc::I420Buffer::Create(width_, height_);
+  libyuv::NV12ToI420(data_y_, stride_y_, data_uv_, stride_uv_,
+                     i420->MutableDataY(), i420->StrideY(),
+                     i420->MutableDataU(), i420->StrideU(),
+                     i420->MutableDataV(), i420->StrideV(), width_, height_);
+  return i420;
+}
+
+namespace {
+
+// Returns a buffer only referenced by the pool, allocating a new one with
+// `args` if there is none and the pool is not full yet.
+template <typename T, typename... Args>
+webrtc::scoped_refptr<T> acquire_buffer(
+    std::vector<webrtc::scoped_refptr<webrtc::RefCountedObject<T>>>& buffers,
+    size_t max_buffers,
+    Args... args) {
+  for (const auto& buffer : buffers) {
+    if (buffer->HasOneRef())
+      return buffer;
+  }
+
+  if (buffers.size() >= max_buffers)
+    return nullptr;
+
+  auto buffer = webrtc::scoped_refptr<webrtc::RefCountedObject<T>>(
+      new webrtc::RefCountedObject<T>(args...));
+  buffers.push_back(buffer);
+  return buffer;
+}
+
+template <typename T>
+void release_unused_buffers(
+    std::vector<webrtc::scoped_refptr<webrtc::RefCountedObject<T>>>& buffers) {
+  buffers.erase(std::remove_if(buffers.begin(), buffers.end(),
+                               [](const auto& buffer) {
+                                 return buffer->HasOneRef();
+                               }),
+                buffers.end());
+}
+
+}  // namespace
+
+I420BufferPool::I420BufferPool(int width,
+                               int height,
+                               int stride_y,
+                               int stride_u,
+                               int stride_v,
+                               size_t max_buffers)
+    : width_(width),
+      height_(height),
+      stride_y_(stride_y),
+      stride_u_(stride_u),
+      stride_v_(stride_v),
+      max_buffers_(max_buffers) {}
+
+std::unique_ptr<I420Buffer> I420BufferPool::acquire() const {
+  webrtc::MutexLock lock(&mutex_);
+  webrtc::scoped_refptr<webrtc::I420Buffer> buffer =
+      acquire_buffer(buffers_, max_buffers_, width_, height_, stride_y_,
+                     stride_u_, stride_v_);
+  if (!buffer)
+    return nullptr;
+
+  return std::make_unique<I420Buffer>(buffer);
+}
+
+size_t I420BufferPool::allocated() const {
+  webrtc::MutexLock lock(&mutex_);
+  return buffers_.size();
+}
+
+void I420BufferPool::release_unused() const {
+  webrtc::MutexLock lock(&mutex_);
+  release_unused_buffers(buffers_);
+}
+
+NV12BufferPool::NV12BufferPool(int width,
+                               int height,
+                               int stride_y,
+                               int stride_uv,
+                               size_t max_buffers)
+    : width_(width),
+      height_(height),
+      stride_y_(stride_y),
+      stride_uv_(stride_uv),
+      max_buffers_(max_buffers) {}
+
+std::unique_ptr<NV12Buffer> NV12BufferPool::acquire() const {
+  webrtc::MutexLock lock(&mutex_);
+  webrtc::scoped_refptr<webrtc::NV12Buffer> buffer = acquire_buffer(
+      buffers_, max_buffers_, width_, height_, stride_y_, stride_uv_);
+  if (!buffer)
+    return nullptr;
+
+  return std::make_unique<NV12Buffer>(buffer);
+}
+
+size_t NV12BufferPool::allocated() const {
+  webrtc::MutexLock lock(&mutex_);
+  return buffers_.size();
+}
+
+void NV12BufferPool::release_unused() const {
+  webrtc::MutexLock lock(&mutex_);
+  release_unused_buffers(buffers_);
+}
+
 std::unique_ptr<I420Buffer> copy_i420_buffer(
     const std::unique_ptr<I420Buffer>& i420) {
   return std::make_unique<I420Buffer>(webrtc::I420Buffer::Copy(*i420->get()));
@@ -343,6 +646,149 @@ std::unique_ptr<NV12Buffer> new_nv12_buffer(int width,
       webrtc::NV12Buffer::Create(width, height, stride_y, stride_uv));
 }
 
+std::unique_ptr<I420ABuffer> new_i420a_buffer(int width,
+                                              int height,
+                                              int stride_y,
+                                              int stride_u,
+                                              int stride_v,
+                                              int stride_a) {
+  // WebRTC only wraps existing planes as I420A, the lambda keeps them alive
+  webrtc::scoped_refptr<webrtc::I420Buffer> yuv =
+      webrtc::I420Buffer::Create(width, height, stride_y, stride_u, stride_v);
+  auto alpha = std::make_shared<std::vector<uint8_t>>(
+      static_cast<size_t>(stride_a) * height);
+  return std::make_unique<I420ABuffer>(webrtc::WrapI420ABuffer(
+      width, height, yuv->DataY(), yuv->StrideY(), yuv->DataU(),
+      yuv->StrideU(), yuv->DataV(), yuv->StrideV(), alpha->data(), stride_a,
+      [yuv, alpha] {}));
+}
+
+std::unique_ptr<RgbaBuffer> new_rgba_buffer(RgbaFormat format,
+                                            int width,
+                                            int height,
+                                            int stride) {
+  return std::make_unique<RgbaBuffer>(
+      webrtc::make_ref_counted<PackedRgbaBuffer>(format, width, height,
+                                                 stride));
+}
+
+std::unique_ptr<I420Buffer> new_external_i420_buffer(
+    int width,
+    int height,
+    const uint8_t* data_y,
+    int stride_y,
+    const uint8_t* data_u,
+    int stride_u,
+    const uint8_t* data_v,
+    int stride_v,
+    rust::Box<ExternalBufferRelease> release) {
+  return std::make_unique<I420Buffer>(
+      webrtc::make_ref_counted<ExternalI420Buffer>(
+          width, height, data_y, stride_y, data_u, stride_u, data_v, stride_v,
+          std::move(release)));
+}
+
+std::unique_ptr<NV12Buffer> new_external_nv12_buffer(
+    int width,
+    int height,
+    const uint8_t* data_y,
+    int stride_y,
+    const uint8_t* data_uv,
+    int stride_uv,
+    rust::Box<ExternalBufferRelease> release) {
+  return std::make_unique<NV12Buffer>(
+      webrtc::make_ref_counted<ExternalNV12Buffer>(
+          width, height, data_y, stride_y, data_uv, stride_uv,
+          std::move(release)));
+}
+
+#if defined(__linux__)
+
+namespace {
+
+// The fd shares its offset with the descriptor it was duplicated from, so it
+// is restored after probing. dmabufs can't report it (they only seek to 0 from
+// the start or the end) and are rewound instead.
+off_t probe_fd_size(int fd) {
+  off_t offset = lseek(fd, 0, SEEK_CUR);
+  off_t size = lseek(fd, 0, SEEK_END);
+  lseek(fd, offset < 0 ? 0 : offset, SEEK_SET);
+  return size;
+}
+
+}  // namespace
+
+// The sync ioctls make the CPU view coherent with the device for dmabufs.
+// Other mappable fds (e.g. memfd) don't support them (ENOTTY), a plain mmap is
+// enough.
+const uint8_t* map_dmabuf(int fd, size_t size, bool writable) {
+  // Pages past the end of the fd would fault (SIGBUS) when read.
+  // Depending on the kernel, dmabufs only report their size through lseek.
+  struct stat st;
+  if (fstat(fd, &st) != 0)
+    throw std::runtime_error(std::string("fstat failed: ") +
+                             std::strerror(errno));
+  off_t fd_size = st.st_size > 0 ? st.st_size : probe_fd_size(fd);
+  if (fd_size < 0 || static_cast<size_t>(fd_size) < size)
+    throw std::runtime_error("the fd is smaller than the planes");
+
+  int prot = writable ? PROT_READ | PROT_WRITE : PROT_READ;
+  void* data = mmap(nullptr, size, prot, MAP_SHARED, fd, 0);
+  if (data == MAP_FAILED)
+    throw std::runtime_error(std::string("mmap failed: ") +
+                             std::strerror(errno));
+
+  struct dma_buf_sync sync = {
+      DMA_BUF_SYNC_START | (writable ? DMA_BUF_SYNC_RW : DMA_BUF_SYNC_READ)};
+  int result;
+  do {
+    result = ioctl(fd, DMA_BUF_IOCTL_SYNC, &sync);
+  } while (result != 0 && (errno == EINTR || errno == EAGAIN));
+  if (result != 0 && errno != ENOTTY) {
+    int error = errno;
+    munmap(data, size);
+    throw std::runtime_error(std::string("DMA_BUF_IOCTL_SYNC failed: ") +
+                             std::strerror(error));
+  }
+  return static_cast<const uint8_t*>(data);
+}
+
+void unmap_dmabuf(int fd, const uint8_t* data, size_t size, bool writable) {
+  struct dma_buf_sync sync = {
+      DMA_BUF_SYNC_END | (writable ? DMA_BUF_SYNC_RW : DMA_BUF_SYNC_READ)};
+  ioctl(fd, DMA_BUF_IOCTL_SYNC, &sync);
+  munmap(const_cast<uint8_t*>(data), size);
+}
+
+#else
+
+const uint8_t* map_dmabuf(int fd, size_t size, bool writable) {
+  throw std::runtime_error("dmabuf is only supported on Linux");
+}
+
+void unmap_dmabuf(int fd, const uint8_t* data, size_t size, bool writable) {}
+
+#endif
+
+std::unique_ptr<I420BufferPool> new_i420_buffer_pool(int width,
+                                                    int height,
+                                                    int stride_y,
+                                                    int stride_u,
+                                                    int stride_v,
+                                                    size_t max_buffers) {
+  return std::make_unique<I420BufferPool>(width, height, stride_y, stride_u,
+                                          stride_v, max_buffers);
+}
+
+std::unique_ptr<NV12BufferPool> new_nv12_buffer_pool(int width,
+                                                    int height,
+                                                    int stride_y,
+                                                    int stride_uv,
+                                                    size_t max_buffers) {
+  return std::make_unique<NV12BufferPool>(width, height, stride_y, stride_uv,
+                                          max_buffers);
+}
+
 #ifndef __APPLE__
 
 std::unique_ptr<VideoFrameBuffer> new_native_buffer_from_platform_image_buffer(
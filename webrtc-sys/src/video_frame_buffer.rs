@@ -28,6 +28,15 @@ pub mod ffi {
         NV12,
     }
 
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum RgbaFormat {
+        Argb,
+        Bgra,
+        Abgr,
+        Rgba,
+    }
+
     unsafe extern "C++" {
         include!("livekit/video_frame_buffer.h");
 
@@ -43,6 +52,9 @@ pub mod ffi {
         type I444Buffer;
         type I010Buffer;
         type NV12Buffer;
+        type RgbaBuffer;
+        type I420BufferPool;
+        type NV12BufferPool;
         type PlatformImageBuffer;
 
         fn buffer_type(self: &VideoFrameBuffer) -> VideoFrameBufferType;
@@ -103,6 +115,10 @@ pub mod ffi {
         fn scale(self: &NV12Buffer, scaled_width: i32, scaled_height: i32)
             -> UniquePtr<NV12Buffer>;
 
+        fn format(self: &RgbaBuffer) -> RgbaFormat;
+        fn stride(self: &RgbaBuffer) -> u32;
+        fn data(self: &RgbaBuffer) -> *const u8;
+
         fn copy_i420_buffer(i420: &UniquePtr<I420Buffer>) -> UniquePtr<I420Buffer>;
         fn new_i420_buffer(
             width: i32,
@@ -143,6 +159,74 @@ pub mod ffi {
             stride_uv: i32,
         ) -> UniquePtr<NV12Buffer>;
 
+        fn new_i420a_buffer(
+            width: i32,
+            height: i32,
+            stride_y: i32,
+            stride_u: i32,
+            stride_v: i32,
+            stride_a: i32,
+        ) -> UniquePtr<I420ABuffer>;
+
+        fn new_rgba_buffer(
+            format: RgbaFormat,
+            width: i32,
+            height: i32,
+            stride: i32,
+        ) -> UniquePtr<RgbaBuffer>;
+
+        fn acquire(self: &I420BufferPool) -> UniquePtr<I420Buffer>;
+        fn allocated(self: &I420BufferPool) -> usize;
+        fn release_unused(self: &I420BufferPool);
+
+        fn acquire(self: &NV12BufferPool) -> UniquePtr<NV12Buffer>;
+        fn allocated(self: &NV12BufferPool) -> usize;
+        fn release_unused(self: &NV12BufferPool);
+
+        unsafe fn new_external_i420_buffer(
+            width: i32,
+            height: i32,
+            data_y: *const u8,
+            stride_y: i32,
+            data_u: *const u8,
+            stride_u: i32,
+            data_v: *const u8,
+            stride_v: i32,
+            release: Box<ExternalBufferRelease>,
+        ) -> UniquePtr<I420Buffer>;
+
+        unsafe fn new_external_nv12_buffer(
+            width: i32,
+            height: i32,
+            data_y: *const u8,
+            stride_y: i32,
+            data_uv: *const u8,
+            stride_uv: i32,
+            release: Box<ExternalBufferRelease>,
+        ) -> UniquePtr<NV12Buffer>;
+
+        /// Maps `size` bytes of the fd, shared with the other mappings of the buffer.
+        /// Fails if the fd is smaller than `size`.
+        fn map_dmabuf(fd: i32, size: usize, writable: bool) -> Result<*const u8>;
+        unsafe fn unmap_dmabuf(fd: i32, data: *const u8, size: usize, writable: bool);
+
+        fn new_i420_buffer_pool(
+            width: i32,
+            height: i32,
+            stride_y: i32,
+            stride_u: i32,
+            stride_v: i32,
+            max_buffers: usize,
+        ) -> UniquePtr<I420BufferPool>;
+
+        fn new_nv12_buffer_pool(
+            width: i32,
+            height: i32,
+            stride_y: i32,
+            stride_uv: i32,
+            max_buffers: usize,
+        ) -> UniquePtr<NV12BufferPool>;
+
         unsafe fn new_native_buffer_from_platform_image_buffer(
             platform_native_buffer: *mut PlatformImageBuffer,
         ) -> UniquePtr<VideoFrameBuffer>;
@@ -151,6 +235,7 @@ pub mod ffi {
         ) -> *mut PlatformImageBuffer;
 
         unsafe fn yuv_to_vfb(yuv: *const PlanarYuvBuffer) -> *const VideoFrameBuffer;
+        unsafe fn rgba_to_vfb(rgba: *const RgbaBuffer) -> *const VideoFrameBuffer;
         unsafe fn biyuv_to_vfb(yuv: *const BiplanarYuvBuffer) -> *const VideoFrameBuffer;
         unsafe fn yuv8_to_yuv(yuv8: *const PlanarYuv8Buffer) -> *const PlanarYuvBuffer;
         unsafe fn yuv16b_to_yuv(yuv16b: *const PlanarYuv16BBuffer) -> *const PlanarYuvBuffer;
@@ -164,6 +249,12 @@ pub mod ffi {
 
         fn _unique_video_frame_buffer() -> UniquePtr<VideoFrameBuffer>;
     }
+
+    extern "Rust" {
+        type ExternalBufferRelease;
+
+        fn release(self: &mut ExternalBufferRelease);
+    }
 }
 
 impl_thread_safety!(ffi::VideoFrameBuffer, Send + Sync);
@@ -178,3 +269,23 @@ impl_thread_safety!(ffi::I422Buffer, Send + Sync);
 impl_thread_safety!(ffi::I444Buffer, Send + Sync);
 impl_thread_safety!(ffi::I010Buffer, Send + Sync);
 impl_thread_safety!(ffi::NV12Buffer, Send + Sync);
+impl_thread_safety!(ffi::RgbaBuffer, Send + Sync);
+impl_thread_safety!(ffi::I420BufferPool, Send + Sync);
+impl_thread_safety!(ffi::NV12BufferPool, Send + Sync);
+
+/// Called when a buffer wrapping external memory is destroyed, possibly on a WebRTC thread
+pub struct ExternalBufferRelease {
+    release: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl ExternalBufferRelease {
+    pub fn new(release: impl FnOnce() + Send + 'static) -> Self {
+        Self { release: Some(Box::new(release)) }
+    }
+
+    fn release(&mut self) {
+        if let Some(release) = self.release.take() {
+            release();
+        }
+    }
+}
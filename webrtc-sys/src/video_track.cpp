@@ -18,6 +18,7 @@
 
 #include <algorithm>
 #include <iostream>
+#include <limits>
 #include <memory>
 
 #include "api/media_stream_interface.h"
@@ -106,7 +107,10 @@ std::shared_ptr<NativeVideoSink> new_native_video_sink(
 
 VideoTrackSource::InternalSource::InternalSource(
     const VideoResolution& resolution)
-    : webrtc::AdaptedVideoTrackSource(4), resolution_(resolution) {}
+    : webrtc::AdaptedVideoTrackSource(4),
+      resolution_(resolution),
+      adaptation_{false, 0, false, VideoResolution{0, 0}, true},
+      adapter_(4) {}
 
 VideoTrackSource::InternalSource::~InternalSource() {}
 
@@ -132,6 +136,55 @@ VideoResolution VideoTrackSource::InternalSource::video_resolution() const {
   return resolution_;
 }
 
+void VideoTrackSource::InternalSource::set_adaptation(
+    const VideoAdaptation& adaptation) {
+  webrtc::MutexLock lock(&mutex_);
+  adaptation_ = adaptation;
+
+  // Clamped to the range of the adapter, a frame rate of 0 would drop every
+  // frame.
+  constexpr uint64_t kMaxInt = std::numeric_limits<int>::max();
+  std::optional<int> max_pixel_count;
+  if (adaptation.has_max_resolution) {
+    uint64_t pixel_count =
+        static_cast<uint64_t>(adaptation.max_resolution.width) *
+        adaptation.max_resolution.height;
+    max_pixel_count = static_cast<int>(std::min(pixel_count, kMaxInt));
+  }
+
+  std::optional<int> max_fps;
+  if (adaptation.has_max_fps) {
+    max_fps = static_cast<int>(
+        std::clamp<uint64_t>(adaptation.max_fps, 1, kMaxInt));
+  }
+
+  // Without a target aspect ratio, the frames are downscaled but not cropped
+  video_adapter()->OnOutputFormatRequest(std::nullopt, max_pixel_count,
+                                         max_fps);
+  adapter_.OnOutputFormatRequest(std::nullopt, max_pixel_count, max_fps);
+}
+
+bool VideoTrackSource::InternalSource::adapt_frame_ignoring_wants(
+    int width,
+    int height,
+    int64_t time_us,
+    int* out_width,
+    int* out_height,
+    int* crop_width,
+    int* crop_height,
+    int* crop_x,
+    int* crop_y) {
+  if (!adapter_.AdaptFrameResolution(
+          width, height, time_us * webrtc::kNumNanosecsPerMicrosec,
+          crop_width, crop_height, out_width, out_height)) {
+    return false;
+  }
+
+  *crop_x = (width - *crop_width) / 2;
+  *crop_y = (height - *crop_height) / 2;
+  return true;
+}
+
 bool VideoTrackSource::InternalSource::on_captured_frame(
     const webrtc::VideoFrame& frame) {
   webrtc::MutexLock lock(&mutex_);
@@ -147,10 +200,19 @@ bool VideoTrackSource::InternalSource::on_captured_frame(
                                   static_cast<uint32_t>(buffer->height())};
   }
 
+  // Frames are dropped based on the capture timestamps, the aligned ones
+  // follow the time at which the frames are delivered instead
   int adapted_width, adapted_height, crop_width, crop_height, crop_x, crop_y;
-  if (!AdaptFrame(buffer->width(), buffer->height(), aligned_timestamp_us,
-                  &adapted_width, &adapted_height, &crop_width, &crop_height,
-                  &crop_x, &crop_y)) {
+  bool adapted =
+      adaptation_.follow_sink_wants
+          ? AdaptFrame(buffer->width(), buffer->height(), frame.timestamp_us(),
+                       &adapted_width, &adapted_height, &crop_width,
+                       &crop_height, &crop_x, &crop_y)
+          : adapt_frame_ignoring_wants(
+                buffer->width(), buffer->height(), frame.timestamp_us(),
+                &adapted_width, &adapted_height, &crop_width, &crop_height,
+                &crop_x, &crop_y);
+  if (!adapted) {
     return false;
   }
 
@@ -175,6 +237,26 @@ bool VideoTrackSource::InternalSource::on_captured_frame(
   return true;
 }
 
+bool VideoTrackSource::InternalSource::on_captured_encoded_frame(
+    const webrtc::VideoFrame& frame) {
+  webrtc::MutexLock lock(&mutex_);
+
+  int64_t aligned_timestamp_us = timestamp_aligner_.TranslateTimestamp(
+      frame.timestamp_us(), webrtc::TimeMicros());
+
+  // Encoded frames aren't scaled, the source has the size of the last one
+  resolution_ = VideoResolution{static_cast<uint32_t>(frame.width()),
+                                static_cast<uint32_t>(frame.height())};
+
+  OnFrame(webrtc::VideoFrame::Builder()
+              .set_video_frame_buffer(frame.video_frame_buffer())
+              .set_rotation(frame.rotation())
+              .set_timestamp_us(aligned_timestamp_us)
+              .build());
+
+  return true;
+}
+
 VideoTrackSource::VideoTrackSource(const VideoResolution& resolution) {
   source_ = webrtc::make_ref_counted<InternalSource>(resolution);
 }
@@ -189,6 +271,11 @@ bool VideoTrackSource::on_captured_frame(
   return source_->on_captured_frame(rtc_frame);
 }
 
+void VideoTrackSource::set_adaptation(
+    const VideoAdaptation& adaptation) const {
+  source_->set_adaptation(adaptation);
+}
+
 webrtc::scoped_refptr<VideoTrackSource::InternalSource> VideoTrackSource::get()
     const {
   return source_;
@@ -42,6 +42,15 @@ pub mod ffi {
         pub height: u32,
     }
 
+    #[derive(Debug)]
+    pub struct VideoAdaptation {
+        pub has_max_fps: bool,
+        pub max_fps: u32,
+        pub has_max_resolution: bool,
+        pub max_resolution: VideoResolution,
+        pub follow_sink_wants: bool,
+    }
+
     extern "C++" {
         include!("livekit/video_frame.h");
         include!("livekit/media_stream_track.h");
@@ -66,6 +75,7 @@ pub mod ffi {
         fn new_native_video_sink(observer: Box<VideoSinkWrapper>) -> SharedPtr<NativeVideoSink>;
 
         fn video_resolution(self: &VideoTrackSource) -> VideoResolution;
+        fn set_adaptation(self: &VideoTrackSource, adaptation: &VideoAdaptation);
         fn on_captured_frame(self: &VideoTrackSource, frame: &UniquePtr<VideoFrame>) -> bool;
         fn new_video_track_source(resolution: &VideoResolution) -> SharedPtr<VideoTrackSource>;
         fn video_to_media(track: SharedPtr<VideoTrack>) -> SharedPtr<MediaStreamTrack>;
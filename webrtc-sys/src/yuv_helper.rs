@@ -308,5 +308,433 @@ pub mod ffi {
             width: i32,
             height: i32,
         ) -> Result<()>;
+
+        unsafe fn i420_scale(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            dst_width: i32,
+            dst_height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i422_scale(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            dst_width: i32,
+            dst_height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i444_scale(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            dst_width: i32,
+            dst_height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i010_scale(
+            src_y: *const u16,
+            src_stride_y: i32,
+            src_u: *const u16,
+            src_stride_u: i32,
+            src_v: *const u16,
+            src_stride_v: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: *mut u16,
+            dst_stride_y: i32,
+            dst_u: *mut u16,
+            dst_stride_u: i32,
+            dst_v: *mut u16,
+            dst_stride_v: i32,
+            dst_width: i32,
+            dst_height: i32,
+        ) -> Result<()>;
+
+        unsafe fn nv12_scale(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_uv: *const u8,
+            src_stride_uv: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_uv: *mut u8,
+            dst_stride_uv: i32,
+            dst_width: i32,
+            dst_height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_rotate(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+            rotation: i32,
+        ) -> Result<()>;
+
+        unsafe fn i422_rotate(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+            rotation: i32,
+        ) -> Result<()>;
+
+        unsafe fn i444_rotate(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+            rotation: i32,
+        ) -> Result<()>;
+
+        unsafe fn i010_rotate(
+            src_y: *const u16,
+            src_stride_y: i32,
+            src_u: *const u16,
+            src_stride_u: i32,
+            src_v: *const u16,
+            src_stride_v: i32,
+            dst_y: *mut u16,
+            dst_stride_y: i32,
+            dst_u: *mut u16,
+            dst_stride_u: i32,
+            dst_v: *mut u16,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+            rotation: i32,
+        ) -> Result<()>;
+
+        unsafe fn nv12_rotate(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_uv: *const u8,
+            src_stride_uv: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_uv: *mut u8,
+            dst_stride_uv: i32,
+            width: i32,
+            height: i32,
+            rotation: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_mirror(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i422_mirror(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i444_mirror(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i010_mirror(
+            src_y: *const u16,
+            src_stride_y: i32,
+            src_u: *const u16,
+            src_stride_u: i32,
+            src_v: *const u16,
+            src_stride_v: i32,
+            dst_y: *mut u16,
+            dst_stride_y: i32,
+            dst_u: *mut u16,
+            dst_stride_u: i32,
+            dst_v: *mut u16,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn nv12_mirror(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_uv: *const u8,
+            src_stride_uv: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_uv: *mut u8,
+            dst_stride_uv: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn rgba_to_i420(
+            src_rgba: *const u8,
+            src_stride_rgba: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn bgra_to_i420(
+            src_bgra: *const u8,
+            src_stride_bgra: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_to_i422(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_to_i444(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_to_i010(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u16,
+            dst_stride_y: i32,
+            dst_u: *mut u16,
+            dst_stride_u: i32,
+            dst_v: *mut u16,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420a_to_argb(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            src_a: *const u8,
+            src_stride_a: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420a_to_abgr(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            src_a: *const u8,
+            src_stride_a: i32,
+            dst_abgr: *mut u8,
+            dst_stride_abgr: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_extract_alpha(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            dst_a: *mut u8,
+            dst_stride_a: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_to_abgr(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            dst_abgr: *mut u8,
+            dst_stride_abgr: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_to_bgra(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            dst_bgra: *mut u8,
+            dst_stride_bgra: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_to_rgba(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            dst_rgba: *mut u8,
+            dst_stride_rgba: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn abgr_to_argb(
+            src_abgr: *const u8,
+            src_stride_abgr: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn bgra_to_argb(
+            src_bgra: *const u8,
+            src_stride_bgra: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn rgba_to_argb(
+            src_rgba: *const u8,
+            src_stride_rgba: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
     }
 }